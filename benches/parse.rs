@@ -0,0 +1,40 @@
+#![feature(test)]
+
+extern crate test;
+
+use std::fs;
+
+use serde_scala::Scale;
+use test::Bencher;
+
+/// The text of every scale in the archive.
+fn archive() -> Vec<String>
+{
+    let Ok(entries) = fs::read_dir("scl")
+    else
+    {
+        return vec![]
+    };
+    entries.filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("scl")))
+        .filter_map(|path| fs::read(path).ok())
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .collect()
+}
+
+#[bench]
+fn parse_archive(b: &mut Bencher)
+{
+    let files = archive();
+    b.bytes = files.iter().map(|text| text.len() as u64).sum();
+    b.iter(|| files.iter().filter(|text| text.parse::<Scale>().is_ok()).count());
+}
+
+#[bench]
+fn parse_large_scale(b: &mut Bencher)
+{
+    let text = Scale::edo(1000).to_string();
+    b.bytes = text.len() as u64;
+    b.iter(|| text.parse::<Scale>().unwrap());
+}
@@ -0,0 +1,33 @@
+#![feature(test)]
+
+extern crate test;
+
+use serde_scala::Scale;
+use test::Bencher;
+
+fn edos() -> Vec<Scale>
+{
+    (1..=200).map(Scale::edo).collect()
+}
+
+#[bench]
+fn display_edos(b: &mut Bencher)
+{
+    let scales = edos();
+    b.iter(|| scales.iter().map(|scale| scale.to_string().len()).sum::<usize>());
+}
+
+#[bench]
+fn write_fast_edos(b: &mut Bencher)
+{
+    let scales = edos();
+    let mut bytes = vec![];
+    b.iter(|| {
+        bytes.clear();
+        for scale in &scales
+        {
+            scale.write_fast(&mut bytes).unwrap();
+        }
+        bytes.len()
+    });
+}
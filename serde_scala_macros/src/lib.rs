@@ -0,0 +1,95 @@
+use std::{fs, path::PathBuf};
+
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+use serde_scala::{Pitch, Scale};
+use syn::{parse_macro_input, LitStr};
+
+/// Parses a .scl file at compile time and expands into the resulting `serde_scala::Scale`.
+///
+/// The path is relative to the root of the crate invoking the macro, like `CARGO_MANIFEST_DIR`.
+/// A missing or malformed file fails the build.
+///
+/// ```ignore
+/// let ptolemy: serde_scala::Scale = serde_scala_macros::include_scl!("scl/ptolemy.scl");
+/// ```
+#[proc_macro]
+pub fn include_scl(input: proc_macro::TokenStream) -> proc_macro::TokenStream
+{
+    let path = parse_macro_input!(input as LitStr);
+    match expand(&path)
+    {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into()
+    }
+}
+
+fn expand(path: &LitStr) -> syn::Result<TokenStream>
+{
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let full_path = PathBuf::from(manifest_dir).join(path.value());
+
+    let bytes = fs::read(&full_path)
+        .map_err(|error| syn::Error::new(path.span(), format!("couldn't read {}: {}", full_path.display(), error)))?;
+    let contents = String::from_utf8(bytes)
+        .map_err(|error| syn::Error::new(path.span(), format!("{} is not valid UTF-8: {}", full_path.display(), error)))?;
+    let scale: Scale = contents.parse()
+        .map_err(|error| syn::Error::new(path.span(), format!("{} is not a valid scale: {:?}", full_path.display(), error)))?;
+
+    let pitches = scale.pitches.iter()
+        .map(|pitch| match *pitch
+        {
+            Pitch::Cents(cents) if !cents.is_finite() => Err(syn::Error::new(path.span(), format!("{} contains a non-finite pitch", full_path.display()))),
+            Pitch::Cents(cents) => {
+                let cents = Literal::f64_suffixed(cents);
+                Ok(quote!(::serde_scala::Pitch::Cents(#cents)))
+            },
+            Pitch::Ratio(ratio) => {
+                let numer = Literal::u128_suffixed(*ratio.numer());
+                let denom = Literal::u128_suffixed(*ratio.denom());
+                Ok(quote!(::serde_scala::Pitch::Ratio(::serde_scala::__private::Ratio::new_raw(#numer, #denom))))
+            }
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let labels = (0..scale.pitches.len())
+        .filter_map(|i| Some((i, scale.label(i)?)))
+        .map(|(i, label)| quote!(scale.set_label(#i, #label);));
+
+    let cents_text = scale.cents_text.iter().map(|text| optional_string(text.as_deref()));
+
+    let metadata = &scale.metadata;
+    let file_name = optional_string(metadata.file_name.as_deref());
+    let author = optional_string(metadata.author.as_deref());
+    let source = optional_string(metadata.source.as_deref());
+    let date = optional_string(metadata.date.as_deref());
+    let comments = &metadata.comments;
+
+    let name = &scale.name;
+    let full_path = full_path.to_string_lossy();
+    Ok(quote! {
+        {
+            const _: &[u8] = ::core::include_bytes!(#full_path);
+            let mut scale = ::serde_scala::Scale::new(::std::string::String::from(#name), ::std::vec![#(#pitches),*]);
+            #(#labels)*
+            scale.cents_text = ::std::vec![#(#cents_text),*];
+            scale.metadata = ::serde_scala::ScaleMetadata {
+                file_name: #file_name,
+                author: #author,
+                source: #source,
+                date: #date,
+                comments: ::std::vec![#(::std::string::String::from(#comments)),*]
+            };
+            scale
+        }
+    })
+}
+
+fn optional_string(text: Option<&str>) -> TokenStream
+{
+    match text
+    {
+        Some(text) => quote!(::std::option::Option::Some(::std::string::String::from(#text))),
+        None => quote!(::std::option::Option::None)
+    }
+}
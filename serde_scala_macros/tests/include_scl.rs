@@ -0,0 +1,26 @@
+use serde_scala::Scale;
+use serde_scala_macros::include_scl;
+
+#[test]
+fn embeds_scale()
+{
+    let embedded: Scale = include_scl!("../scl/ptolemy.scl");
+    let parsed: Scale = include_str!("../../scl/ptolemy.scl").parse().unwrap();
+
+    assert_eq!(embedded, parsed);
+    assert_eq!(embedded.pitches.len(), 7);
+}
+
+#[test]
+fn keeps_cents_text_and_metadata()
+{
+    let embedded: Scale = include_scl!("../scl/05-19.scl");
+    let parsed: Scale = include_str!("../../scl/05-19.scl").parse().unwrap();
+    assert_eq!(embedded.to_string(), parsed.to_string());
+
+    let embedded: Scale = include_scl!("tests/annotated.scl");
+    let parsed: Scale = include_str!("annotated.scl").parse().unwrap();
+    assert_eq!(embedded.to_string(), parsed.to_string());
+    assert_eq!(embedded.metadata, parsed.metadata);
+    assert_eq!(embedded.metadata.author.as_deref(), Some("Someone"));
+}
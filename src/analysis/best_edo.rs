@@ -0,0 +1,90 @@
+use crate::Scale;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EdoWeighting
+{
+    #[default]
+    Unweighted,
+    /// Divides the error of every ratio `n/d` by `log2(n*d)`, so complex intervals count less.
+    Tenney
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdoApproximation
+{
+    pub edo: u32,
+    pub max_error: f64,
+    pub mean_error: f64
+}
+
+impl Scale
+{
+    /// Ranks the given equal divisions of the octave by how closely they approximate this scale.
+    ///
+    /// Results are sorted by mean error, then max error, best first.
+    pub fn best_edos(&self, edos: impl IntoIterator<Item = u32>, weighting: EdoWeighting) -> Vec<EdoApproximation>
+    {
+        let weights: Vec<f64> = self.pitches.iter()
+            .map(|&pitch| match weighting
+            {
+                EdoWeighting::Unweighted => 1.0,
+                EdoWeighting::Tenney => match pitch.tenney_height()
+                {
+                    Some(height) if height > 0.0 => 1.0/height,
+                    _ => 1.0
+                }
+            })
+            .collect();
+
+        let mut approximations: Vec<EdoApproximation> = edos.into_iter()
+            .filter_map(|edo| self.deviation_from_edo(edo))
+            .map(|deviation| {
+                let edo = deviation.edo;
+                let errors: Vec<f64> = deviation.steps
+                    .iter()
+                    .zip(weights.iter())
+                    .map(|(step, weight)| step.deviation.abs()*weight)
+                    .collect();
+                let max_error = errors.iter().copied().fold(0.0, f64::max);
+                let mean_error = if errors.is_empty()
+                {
+                    0.0
+                }
+                else
+                {
+                    errors.iter().sum::<f64>()/errors.len() as f64
+                };
+                EdoApproximation {
+                    edo,
+                    max_error,
+                    mean_error
+                }
+            })
+            .collect();
+
+        approximations.sort_by(|a, b| a.mean_error.total_cmp(&b.mean_error).then(a.max_error.total_cmp(&b.max_error)));
+        approximations
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, EdoWeighting};
+
+    #[test]
+    fn five_limit_diatonic()
+    {
+        let scale = scl!{"ptolemy" 9/8 5/4 4/3 3/2 5/3 15/8 2/1};
+
+        let best = scale.best_edos(5..=40, EdoWeighting::Unweighted);
+        assert_eq!(best.len(), 36);
+        assert!(best.windows(2).all(|w| w[0].mean_error <= w[1].mean_error));
+        assert!(best.iter().take(3).any(|approximation| approximation.edo == 34 || approximation.edo == 31));
+
+        let unweighted = scale.best_edos([12, 19, 22], EdoWeighting::Unweighted);
+        let tenney = scale.best_edos([12, 19, 22], EdoWeighting::Tenney);
+        assert_eq!(unweighted[0].edo, 22);
+        assert_eq!(tenney[0].edo, 12);
+    }
+}
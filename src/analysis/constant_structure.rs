@@ -0,0 +1,46 @@
+use crate::Scale;
+
+impl Scale
+{
+    /// Whether every interval size, up to `tolerance` cents, only ever spans the same number of steps.
+    pub fn is_constant_structure(&self, tolerance: f64) -> bool
+    {
+        let matrix = self.interval_cents_matrix();
+        let n = self.pitches.len();
+
+        let mut intervals: Vec<(f64, usize)> = matrix.iter()
+            .flat_map(|row| (1..n).map(move |k| (row[k], k)))
+            .collect();
+        intervals.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        for (i, &(cents, k)) in intervals.iter().enumerate()
+        {
+            if intervals[i + 1..].iter()
+                .take_while(|&&(other, _)| other - cents <= tolerance)
+                .any(|&(_, other_k)| other_k != k)
+            {
+                return false
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::scl;
+
+    #[test]
+    fn constant_structure()
+    {
+        let ptolemy = scl!{"ptolemy" 9/8 5/4 4/3 3/2 5/3 15/8 2/1};
+        let diatonic = scl!{"12edo diatonic" 200.0 400.0 500.0 700.0 900.0 1100.0 1200.0};
+        let pentatonic = scl!{"pentatonic" 9/8 5/4 3/2 5/3 2/1};
+
+        assert!(ptolemy.is_constant_structure(0.001));
+        assert!(!diatonic.is_constant_structure(0.001));
+        assert!(pentatonic.is_constant_structure(0.001));
+        assert!(!ptolemy.is_constant_structure(30.0));
+    }
+}
@@ -0,0 +1,151 @@
+use std::cmp::Ordering;
+
+use num_rational::Ratio;
+
+use crate::{prime::{gcd, prime_limit_of}, Degree, Pitch, Scale};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DetemperOptions
+{
+    /// Largest prime allowed in either side of a proposed ratio.
+    pub prime_limit: u64,
+    /// Largest allowed distance in cents between a degree and its proposed ratio.
+    pub max_error: f64,
+    pub max_denominator: u128,
+    /// How many proposals to keep per degree.
+    pub max_candidates: usize
+}
+
+impl Default for DetemperOptions
+{
+    fn default() -> Self
+    {
+        Self {
+            prime_limit: 7,
+            max_error: 5.0,
+            max_denominator: 256,
+            max_candidates: 3
+        }
+    }
+}
+
+/// A just interpretation of a tempered degree, with its error in cents.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JustInterpretation
+{
+    pub ratio: Ratio<u128>,
+    pub error: f64
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DegreeInterpretations
+{
+    pub degree: Degree,
+    pub candidates: Vec<JustInterpretation>
+}
+
+fn within_prime_limit(ratio: Ratio<u128>, prime_limit: u64) -> bool
+{
+    [*ratio.numer(), *ratio.denom()].into_iter()
+        .all(|n| prime_limit_of(n).is_some_and(|limit| limit <= prime_limit as u128))
+}
+
+impl Pitch
+{
+    pub(crate) fn just_interpretations(self, options: &DetemperOptions) -> Vec<JustInterpretation>
+    {
+        if let Self::Ratio(ratio) = self
+        {
+            if within_prime_limit(ratio, options.prime_limit)
+            {
+                return vec![JustInterpretation {
+                    ratio,
+                    error: 0.0
+                }]
+            }
+        }
+
+        let cents = self.to_cents();
+        let value = (cents/1200.0).exp2();
+
+        let mut candidates = vec![];
+        for denom in 1..=options.max_denominator
+        {
+            let numer = (value*denom as f64).round() as u128;
+            if numer == 0 || gcd(numer, denom) != 1
+            {
+                continue
+            }
+            let ratio = Ratio::new_raw(numer, denom);
+            if !within_prime_limit(ratio, options.prime_limit)
+            {
+                continue
+            }
+            let error = cents - Pitch::Ratio(ratio).to_cents();
+            if error.abs() <= options.max_error
+            {
+                candidates.push(JustInterpretation {
+                    ratio,
+                    error
+                })
+            }
+        }
+
+        candidates.sort_by(|a, b| {
+            let complexity = |candidate: &JustInterpretation| *candidate.ratio.numer() as f64 * *candidate.ratio.denom() as f64;
+            complexity(a).partial_cmp(&complexity(b))
+                .unwrap_or(Ordering::Equal)
+                .then(a.error.abs().total_cmp(&b.error.abs()))
+        });
+        candidates.truncate(options.max_candidates);
+        candidates
+    }
+}
+
+impl Scale
+{
+    /// Proposes just ratios for every degree, simplest first.
+    ///
+    /// Degrees that are already ratios within the prime limit are kept as their only proposal.
+    pub fn detemper(&self, options: &DetemperOptions) -> Vec<DegreeInterpretations>
+    {
+        self.pitches.iter()
+            .enumerate()
+            .map(|(i, pitch)| DegreeInterpretations {
+                degree: Degree::from_pitch_index(i),
+                candidates: pitch.just_interpretations(options)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use num_rational::Ratio;
+
+    use crate::{scl, DetemperOptions};
+
+    #[test]
+    fn detemper_meantone()
+    {
+        let scale = scl!{"meantone" 386.314 503.422 696.578 1200.0};
+        let options = DetemperOptions {
+            prime_limit: 5,
+            max_error: 6.0,
+            ..Default::default()
+        };
+
+        let interpretations = scale.detemper(&options);
+        let best: Vec<Ratio<u128>> = interpretations.iter()
+            .map(|degree| degree.candidates[0].ratio)
+            .collect();
+        assert_eq!(best, vec![
+            Ratio::new(5, 4),
+            Ratio::new(4, 3),
+            Ratio::new(3, 2),
+            Ratio::new(2, 1)
+        ]);
+        assert!((interpretations[0].candidates[0].error - 0.0).abs() < 0.01);
+    }
+}
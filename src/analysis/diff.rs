@@ -0,0 +1,129 @@
+use crate::{Degree, Scale};
+
+/// A matched pair of degrees and how far apart they are.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DegreeDeviation
+{
+    pub degree: Degree,
+    pub other_degree: Degree,
+    pub deviation: f64
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScaleDiff
+{
+    pub deviations: Vec<DegreeDeviation>,
+    pub missing: Vec<Degree>,
+    pub inserted: Vec<Degree>,
+    pub max_abs_deviation: f64,
+    pub mean_abs_deviation: f64
+}
+
+impl ScaleDiff
+{
+    pub fn is_empty(&self) -> bool
+    {
+        self.missing.is_empty() && self.inserted.is_empty() && self.max_abs_deviation == 0.0
+    }
+}
+
+impl Scale
+{
+    /// Compares this scale against a reference.
+    ///
+    /// Degrees are paired up closest-first if they lie within `tolerance` cents of each other.
+    /// Deviations are signed, in cents, and positive when `other` is higher.
+    /// Degrees of `self` left unpaired are reported as missing, degrees of `other` left unpaired as inserted.
+    pub fn diff(&self, other: &Scale, tolerance: f64) -> ScaleDiff
+    {
+        let cents: Vec<f64> = self.pitches.iter().map(|pitch| pitch.to_cents()).collect();
+        let other_cents: Vec<f64> = other.pitches.iter().map(|pitch| pitch.to_cents()).collect();
+
+        let mut candidates = vec![];
+        for (i, a) in cents.iter().enumerate()
+        {
+            for (j, b) in other_cents.iter().enumerate()
+            {
+                let deviation = b - a;
+                if deviation.abs() <= tolerance
+                {
+                    candidates.push((i, j, deviation))
+                }
+            }
+        }
+        candidates.sort_by(|a, b| a.2.abs().total_cmp(&b.2.abs()));
+
+        let mut matched = vec![false; cents.len()];
+        let mut other_matched = vec![false; other_cents.len()];
+        let mut deviations = vec![];
+        for (i, j, deviation) in candidates
+        {
+            if !matched[i] && !other_matched[j]
+            {
+                matched[i] = true;
+                other_matched[j] = true;
+                deviations.push(DegreeDeviation {
+                    degree: Degree::from_pitch_index(i),
+                    other_degree: Degree::from_pitch_index(j),
+                    deviation
+                })
+            }
+        }
+        deviations.sort_by_key(|deviation| deviation.degree);
+
+        let missing = matched.into_iter()
+            .enumerate()
+            .filter(|&(_, matched)| !matched)
+            .map(|(i, _)| Degree::from_pitch_index(i))
+            .collect();
+        let inserted = other_matched.into_iter()
+            .enumerate()
+            .filter(|&(_, matched)| !matched)
+            .map(|(j, _)| Degree::from_pitch_index(j))
+            .collect();
+
+        let max_abs_deviation = deviations.iter()
+            .map(|deviation| deviation.deviation.abs())
+            .fold(0.0, f64::max);
+        let mean_abs_deviation = if deviations.is_empty()
+        {
+            0.0
+        }
+        else
+        {
+            deviations.iter().map(|deviation| deviation.deviation.abs()).sum::<f64>()/deviations.len() as f64
+        };
+
+        ScaleDiff {
+            deviations,
+            missing,
+            inserted,
+            max_abs_deviation,
+            mean_abs_deviation
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, Degree};
+
+    #[test]
+    fn diff_against_reference()
+    {
+        let reference = scl!{"ji" 9/8 5/4 4/3 3/2 2/1};
+        let tempered = scl!{"tempered" 200.0 400.0 700.0 1100.0 1200.0};
+
+        let diff = tempered.diff(&reference, 15.0);
+
+        assert_eq!(diff.missing, vec![Degree(4)]);
+        assert_eq!(diff.inserted, vec![Degree(3)]);
+        assert_eq!(diff.deviations.len(), 4);
+        assert_eq!(diff.deviations[1].degree, Degree(2));
+        assert_eq!(diff.deviations[1].other_degree, Degree(2));
+        assert!((diff.deviations[1].deviation + 13.686).abs() < 1e-3);
+        assert!((diff.max_abs_deviation - 13.686).abs() < 1e-3);
+        assert!(reference.diff(&reference, 0.0).is_empty());
+    }
+}
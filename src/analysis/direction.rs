@@ -0,0 +1,88 @@
+use std::borrow::Cow;
+
+use crate::Scale;
+
+/// The order pitches are listed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction
+{
+    Ascending,
+    /// Listed from the top down, like some descending tetrachords in the archive.
+    Descending,
+    Unordered
+}
+
+impl Scale
+{
+    pub fn direction(&self) -> Direction
+    {
+        let cents: Vec<f64> = self.pitches.iter().map(|pitch| pitch.to_cents()).collect();
+        if cents.windows(2).all(|w| w[0] < w[1])
+        {
+            Direction::Ascending
+        }
+        else if cents.windows(2).all(|w| w[0] > w[1])
+        {
+            Direction::Descending
+        }
+        else
+        {
+            Direction::Unordered
+        }
+    }
+
+    /// The same pitches listed in reverse order, with their labels and cents text.
+    pub fn reversed(&self) -> Scale
+    {
+        let mut scale = self.clone();
+        let len = scale.pitches.len();
+        scale.labels.resize(len, None);
+        scale.cents_text.resize(len, None);
+        scale.pitches.reverse();
+        scale.labels.reverse();
+        scale.cents_text.reverse();
+        scale
+    }
+
+    /// Descending scales turned the right way up, for analysis that walks the degrees in order.
+    pub(crate) fn normalized(&self) -> Cow<'_, Scale>
+    {
+        match self.direction()
+        {
+            Direction::Descending => Cow::Owned(self.reversed()),
+            Direction::Ascending | Direction::Unordered => Cow::Borrowed(self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, Direction, Scale};
+
+    #[test]
+    fn descending_scales()
+    {
+        let ascending = scl!{"tetrachord" 28/27 9/8 4/3};
+        let descending = ascending.reversed();
+
+        assert_eq!(ascending.direction(), Direction::Ascending);
+        assert_eq!(descending.direction(), Direction::Descending);
+        assert_eq!(scl!{"unordered" 9/8 28/27 4/3}.direction(), Direction::Unordered);
+
+        assert_eq!(descending.interval_matrix(), ascending.interval_matrix());
+        assert_eq!(descending.stats(), ascending.stats());
+        assert_eq!(descending.propriety(), ascending.propriety());
+
+        let mut labelled = ascending.clone();
+        labelled.set_label(0, "semitone");
+        let reversed = labelled.reversed();
+        assert_eq!(reversed.labels, [None, None, Some("semitone".to_string())]);
+        assert_eq!(reversed.normalized().label(0), Some("semitone"));
+
+        let written = "descending\n3\n4/3\n9/8 tone\n28/27\n".parse::<Scale>().unwrap();
+        let normalized = written.normalized();
+        assert_eq!(normalized.pitches, ascending.pitches);
+        assert_eq!(normalized.labels, [None, Some("tone".to_string()), None]);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{Degree, Scale};
+
+/// The equal division step closest to a scale degree.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdoStep
+{
+    pub degree: Degree,
+    pub step: i64,
+    pub deviation: f64
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdoDeviation
+{
+    pub edo: u32,
+    pub steps: Vec<EdoStep>,
+    pub max_abs_deviation: f64,
+    pub mean_abs_deviation: f64,
+    pub total_abs_deviation: f64
+}
+
+impl Scale
+{
+    /// Finds the nearest step of `edo`-note equal division of the octave for every degree.
+    ///
+    /// Deviations are signed, in cents, and positive when the scale degree is sharp of the equal step.
+    /// Returns `None` for 0, which has no steps.
+    pub fn deviation_from_edo(&self, edo: u32) -> Option<EdoDeviation>
+    {
+        if edo == 0
+        {
+            return None
+        }
+        let step_size = 1200.0/edo as f64;
+
+        let steps: Vec<EdoStep> = self.pitches.iter()
+            .enumerate()
+            .map(|(i, pitch)| {
+                let cents = pitch.to_cents();
+                let step = (cents/step_size).round();
+                EdoStep {
+                    degree: Degree::from_pitch_index(i),
+                    step: step as i64,
+                    deviation: cents - step*step_size
+                }
+            })
+            .collect();
+
+        let total_abs_deviation: f64 = steps.iter().map(|step| step.deviation.abs()).sum();
+        let max_abs_deviation = steps.iter().map(|step| step.deviation.abs()).fold(0.0, f64::max);
+        let mean_abs_deviation = if steps.is_empty()
+        {
+            0.0
+        }
+        else
+        {
+            total_abs_deviation/steps.len() as f64
+        };
+
+        Some(EdoDeviation {
+            edo,
+            steps,
+            max_abs_deviation,
+            mean_abs_deviation,
+            total_abs_deviation
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::scl;
+
+    #[test]
+    fn ji_against_12edo()
+    {
+        let scale = scl!{"ji" 9/8 5/4 4/3 3/2 2/1};
+        let deviation = scale.deviation_from_edo(12).unwrap();
+
+        let steps: Vec<i64> = deviation.steps.iter().map(|step| step.step).collect();
+        assert_eq!(steps, vec![2, 4, 5, 7, 12]);
+        assert!((deviation.steps[1].deviation + 13.686).abs() < 1e-3);
+        assert!((deviation.steps[3].deviation - 1.955).abs() < 1e-3);
+        assert!((deviation.max_abs_deviation - 13.686).abs() < 1e-3);
+        assert_eq!(deviation.steps[4].deviation, 0.0);
+
+        assert_eq!(scale.deviation_from_edo(0), None);
+    }
+}
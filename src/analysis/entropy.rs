@@ -0,0 +1,141 @@
+use std::f64::consts::SQRT_2;
+
+use crate::{prime::gcd, Pitch, Scale};
+
+fn erf(x: f64) -> f64
+{
+    // Abramowitz and Stegun 7.1.26
+    let t = 1.0/(1.0 + 0.3275911*x.abs());
+    let y = 1.0 - (((((1.061405429*t - 1.453152027)*t) + 1.421413741)*t - 0.284496736)*t + 0.254829592)*t*(-x*x).exp();
+    y.copysign(x)
+}
+
+fn normal_cdf(x: f64) -> f64
+{
+    0.5*(1.0 + erf(x/SQRT_2))
+}
+
+/// Harmonic entropy in the sense of Paul Erlich.
+///
+/// Every ratio `n/d` with `n*d` up to the given limit claims the region between its mediants with its neighbours,
+/// and an interval's entropy is that of the probabilities of being heard as each ratio under a gaussian spread.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HarmonicEntropy
+{
+    spread: f64,
+    centers: Vec<f64>,
+    bounds: Vec<f64>
+}
+
+impl HarmonicEntropy
+{
+    /// `spread` is the standard deviation of the hearing model in cents, commonly around 17 cents.
+    pub fn new(benedetti_limit: u64, spread: f64) -> Self
+    {
+        let mut ratios = vec![];
+        for denom in 1..=benedetti_limit
+        {
+            for numer in 1..=benedetti_limit/denom
+            {
+                if gcd(numer as u128, denom as u128) == 1
+                {
+                    ratios.push((numer, denom))
+                }
+            }
+        }
+        let cents = |numer: u64, denom: u64| (numer as f64/denom as f64).log2()*1200.0;
+        ratios.sort_by(|a, b| cents(a.0, a.1).total_cmp(&cents(b.0, b.1)));
+
+        let centers = ratios.iter().map(|&(numer, denom)| cents(numer, denom)).collect();
+        let bounds = ratios.windows(2)
+            .map(|w| cents(w[0].0 + w[1].0, w[0].1 + w[1].1))
+            .collect();
+
+        Self {
+            spread,
+            centers,
+            bounds
+        }
+    }
+
+    pub fn of_cents(&self, cents: f64) -> f64
+    {
+        let reach = 8.0*self.spread;
+        let first = self.centers.partition_point(|&center| center < cents - reach);
+        let last = self.centers.partition_point(|&center| center <= cents + reach);
+
+        let mut entropy = 0.0;
+        for i in first..last
+        {
+            let lower = if i == 0
+            {
+                f64::NEG_INFINITY
+            }
+            else
+            {
+                self.bounds[i - 1]
+            };
+            let upper = self.bounds.get(i).copied().unwrap_or(f64::INFINITY);
+            let probability = normal_cdf((upper - cents)/self.spread) - normal_cdf((lower - cents)/self.spread);
+            if probability > 0.0
+            {
+                entropy -= probability*probability.ln();
+            }
+        }
+        entropy
+    }
+
+    pub fn of_pitch(&self, pitch: Pitch) -> f64
+    {
+        self.of_cents(pitch.to_cents())
+    }
+}
+
+impl Default for HarmonicEntropy
+{
+    fn default() -> Self
+    {
+        Self::new(10000, 17.0)
+    }
+}
+
+impl Scale
+{
+    /// The mean harmonic entropy of every interval in the interval matrix, excluding unisons and periods.
+    pub fn harmonic_entropy(&self, model: &HarmonicEntropy) -> f64
+    {
+        let intervals: Vec<f64> = self.interval_cents_matrix()
+            .into_iter()
+            .flat_map(|row| {
+                let n = row.len();
+                row.into_iter().take(n - 1).skip(1)
+            })
+            .collect();
+        if intervals.is_empty()
+        {
+            return 0.0
+        }
+        intervals.iter().map(|&cents| model.of_cents(cents)).sum::<f64>()/intervals.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, HarmonicEntropy};
+
+    #[test]
+    fn simple_ratios_are_local_minima()
+    {
+        let model = HarmonicEntropy::new(10000, 17.0);
+
+        let fifth = model.of_cents(701.955);
+        assert!(fifth < model.of_cents(680.0));
+        assert!(fifth < model.of_cents(720.0));
+        assert!(model.of_cents(1200.0) < model.of_cents(1150.0));
+
+        let ji = scl!{"ji" 5/4 3/2 2/1};
+        let tempered = scl!{"tempered" 450.0 650.0 1200.0};
+        assert!(ji.harmonic_entropy(&model) < tempered.harmonic_entropy(&model));
+    }
+}
@@ -0,0 +1,161 @@
+use crate::Scale;
+
+/// A scale fitted as a chain of one generator within a period, possibly repeated over several equal periods.
+///
+/// All sizes are in cents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratorChain
+{
+    /// The generator, least-squares fitted to the degrees, reduced to at most half the period.
+    pub generator: f64,
+    pub period: f64,
+    /// How many periods fit in the whole scale.
+    pub periods: usize,
+    /// For each pitch, how many generators up the chain it lies, negative below the unison.
+    pub generator_counts: Vec<i32>,
+    /// For each pitch, how far it lies above its place in the fitted chain.
+    pub errors: Vec<f64>,
+    pub max_abs_error: f64
+}
+
+impl Scale
+{
+    /// Detects whether every degree lies within `tolerance` cents of a chain of one generator, reduced by the period.
+    ///
+    /// Fewer periods are preferred, then the closest fit.
+    ///
+    /// ```
+    /// use serde_scala::scl;
+    ///
+    /// let meantone = scl!{"meantone" 193.2 386.3 503.4 696.6 889.7 1082.9 1200.0};
+    /// let chain = meantone.generator_chain(0.1).unwrap();
+    /// assert!((chain.generator - 503.4).abs() < 0.1);
+    /// assert_eq!(chain.generator_counts, [-2, -4, 1, -1, -3, -5, 0]);
+    /// ```
+    pub fn generator_chain(&self, tolerance: f64) -> Option<GeneratorChain>
+    {
+        let n = self.pitches.len();
+        let total_period = self.period()?.to_cents();
+        if n < 2 || total_period <= 0.0
+        {
+            return None
+        }
+        let degrees: Vec<f64> = self.degrees().take(n).map(|pitch| pitch.to_cents()).collect();
+
+        for periods in (1..=n).filter(|&p| n.is_multiple_of(p))
+        {
+            let period = total_period/periods as f64;
+            let per_period = n/periods;
+            let mut best: Option<GeneratorChain> = None;
+            for candidate in degrees.iter().take(per_period).skip(1)
+            {
+                for start in 0..per_period as i32
+                {
+                    if let Some(chain) = fit_chain(&degrees, *candidate, start, period, periods)
+                    {
+                        if chain.max_abs_error <= tolerance && best.as_ref().is_none_or(|best| chain.max_abs_error < best.max_abs_error)
+                        {
+                            best = Some(chain);
+                        }
+                    }
+                }
+            }
+            if best.is_some()
+            {
+                return best
+            }
+        }
+        None
+    }
+}
+
+/// Fits the degrees to the chain of `generator` starting `start` generators below the unison.
+fn fit_chain(degrees: &[f64], generator: f64, start: i32, period: f64, periods: usize) -> Option<GeneratorChain>
+{
+    let per_period = (degrees.len()/periods) as i32;
+    let mut predicted: Vec<(f64, i32)> = (-start..per_period - start)
+        .flat_map(|k| (0..periods).map(move |q| ((k as f64*generator).rem_euclid(period) + q as f64*period, k)))
+        .collect();
+    predicted.sort_by(|a, b| a.0.total_cmp(&b.0));
+    if predicted[0].1 != 0
+    {
+        return None
+    }
+
+    let counts: Vec<i32> = predicted.iter().map(|&(_, k)| k).collect();
+    // How many periods each degree lies above its place in the chain.
+    let octaves: Vec<f64> = degrees.iter()
+        .zip(counts.iter())
+        .map(|(&cents, &k)| ((cents - k as f64*generator)/period).round())
+        .collect();
+    let squares: f64 = counts.iter().map(|&k| (k*k) as f64).sum();
+    let generator = if squares > 0.0
+    {
+        degrees.iter()
+            .zip(counts.iter())
+            .zip(octaves.iter())
+            .map(|((&cents, &k), &o)| k as f64*(cents - o*period))
+            .sum::<f64>()/squares
+    }
+    else
+    {
+        generator
+    };
+
+    let mut errors: Vec<f64> = degrees.iter()
+        .zip(counts.iter())
+        .zip(octaves.iter())
+        .map(|((&cents, &k), &o)| cents - k as f64*generator - o*period)
+        .collect();
+    let mut generator_counts = counts;
+
+    // Degree 0 is the unison, which lines up with the period closing the scale.
+    errors.rotate_left(1);
+    generator_counts.rotate_left(1);
+
+    let reduced = generator.rem_euclid(period);
+    let (generator, sign) = if reduced > period/2.0
+    {
+        (period - reduced, -1)
+    }
+    else
+    {
+        (reduced, 1)
+    };
+    let max_abs_error = errors.iter().map(|error| error.abs()).fold(0.0, f64::max);
+    Some(GeneratorChain {
+        generator,
+        period,
+        periods,
+        generator_counts: generator_counts.into_iter().map(|k| k*sign).collect(),
+        errors,
+        max_abs_error
+    })
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::scl;
+
+    #[test]
+    fn detect_generator()
+    {
+        let pythagorean = scl!{"pythagorean" 9/8 81/64 4/3 3/2 27/16 243/128 2/1};
+        let chain = pythagorean.generator_chain(1e-6).unwrap();
+        assert!((chain.generator - 498.045).abs() < 1e-3);
+        assert_eq!(chain.periods, 1);
+        assert_eq!(chain.generator_counts, [-2, -4, 1, -1, -3, -5, 0]);
+
+        let pajara = scl!{"pajara[10]" 109.0 218.0 382.0 491.0 600.0 709.0 818.0 982.0 1091.0 1200.0};
+        let chain = pajara.generator_chain(0.001).unwrap();
+        assert_eq!(chain.periods, 2);
+        assert_eq!(chain.period, 600.0);
+        assert!((chain.generator - 109.0).abs() < 1e-9);
+
+        let ji = scl!{"ji major" 9/8 5/4 4/3 3/2 5/3 15/8 2/1};
+        assert!(ji.generator_chain(1.0).is_none());
+        let chain = ji.generator_chain(15.0).unwrap();
+        assert!(chain.max_abs_error > 1.0 && chain.max_abs_error <= 15.0);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{Degree, Pitch, Scale};
+
+impl Pitch
+{
+    /// `log2(n*d)` of the ratio `n/d`, or `None` for cents.
+    pub fn tenney_height(self) -> Option<f64>
+    {
+        match self
+        {
+            Self::Cents(_) => None,
+            Self::Ratio(ratio) => Some((*ratio.numer() as f64).log2() + (*ratio.denom() as f64).log2())
+        }
+    }
+
+    /// `n*d` of the ratio `n/d`, or `None` for cents or if the product overflows.
+    pub fn benedetti_height(self) -> Option<u128>
+    {
+        match self
+        {
+            Self::Cents(_) => None,
+            Self::Ratio(ratio) => ratio.numer().checked_mul(*ratio.denom())
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsonanceSummary
+{
+    pub min_tenney_height: f64,
+    pub max_tenney_height: f64,
+    pub mean_tenney_height: f64,
+    /// Degrees ordered from simplest to most complex ratio.
+    pub ranking: Vec<Degree>
+}
+
+impl Scale
+{
+    /// Summarizes the Tenney heights of the degrees, or `None` if some degree is given in cents.
+    pub fn consonance(&self) -> Option<ConsonanceSummary>
+    {
+        let heights = self.pitches.iter()
+            .map(|pitch| pitch.tenney_height())
+            .collect::<Option<Vec<f64>>>()?;
+
+        let mut ranking: Vec<Degree> = (0..heights.len()).map(Degree::from_pitch_index).collect();
+        ranking.sort_by(|&a, &b| heights[a.0 - 1].total_cmp(&heights[b.0 - 1]));
+
+        Some(ConsonanceSummary {
+            min_tenney_height: heights.iter().copied().fold(f64::INFINITY, f64::min),
+            max_tenney_height: heights.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            mean_tenney_height: heights.iter().sum::<f64>()/heights.len().max(1) as f64,
+            ranking
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use num_rational::Ratio;
+
+    use crate::{scl, Degree, Pitch};
+
+    #[test]
+    fn heights()
+    {
+        let fifth = Pitch::Ratio(Ratio::new(3, 2));
+        assert_eq!(fifth.benedetti_height(), Some(6));
+        assert!((fifth.tenney_height().unwrap() - 6f64.log2()).abs() < 1e-12);
+        assert_eq!(Pitch::Cents(700.0).tenney_height(), None);
+
+        let scale = scl!{"ji" 16/15 5/4 3/2 2/1};
+        let consonance = scale.consonance().unwrap();
+        assert_eq!(consonance.ranking, vec![Degree(4), Degree(3), Degree(2), Degree(1)]);
+        assert_eq!(consonance.min_tenney_height, 1.0);
+        assert!((consonance.max_tenney_height - 240f64.log2()).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{prime::prime_limit_of, Pitch, Scale};
+
+fn odd_part(mut n: u128) -> u128
+{
+    if n == 0
+    {
+        return 0
+    }
+    while n.is_multiple_of(2)
+    {
+        n /= 2;
+    }
+    n
+}
+
+impl Pitch
+{
+    /// The largest prime in the ratio, or `None` for cents, for zero and for ratios too large to factor.
+    pub fn prime_limit(self) -> Option<u128>
+    {
+        match self
+        {
+            Self::Cents(_) => None,
+            Self::Ratio(ratio) => Some(prime_limit_of(*ratio.numer())?.max(prime_limit_of(*ratio.denom())?))
+        }
+    }
+
+    /// The largest odd number in the ratio once factors of two are removed, or `None` for cents.
+    pub fn odd_limit(self) -> Option<u128>
+    {
+        match self
+        {
+            Self::Cents(_) => None,
+            Self::Ratio(ratio) => Some(odd_part(*ratio.numer()).max(odd_part(*ratio.denom())))
+        }
+    }
+}
+
+impl Scale
+{
+    /// The largest prime used by any degree, or `None` if some degree is given in cents or can't be factored.
+    pub fn prime_limit(&self) -> Option<u128>
+    {
+        self.pitches.iter()
+            .map(|pitch| pitch.prime_limit())
+            .try_fold(1, |limit, pitch_limit| Some(limit.max(pitch_limit?)))
+    }
+
+    /// The largest odd limit of any degree, or `None` if some degree is given in cents.
+    pub fn odd_limit(&self) -> Option<u128>
+    {
+        self.pitches.iter()
+            .map(|pitch| pitch.odd_limit())
+            .try_fold(1, |limit, pitch_limit| Some(limit.max(pitch_limit?)))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, Pitch};
+
+    #[test]
+    fn limits()
+    {
+        let scale = scl!{"harmonic" 9/8 5/4 11/8 3/2 7/4 15/8 2/1};
+        assert_eq!(scale.prime_limit(), Some(11));
+        assert_eq!(scale.odd_limit(), Some(15));
+
+        let pythagorean = scl!{"pythagorean" 9/8 81/64 4/3 3/2 27/16 243/128 2/1};
+        assert_eq!(pythagorean.prime_limit(), Some(3));
+        assert_eq!(pythagorean.odd_limit(), Some(243));
+
+        let tempered = scl!{"tempered" 9/8 400.0 2/1};
+        assert_eq!(tempered.prime_limit(), None);
+        assert_eq!(tempered.odd_limit(), None);
+
+        assert_eq!(Pitch::ratio(18446744073709551557, 1).prime_limit(), Some(18446744073709551557));
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt::Display;
+
+use crate::{Pitch, Scale};
+
+/// The intervals of every mode of a scale, like Scala's `SHOW INTERVALS`.
+///
+/// Row `i` is the mode starting on degree `i`, and column `k` is the interval spanning `k` steps from there,
+/// from the unison up to the period.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntervalMatrix
+{
+    pub rows: Vec<Vec<Pitch>>
+}
+
+impl IntervalMatrix
+{
+    pub fn to_cents(&self) -> Vec<Vec<f64>>
+    {
+        self.rows.iter()
+            .map(|row| row.iter().map(|pitch| pitch.to_cents()).collect())
+            .collect()
+    }
+}
+
+impl Display for IntervalMatrix
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let columns = self.rows.first().map(|row| row.len()).unwrap_or(0);
+
+        write!(f, "{:>5}", "")?;
+        for k in 0..columns
+        {
+            write!(f, " {:>9}", k)?;
+        }
+        writeln!(f)?;
+
+        for (i, row) in self.rows.iter().enumerate()
+        {
+            write!(f, "{:>4}:", i)?;
+            for pitch in row.iter()
+            {
+                write!(f, " {:>9.3}", pitch.to_cents())?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Scale
+{
+    /// The pitch of degree `i`, counting on into the next periods past the last degree.
+    pub(crate) fn extended_degree(&self, i: usize) -> Pitch
+    {
+        let n = self.pitches.len();
+        let mut pitch = match i % n
+        {
+            0 => Pitch::unison(),
+            i => self.pitches[i - 1]
+        };
+        if let Some(period) = self.period()
+        {
+            for _ in 0..i/n
+            {
+                pitch = pitch*period;
+            }
+        }
+        pitch
+    }
+
+    /// Descending scales are turned the right way up first.
+    pub fn interval_matrix(&self) -> IntervalMatrix
+    {
+        let scale = self.normalized();
+        let n = scale.pitches.len();
+        let degrees: Vec<Pitch> = (0..2*n).map(|i| scale.extended_degree(i)).collect();
+
+        IntervalMatrix {
+            rows: (0..n).map(|mode| (0..=n).map(|k| degrees[mode + k]/degrees[mode]).collect())
+                .collect()
+        }
+    }
+
+    pub(crate) fn interval_cents_matrix(&self) -> Vec<Vec<f64>>
+    {
+        self.interval_matrix().to_cents()
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use num_rational::Ratio;
+
+    use crate::{scl, Pitch};
+
+    #[test]
+    fn interval_matrix()
+    {
+        let scale = scl!{"ji" 5/4 3/2 2/1};
+        let matrix = scale.interval_matrix();
+
+        let ratio = |numer, denom| Pitch::Ratio(Ratio::new(numer, denom));
+        assert_eq!(matrix.rows, vec![
+            vec![ratio(1, 1), ratio(5, 4), ratio(3, 2), ratio(2, 1)],
+            vec![ratio(1, 1), ratio(6, 5), ratio(8, 5), ratio(2, 1)],
+            vec![ratio(1, 1), ratio(4, 3), ratio(5, 3), ratio(2, 1)]
+        ]);
+
+        let text = matrix.to_string();
+        assert_eq!(text.lines().count(), 4);
+        assert!(text.lines().nth(2).unwrap().starts_with("   1:     0.000   315.641"));
+    }
+}
@@ -0,0 +1,30 @@
+mod best_edo;
+mod constant_structure;
+mod detemper;
+mod diff;
+mod direction;
+mod edo;
+mod entropy;
+mod generator;
+mod height;
+mod limit;
+mod matrix;
+mod mos;
+mod propriety;
+mod simplify;
+mod spectrum;
+mod stats;
+
+pub use best_edo::*;
+pub use detemper::*;
+pub use diff::*;
+pub use direction::*;
+pub use edo::*;
+pub use entropy::*;
+pub use generator::*;
+pub use height::*;
+pub use matrix::*;
+pub use mos::*;
+pub use propriety::*;
+pub use spectrum::*;
+pub use stats::*;
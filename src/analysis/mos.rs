@@ -0,0 +1,141 @@
+use crate::Scale;
+
+/// A moment of symmetry scale, i.e. a well-formed scale with two step sizes.
+///
+/// All sizes are in cents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mos
+{
+    /// The step pattern, like `LLsLLLs` for the diatonic scale.
+    pub pattern: String,
+    pub large_step: f64,
+    pub small_step: f64,
+    pub large_count: usize,
+    pub small_count: usize,
+    /// The smallest generating interval, spanning `generator_steps` steps.
+    pub generator: f64,
+    pub generator_steps: usize,
+    pub period: f64,
+    /// How many periods fit in the whole scale.
+    pub periods: usize
+}
+
+impl Scale
+{
+    /// Detects whether this is a moment of symmetry scale, treating steps within `tolerance` cents of each other as equal.
+    pub fn mos(&self, tolerance: f64) -> Option<Mos>
+    {
+        let matrix = self.interval_cents_matrix();
+        let n = self.pitches.len();
+        let steps: Vec<f64> = matrix.iter().map(|row| row[1]).collect();
+
+        let large_step = steps.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let small_step = steps.iter().copied().fold(f64::INFINITY, f64::min);
+        if n < 2 || large_step - small_step <= tolerance
+        {
+            return None
+        }
+        let large: Vec<bool> = steps.iter()
+            .map(|&step| {
+                if large_step - step <= tolerance
+                {
+                    Some(true)
+                }
+                else if step - small_step <= tolerance
+                {
+                    Some(false)
+                }
+                else
+                {
+                    None
+                }
+            })
+            .collect::<Option<_>>()?;
+
+        let period_steps = (1..=n).find(|&t| n.is_multiple_of(t) && (0..n).all(|i| large[i] == large[(i + t) % n]))
+            .unwrap();
+        let periods = n/period_steps;
+
+        let large_in_window = |start: usize, k: usize| (start..start + k).filter(|&i| large[i % n]).count();
+        let mut generator = None;
+        for k in 1..n
+        {
+            let counts: Vec<usize> = (0..n).map(|start| large_in_window(start, k)).collect();
+            let mut distinct = counts.clone();
+            distinct.sort_unstable();
+            distinct.dedup();
+
+            let expected = if k.is_multiple_of(period_steps)
+            {
+                1
+            }
+            else
+            {
+                2
+            };
+            if distinct.len() != expected
+            {
+                return None
+            }
+            if generator.is_none() && k < period_steps
+            {
+                if let Some(&count) = distinct.iter().find(|&&count| counts.iter().filter(|&&c| c == count).count() == n - periods)
+                {
+                    let sizes: Vec<f64> = matrix.iter()
+                        .zip(counts.iter())
+                        .filter(|&(_, &c)| c == count)
+                        .map(|(row, _)| row[k])
+                        .collect();
+                    generator = Some((sizes.iter().sum::<f64>()/sizes.len() as f64, k));
+                }
+            }
+        }
+        let (generator, generator_steps) = generator?;
+
+        let large_count = large.iter().filter(|&&large| large).count();
+        Some(Mos {
+            pattern: large.iter().map(|&large| if large {'L'} else {'s'}).collect(),
+            large_step,
+            small_step,
+            large_count,
+            small_count: n - large_count,
+            generator,
+            generator_steps,
+            period: matrix[0][period_steps],
+            periods
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::scl;
+
+    #[test]
+    fn diatonic_is_mos()
+    {
+        let diatonic = scl!{"12edo diatonic" 200.0 400.0 500.0 700.0 900.0 1100.0 1200.0};
+        let mos = diatonic.mos(0.001).unwrap();
+
+        assert_eq!(mos.pattern, "LLsLLLs");
+        assert_eq!((mos.large_count, mos.small_count), (5, 2));
+        assert_eq!(mos.generator_steps, 3);
+        assert!((mos.generator - 500.0).abs() < 1e-9);
+        assert_eq!(mos.periods, 1);
+        assert_eq!(mos.period, 1200.0);
+    }
+
+    #[test]
+    fn multiple_periods()
+    {
+        let pajara = scl!{"pajara[10]" 109.0 218.0 382.0 491.0 600.0 709.0 818.0 982.0 1091.0 1200.0};
+        let mos = pajara.mos(0.001).unwrap();
+        assert_eq!(mos.periods, 2);
+        assert_eq!(mos.period, 600.0);
+
+        let harmonic = scl!{"harmonics" 9/8 5/4 11/8 3/2 13/8 7/4 15/8 2/1};
+        assert!(harmonic.mos(1.0).is_none());
+        assert!(scl!{"12edo" 100.0 200.0 300.0}.mos(0.001).is_none());
+    }
+}
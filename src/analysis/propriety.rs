@@ -0,0 +1,63 @@
+use crate::{Scale, EPSILON};
+
+/// Rothenberg propriety.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Propriety
+{
+    /// Every interval class is strictly smaller than the next one.
+    StrictlyProper,
+    /// Neighbouring interval classes overlap at most at their boundaries.
+    Proper,
+    Improper
+}
+
+impl Scale
+{
+    pub fn propriety(&self) -> Propriety
+    {
+        let matrix = self.interval_cents_matrix();
+        let n = self.pitches.len();
+
+        let ranges: Vec<(f64, f64)> = (1..n).map(|k| matrix.iter()
+                .map(|row| row[k])
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), cents| (min.min(cents), max.max(cents)))
+            )
+            .collect();
+
+        let mut propriety = Propriety::StrictlyProper;
+        for w in ranges.windows(2)
+        {
+            let (_, max) = w[0];
+            let (min, _) = w[1];
+            if max > min + EPSILON
+            {
+                return Propriety::Improper
+            }
+            if max > min - EPSILON
+            {
+                propriety = Propriety::Proper
+            }
+        }
+        propriety
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, Propriety};
+
+    #[test]
+    fn propriety()
+    {
+        let meantone = scl!{"meantone" 193.157 386.314 503.422 696.579 889.735 1082.892 1200.0};
+        let diatonic = scl!{"12edo diatonic" 200.0 400.0 500.0 700.0 900.0 1100.0 1200.0};
+        let ptolemy = scl!{"ptolemy" 9/8 5/4 4/3 3/2 5/3 15/8 2/1};
+        let improper = scl!{"improper" 100.0 200.0 1100.0 1200.0};
+
+        assert_eq!(meantone.propriety(), Propriety::StrictlyProper);
+        assert_eq!(diatonic.propriety(), Propriety::Proper);
+        assert_eq!(ptolemy.propriety(), Propriety::StrictlyProper);
+        assert_eq!(improper.propriety(), Propriety::Improper);
+    }
+}
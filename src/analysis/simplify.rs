@@ -0,0 +1,108 @@
+use num_rational::Ratio;
+
+use crate::Pitch;
+
+fn cents_of(numer: u128, denom: u128) -> f64
+{
+    1200.0*(numer as f64/denom as f64).log2()
+}
+
+impl Pitch
+{
+    /// The closest ratio with a denominator of at most `max_denominator`, found by continued fractions,
+    /// and how many cents it lies above this pitch.
+    ///
+    /// Returns `None` for cents that aren't finite, or a `max_denominator` of zero.
+    ///
+    /// ```
+    /// use num_rational::Ratio;
+    /// use serde_scala::Pitch;
+    ///
+    /// let (ratio, error) = Pitch::cents(701.955).to_ratio(100).unwrap();
+    /// assert_eq!(ratio, Ratio::new(3, 2));
+    /// assert!(error.abs() < 1e-3);
+    /// ```
+    pub fn to_ratio(self, max_denominator: u128) -> Option<(Ratio<u128>, f64)>
+    {
+        let cents = self.to_cents();
+        if !cents.is_finite() || max_denominator == 0
+        {
+            return None
+        }
+        if let Pitch::Ratio(ratio) = self
+        {
+            if *ratio.denom() <= max_denominator
+            {
+                return Some((ratio, 0.0))
+            }
+        }
+
+        let x = (cents/1200.0).exp2();
+        let (mut h0, mut k0, mut h1, mut k1) = (0u128, 1u128, 1u128, 0u128);
+        let mut rest = x;
+        let mut best = (1u128, 1u128);
+        loop
+        {
+            let a = rest.floor();
+            if a >= u128::MAX as f64
+            {
+                break
+            }
+            let a = a as u128;
+            let (Some(h2), Some(k2)) = (a.checked_mul(h1).and_then(|h| h.checked_add(h0)), a.checked_mul(k1).and_then(|k| k.checked_add(k0)))
+            else
+            {
+                break
+            };
+            if k2 > max_denominator
+            {
+                // The largest semiconvergent that still fits may be closer than the last convergent.
+                let steps = (max_denominator - k0)/k1;
+                let semiconvergent = (h0 + steps*h1, k0 + steps*k1);
+                if steps > 0 && (cents_of(semiconvergent.0, semiconvergent.1) - cents).abs() < (cents_of(best.0, best.1) - cents).abs()
+                {
+                    best = semiconvergent;
+                }
+                break
+            }
+            (h0, k0, h1, k1) = (h1, k1, h2, k2);
+            if h1 > 0
+            {
+                best = (h1, k1);
+            }
+
+            let frac = rest - a as f64;
+            if frac < 1e-12
+            {
+                break
+            }
+            rest = 1.0/frac;
+        }
+
+        let ratio = Ratio::new(best.0, best.1);
+        Some((ratio, cents_of(*ratio.numer(), *ratio.denom()) - cents))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use num_rational::Ratio;
+
+    use crate::Pitch;
+
+    #[test]
+    fn simplest_ratios()
+    {
+        let (ratio, error) = Pitch::cents(400.0).to_ratio(10).unwrap();
+        assert_eq!(ratio, Ratio::new(5, 4));
+        assert!((error + 13.686).abs() < 1e-3);
+
+        assert_eq!(Pitch::cents(0.0).to_ratio(8).unwrap().0, Ratio::new(1, 1));
+        assert_eq!(Pitch::cents(-701.955).to_ratio(8).unwrap().0, Ratio::new(2, 3));
+        assert_eq!(Pitch::cents(1200.0*std::f64::consts::PI.log2()).to_ratio(120).unwrap().0, Ratio::new(355, 113));
+        assert_eq!(Pitch::ratio(7, 4).to_ratio(4), Some((Ratio::new(7, 4), 0.0)));
+        assert_eq!(Pitch::ratio(81, 64).to_ratio(10).unwrap().0, Ratio::new(5, 4));
+        assert_eq!(Pitch::cents(f64::NAN).to_ratio(10), None);
+    }
+}
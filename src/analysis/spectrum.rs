@@ -0,0 +1,66 @@
+use crate::Scale;
+
+/// A group of intervals of roughly the same size, in cents.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntervalSize
+{
+    pub cents: f64,
+    pub count: usize
+}
+
+impl Scale
+{
+    /// Groups every interval of the interval matrix, excluding unisons and periods, into sizes.
+    ///
+    /// Intervals within `tolerance` cents of the smallest interval in a group join that group,
+    /// and every group is reported with its mean size, from smallest to largest.
+    pub fn interval_spectrum(&self, tolerance: f64) -> Vec<IntervalSize>
+    {
+        let n = self.pitches.len();
+        let mut intervals: Vec<f64> = self.interval_cents_matrix()
+            .into_iter()
+            .flat_map(|row| row.into_iter().take(n).skip(1))
+            .collect();
+        intervals.sort_by(f64::total_cmp);
+
+        let mut spectrum: Vec<(f64, f64, usize)> = vec![];
+        for cents in intervals
+        {
+            match spectrum.last_mut()
+            {
+                Some((first, sum, count)) if cents - *first <= tolerance => {
+                    *sum += cents;
+                    *count += 1;
+                },
+                _ => spectrum.push((cents, cents, 1))
+            }
+        }
+
+        spectrum.into_iter()
+            .map(|(_, sum, count)| IntervalSize {
+                cents: sum/count as f64,
+                count
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::scl;
+
+    #[test]
+    fn diatonic_spectrum()
+    {
+        let diatonic = scl!{"12edo diatonic" 200.0 400.0 500.0 700.0 900.0 1100.0 1200.0};
+        let spectrum = diatonic.interval_spectrum(0.001);
+
+        let sizes: Vec<(f64, usize)> = spectrum.iter().map(|size| (size.cents, size.count)).collect();
+        assert_eq!(sizes, vec![
+            (100.0, 2), (200.0, 5), (300.0, 4), (400.0, 3), (500.0, 6), (600.0, 2),
+            (700.0, 6), (800.0, 3), (900.0, 4), (1000.0, 5), (1100.0, 2)
+        ]);
+        assert_eq!(diatonic.interval_spectrum(150.0).len(), 6);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{Scale, EPSILON};
+
+/// Step statistics, in cents.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleStats
+{
+    pub smallest_step: f64,
+    pub largest_step: f64,
+    pub mean_step: f64,
+    pub step_variance: f64,
+    pub distinct_steps: usize,
+    /// The distance between the lowest and highest degree, including the unison.
+    pub span: f64
+}
+
+impl Scale
+{
+    /// Returns `None` for a scale without pitches. Descending scales are turned the right way up first.
+    pub fn stats(&self) -> Option<ScaleStats>
+    {
+        if self.pitches.is_empty()
+        {
+            return None
+        }
+
+        let degrees: Vec<f64> = [0.0].into_iter()
+            .chain(self.normalized().pitches.iter().map(|pitch| pitch.to_cents()))
+            .collect();
+        let mut steps: Vec<f64> = degrees.windows(2).map(|w| w[1] - w[0]).collect();
+
+        let mean_step = steps.iter().sum::<f64>()/steps.len() as f64;
+        let step_variance = steps.iter().map(|step| (step - mean_step).powi(2)).sum::<f64>()/steps.len() as f64;
+
+        steps.sort_by(f64::total_cmp);
+        let distinct_steps = 1 + steps.windows(2).filter(|w| w[1] - w[0] > EPSILON).count();
+
+        Some(ScaleStats {
+            smallest_step: steps[0],
+            largest_step: steps[steps.len() - 1],
+            mean_step,
+            step_variance,
+            distinct_steps,
+            span: degrees.iter().copied().fold(f64::NEG_INFINITY, f64::max) - degrees.iter().copied().fold(f64::INFINITY, f64::min)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::scl;
+
+    #[test]
+    fn diatonic_stats()
+    {
+        let diatonic = scl!{"12edo diatonic" 200.0 400.0 500.0 700.0 900.0 1100.0 1200.0};
+        let stats = diatonic.stats().unwrap();
+
+        assert_eq!(stats.smallest_step, 100.0);
+        assert_eq!(stats.largest_step, 200.0);
+        assert!((stats.mean_step - 1200.0/7.0).abs() < 1e-9);
+        assert!((stats.step_variance - 10.0/49.0*10000.0).abs() < 1e-6);
+        assert_eq!(stats.distinct_steps, 2);
+        assert_eq!(stats.span, 1200.0);
+    }
+}
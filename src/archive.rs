@@ -0,0 +1,172 @@
+use std::{error::Error, fmt::Display, fs::{self, File}, io::{self, Read, Seek}, path::{Path, PathBuf}};
+
+use zip::{result::ZipError, ZipArchive};
+
+use crate::{Diagnostic, LibraryEntry, ParseScaleError, Scale, ScaleLibrary};
+
+/// Where the Huygens-Fokker Foundation publishes the Scala scale archive.
+pub const ARCHIVE_URL: &str = "https://www.huygens-fokker.org/docs/scales.zip";
+
+const CACHE_FILE_NAME: &str = "scales.zip";
+
+#[derive(Debug)]
+pub enum ArchiveError
+{
+    IO(io::Error),
+    Http(Box<ureq::Error>),
+    Zip(ZipError)
+}
+impl From<io::Error> for ArchiveError
+{
+    fn from(value: io::Error) -> Self
+    {
+        Self::IO(value)
+    }
+}
+impl From<ureq::Error> for ArchiveError
+{
+    fn from(value: ureq::Error) -> Self
+    {
+        Self::Http(Box::new(value))
+    }
+}
+impl From<ZipError> for ArchiveError
+{
+    fn from(value: ZipError) -> Self
+    {
+        Self::Zip(value)
+    }
+}
+impl Display for ArchiveError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Self::IO(err) => write!(f, "I/O error: {}", err),
+            Self::Http(err) => write!(f, "download failed: {}", err),
+            Self::Zip(err) => write!(f, "invalid archive: {}", err)
+        }
+    }
+}
+impl Error for ArchiveError
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)>
+    {
+        match self
+        {
+            Self::IO(err) => Some(err),
+            Self::Http(err) => Some(err.as_ref()),
+            Self::Zip(err) => Some(err)
+        }
+    }
+}
+
+/// The parsed scale archive.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScalaArchive
+{
+    pub library: ScaleLibrary,
+    /// Entries that couldn't be parsed, and why.
+    pub rejected: Vec<(PathBuf, ParseScaleError)>
+}
+
+impl ScalaArchive
+{
+    /// Loads the archive from `cache_dir`, downloading it there first if it isn't cached yet.
+    pub fn fetch(cache_dir: impl AsRef<Path>) -> Result<Self, ArchiveError>
+    {
+        let cache_dir = cache_dir.as_ref();
+        let path = cache_dir.join(CACHE_FILE_NAME);
+        if !path.is_file()
+        {
+            fs::create_dir_all(cache_dir)?;
+            Self::download(&path)?;
+        }
+        Self::from_zip(File::open(path)?)
+    }
+
+    /// Downloads the archive zip to `path`, replacing any file already there.
+    pub fn download(path: impl AsRef<Path>) -> Result<(), ArchiveError>
+    {
+        let path = path.as_ref();
+        let partial = path.with_extension("part");
+
+        let response = ureq::get(ARCHIVE_URL).call()?;
+        io::copy(&mut response.into_reader(), &mut File::create(&partial)?)?;
+        fs::rename(partial, path)?;
+
+        Ok(())
+    }
+
+    /// Parses every `.scl` entry of an archive zip.
+    ///
+    /// Invalid UTF-8 is replaced and entries are read with [`Scale::parse_with_diagnostics`](crate::Scale::parse_with_diagnostics),
+    /// so entries with only warnings, like a note count off by one, are kept.
+    /// Entries with errors are set aside in `rejected` with their first error.
+    pub fn from_zip(reader: impl Read + Seek) -> Result<Self, ArchiveError>
+    {
+        let mut zip = ZipArchive::new(reader)?;
+        let mut archive = Self::default();
+
+        for i in 0..zip.len()
+        {
+            let mut file = zip.by_index(i)?;
+            let Some(path) = file.enclosed_name()
+            else
+            {
+                continue
+            };
+            if !file.is_file() || !path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("scl"))
+            {
+                continue
+            }
+
+            let mut bytes = vec![];
+            file.read_to_end(&mut bytes)?;
+            let (scale, diagnostics) = Scale::parse_with_diagnostics(&String::from_utf8_lossy(&bytes));
+            match diagnostics.into_iter().find(Diagnostic::is_error)
+            {
+                None => archive.library.entries.push(LibraryEntry {
+                    path: Some(path),
+                    scale
+                }),
+                Some(diagnostic) => archive.rejected.push((path, diagnostic.error))
+            }
+        }
+        archive.library.entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(archive)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::{io::{Cursor, Write}, path::PathBuf};
+
+    use zip::{write::SimpleFileOptions, ZipWriter};
+
+    use crate::{ParseScaleError, ScalaArchive};
+
+    #[test]
+    fn parse_zip()
+    {
+        let mut zip = ZipWriter::new(Cursor::new(vec![]));
+        zip.start_file("scl/good.scl", SimpleFileOptions::default()).unwrap();
+        zip.write_all(b"! good.scl\nA good scale\n2\n!\n3/2\n2/1\n").unwrap();
+        zip.start_file("scl/bad.scl", SimpleFileOptions::default()).unwrap();
+        zip.write_all(b"! bad.scl\nA bad scale\n2\n!\n3/2x\n2/1\n").unwrap();
+        zip.start_file("scl/short.scl", SimpleFileOptions::default()).unwrap();
+        zip.write_all(b"! short.scl\nA short scale\n2\n!\n3/2\n").unwrap();
+        zip.start_file("scl/readme.txt", SimpleFileOptions::default()).unwrap();
+        zip.write_all(b"not a scale").unwrap();
+        let bytes = zip.finish().unwrap();
+
+        let archive = ScalaArchive::from_zip(bytes).unwrap();
+        assert_eq!(archive.library.len(), 2);
+        assert_eq!(archive.library.entries[0].scale.name, "A good scale");
+        assert_eq!(archive.library.entries[1].scale.name, "A short scale");
+        assert_eq!(archive.rejected, vec![(PathBuf::from("scl/bad.scl"), ParseScaleError::TrailingCharacters("x".to_string()))]);
+    }
+}
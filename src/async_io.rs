@@ -0,0 +1,75 @@
+use std::{io, path::Path};
+
+use tokio::{fs, io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader}};
+
+use crate::{LibraryEntry, Scale, ScaleLibrary, ScaleParser, SerdeScalaError};
+
+impl Scale
+{
+    /// Parses a scale line by line as it is read. Invalid UTF-8 is replaced.
+    pub async fn from_async_reader(reader: impl AsyncRead + Unpin) -> Result<Self, SerdeScalaError>
+    {
+        let mut reader = BufReader::new(reader);
+        let mut parser = ScaleParser::new();
+        let mut line = vec![];
+        while reader.read_until(b'\n', &mut line).await? > 0
+        {
+            parser.feed_line(&String::from_utf8_lossy(&line))?;
+            line.clear();
+        }
+        Ok(parser.finish()?)
+    }
+
+    pub async fn write_to_async(&self, mut writer: impl AsyncWrite + Unpin) -> io::Result<()>
+    {
+        writer.write_all(self.to_string().as_bytes()).await?;
+        writer.flush().await
+    }
+}
+
+impl ScaleLibrary
+{
+    /// Like [`ScaleLibrary::load_dir`], without blocking the runtime.
+    pub async fn load_dir_async(path: impl AsRef<Path>) -> Result<Self, SerdeScalaError>
+    {
+        let mut paths = vec![];
+        let mut entries = fs::read_dir(path).await?;
+        while let Some(entry) = entries.next_entry().await?
+        {
+            let path = entry.path();
+            if entry.file_type().await?.is_file() && path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("scl"))
+            {
+                paths.push(path)
+            }
+        }
+        paths.sort();
+
+        let mut library = Self::new();
+        for path in paths
+        {
+            let scale = Scale::from_async_reader(fs::File::open(&path).await?).await?;
+            library.entries.push(LibraryEntry {
+                path: Some(path),
+                scale
+            })
+        }
+        Ok(library)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, Scale, ScaleLibrary};
+
+    #[tokio::test]
+    async fn async_round_trip()
+    {
+        let scale = scl!{"async" 9/8 5/4 701.955 2/1};
+        let mut bytes = vec![];
+        scale.write_to_async(&mut bytes).await.unwrap();
+
+        assert_eq!(Scale::from_async_reader(bytes.as_slice()).await.unwrap(), scale.to_string().parse().unwrap());
+        assert_eq!(ScaleLibrary::load_dir_async("scl").await.unwrap().len(), ScaleLibrary::load_dir("scl").unwrap().len());
+    }
+}
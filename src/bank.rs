@@ -0,0 +1,317 @@
+use std::{error::Error, fmt::Display, num::ParseIntError, str::FromStr};
+
+use crate::{KeyboardMapping, ParseKeyboardMappingError, ParseScaleError, Tuning, TuningDump};
+
+const MAPPING_HEADER: &str = "[mapping]";
+
+/// A tuning in a bank, selected by a bank and program number like a MIDI program change.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TuningBankEntry
+{
+    pub bank: u8,
+    pub program: u8,
+    pub tuning: Tuning
+}
+
+/// Tunings to switch between, such as one per section of a song.
+///
+/// Written as a bank file: a name line, then every tuning under a `[program <bank> <program>]` line as the text
+/// of its .scl file, followed by the text of its .kbm file under a `[mapping]` line. A tuning without a mapping
+/// gets the standard linear one.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TuningBank
+{
+    pub name: String,
+    /// Kept sorted by bank and program.
+    pub entries: Vec<TuningBankEntry>
+}
+
+impl TuningBank
+{
+    pub fn new(name: impl Into<String>) -> Self
+    {
+        Self {
+            name: name.into(),
+            entries: vec![]
+        }
+    }
+
+    /// Puts a tuning in a slot, returning the tuning that was there.
+    pub fn insert(&mut self, bank: u8, program: u8, tuning: Tuning) -> Option<Tuning>
+    {
+        match self.entries.binary_search_by_key(&(bank, program), |entry| (entry.bank, entry.program))
+        {
+            Ok(i) => Some(std::mem::replace(&mut self.entries[i].tuning, tuning)),
+            Err(i) =>
+            {
+                self.entries.insert(i, TuningBankEntry {
+                    bank,
+                    program,
+                    tuning
+                });
+                None
+            }
+        }
+    }
+
+    pub fn get(&self, bank: u8, program: u8) -> Option<&Tuning>
+    {
+        self.entries.iter()
+            .find(|entry| entry.bank == bank && entry.program == program)
+            .map(|entry| &entry.tuning)
+    }
+
+    pub fn remove(&mut self, bank: u8, program: u8) -> Option<Tuning>
+    {
+        let i = self.entries.iter().position(|entry| entry.bank == bank && entry.program == program)?;
+        Some(self.entries.remove(i).tuning)
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.entries.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &TuningBankEntry>
+    {
+        self.entries.iter()
+    }
+
+    /// A key-based tuning dump of every tuning to its bank and program, to load a whole bank into a synth
+    /// before switching between them with [`mts_tuning_select`](crate::mts_tuning_select).
+    pub fn tuning_dumps(&self) -> Vec<TuningDump>
+    {
+        self.entries.iter()
+            .map(|entry| TuningDump {
+                bank: Some(entry.bank),
+                ..entry.tuning.tuning_dump(entry.program)
+            })
+            .collect()
+    }
+}
+
+impl Display for TuningBank
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        writeln!(f, "{}", self.name)?;
+        for entry in &self.entries
+        {
+            writeln!(f, "[program {} {}]", entry.bank, entry.program)?;
+            write!(f, "{}", entry.tuning.scale)?;
+            writeln!(f, "{}", MAPPING_HEADER)?;
+            write!(f, "{}", entry.tuning.mapping)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseTuningBankError
+{
+    MissingName,
+    /// A line starting with `[` that isn't a `[program <bank> <program>]` or `[mapping]` line.
+    InvalidHeader(String),
+    ParseInt(ParseIntError),
+    /// Text before the first program.
+    UnexpectedText(String),
+    DuplicateProgram(u8, u8),
+    ParseScale(ParseScaleError),
+    ParseKeyboardMapping(ParseKeyboardMappingError)
+}
+impl From<ParseIntError> for ParseTuningBankError
+{
+    fn from(value: ParseIntError) -> Self
+    {
+        Self::ParseInt(value)
+    }
+}
+impl From<ParseScaleError> for ParseTuningBankError
+{
+    fn from(value: ParseScaleError) -> Self
+    {
+        Self::ParseScale(value)
+    }
+}
+impl From<ParseKeyboardMappingError> for ParseTuningBankError
+{
+    fn from(value: ParseKeyboardMappingError) -> Self
+    {
+        Self::ParseKeyboardMapping(value)
+    }
+}
+impl Display for ParseTuningBankError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Self::MissingName => write!(f, "missing bank name"),
+            Self::InvalidHeader(line) => write!(f, "invalid section header `{}`", line),
+            Self::ParseInt(err) => write!(f, "invalid bank or program number: {}", err),
+            Self::UnexpectedText(line) => write!(f, "unexpected `{}` before the first program", line),
+            Self::DuplicateProgram(bank, program) => write!(f, "bank {} program {} is given twice", bank, program),
+            Self::ParseScale(err) => write!(f, "could not parse scale: {}", err),
+            Self::ParseKeyboardMapping(err) => write!(f, "could not parse keyboard mapping: {}", err)
+        }
+    }
+}
+impl Error for ParseTuningBankError
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)>
+    {
+        match self
+        {
+            Self::ParseInt(err) => Some(err),
+            Self::ParseScale(err) => Some(err),
+            Self::ParseKeyboardMapping(err) => Some(err),
+            _ => None
+        }
+    }
+}
+
+/// A program of a bank file being read, with the text of its scale and mapping so far.
+struct Section
+{
+    bank: u8,
+    program: u8,
+    scl: String,
+    kbm: Option<String>
+}
+
+impl Section
+{
+    /// Whether the scale has no description yet, which may itself start with `[`, like `[draft] 12-EDO`.
+    fn awaits_description(&self) -> bool
+    {
+        self.kbm.is_none() && self.scl.lines().all(|line| line.trim().is_empty() || line.trim_start().starts_with('!'))
+    }
+
+    fn finish(self, bank: &mut TuningBank) -> Result<(), ParseTuningBankError>
+    {
+        let mapping = match self.kbm
+        {
+            Some(kbm) => kbm.parse()?,
+            None => KeyboardMapping::default()
+        };
+        let tuning = Tuning::new(self.scl.parse()?, mapping);
+        if bank.insert(self.bank, self.program, tuning).is_some()
+        {
+            return Err(ParseTuningBankError::DuplicateProgram(self.bank, self.program))
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TuningBank
+{
+    type Err = ParseTuningBankError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let mut lines = s.lines();
+        let name = lines.by_ref()
+            .find(|line| !line.trim_start().starts_with('!'))
+            .ok_or(ParseTuningBankError::MissingName)?;
+        let mut bank = TuningBank::new(name.trim());
+
+        let mut section: Option<Section> = None;
+        for line in lines
+        {
+            let trimmed = line.trim();
+            if trimmed == MAPPING_HEADER
+            {
+                match &mut section
+                {
+                    Some(section) if section.kbm.is_none() => section.kbm = Some(String::new()),
+                    _ => return Err(ParseTuningBankError::InvalidHeader(trimmed.to_string()))
+                }
+            }
+            else if let Some(numbers) = trimmed.strip_prefix("[program ").and_then(|rest| rest.strip_suffix(']'))
+            {
+                let Some((bank_number, program)) = numbers.trim().split_once(char::is_whitespace)
+                else
+                {
+                    return Err(ParseTuningBankError::InvalidHeader(trimmed.to_string()))
+                };
+                if let Some(section) = section.take()
+                {
+                    section.finish(&mut bank)?;
+                }
+                section = Some(Section {
+                    bank: bank_number.trim().parse()?,
+                    program: program.trim().parse()?,
+                    scl: String::new(),
+                    kbm: None
+                });
+            }
+            else if trimmed.starts_with('[') && !section.as_ref().is_some_and(Section::awaits_description)
+            {
+                return Err(ParseTuningBankError::InvalidHeader(trimmed.to_string()))
+            }
+            else
+            {
+                match &mut section
+                {
+                    Some(Section { kbm: Some(text), .. } | Section { kbm: None, scl: text, .. }) =>
+                    {
+                        *text += line;
+                        text.push('\n');
+                    },
+                    None if trimmed.is_empty() || trimmed.starts_with('!') => (),
+                    None => return Err(ParseTuningBankError::UnexpectedText(trimmed.to_string()))
+                }
+            }
+        }
+        if let Some(section) = section
+        {
+            section.finish(&mut bank)?;
+        }
+        Ok(bank)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{kbm, scl, KeyboardMapping, ParseTuningBankError, Scale, Tuning, TuningBank};
+
+    #[test]
+    fn tuning_bank()
+    {
+        let mut bank = TuningBank::new("song");
+        bank.insert(0, 1, Tuning::new(scl!{"ji" 9/8 5/4 2/1}, kbm!{3 0 127 60 60 261.0 3 0 1 2}));
+        bank.insert(0, 0, Tuning::new(Scale::edo(12), KeyboardMapping::default()));
+        assert_eq!(bank.entries.iter().map(|entry| entry.program).collect::<Vec<_>>(), [0, 1]);
+        assert_eq!(bank.get(0, 1).unwrap().scale.name, "ji");
+
+        let text = bank.to_string();
+        assert!(text.starts_with("song\n[program 0 0]\n! Generated scale:\n"));
+        assert_eq!(text.parse::<TuningBank>().unwrap(), bank);
+
+        let dumps = bank.tuning_dumps();
+        assert_eq!(dumps[1].to_sysex()[..7], [0xf0, 0x7e, 0x7f, 0x08, 0x04, 0, 1]);
+
+        let short: TuningBank = "! bank\nshort\n[program 2 5]\nlinear\n1\n2/1\n".parse().unwrap();
+        assert_eq!(short.get(2, 5).unwrap().mapping, KeyboardMapping::default());
+        assert_eq!(
+            "twice\n[program 0 0]\na\n1\n2/1\n[program 0 0]\nb\n1\n2/1\n".parse::<TuningBank>(),
+            Err(ParseTuningBankError::DuplicateProgram(0, 0))
+        );
+
+        let mut draft = TuningBank::new("drafts");
+        let mut scale = Scale::edo(12);
+        scale.name = "[draft] 12-EDO".to_string();
+        draft.insert(0, 0, Tuning::new(scale, KeyboardMapping::default()));
+        assert_eq!(draft.to_string().parse::<TuningBank>().unwrap(), draft);
+        assert_eq!(
+            "typo\n[program 0 0]\na\n1\n2/1\n[progam 0 1]\n".parse::<TuningBank>(),
+            Err(ParseTuningBankError::InvalidHeader("[progam 0 1]".to_string()))
+        );
+    }
+}
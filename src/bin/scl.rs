@@ -0,0 +1,175 @@
+//! Command line tool for Scala scale files.
+
+use std::{env, fs, path::Path, process::ExitCode};
+
+use serde_scala::{Diagnostic, EdoWeighting, KeyboardMapping, Scale, SerdeScalaError, Severity, Tun, Tuning};
+
+const USAGE: &str = "\
+usage:
+    scl validate <file.scl|file.kbm>
+    scl convert <file.scl> --to <scl|tun> [--kbm <file.kbm>]
+    scl generate edo <divisions>
+    scl analyze <file.scl> [--show <summary|intervals|spectrum|edo>]";
+
+fn read(path: &str) -> Result<String, SerdeScalaError>
+{
+    let bytes = fs::read(path)?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// The value following `--name`, if given.
+fn option<'a>(args: &'a [String], name: &str) -> Option<&'a str>
+{
+    args.iter()
+        .position(|arg| arg == name)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
+}
+
+fn validate(path: &str) -> Result<bool, SerdeScalaError>
+{
+    let text = read(path)?;
+    if Path::new(path).extension().is_some_and(|extension| extension.eq_ignore_ascii_case("kbm"))
+    {
+        return match text.parse::<KeyboardMapping>()
+        {
+            Ok(_) => Ok(true),
+            Err(err) =>
+            {
+                eprintln!("{}: {}", path, err);
+                Ok(false)
+            }
+        }
+    }
+
+    let (_, diagnostics) = Scale::parse_with_diagnostics(&text);
+    for diagnostic in diagnostics.iter()
+    {
+        let severity = match diagnostic.severity
+        {
+            Severity::Error => "error",
+            Severity::Warning => "warning"
+        };
+        eprintln!("{}:{}: {}: {}", path, diagnostic.line, severity, diagnostic.error);
+    }
+    Ok(!diagnostics.iter().any(Diagnostic::is_error))
+}
+
+fn convert(path: &str, args: &[String]) -> Result<bool, SerdeScalaError>
+{
+    let scale: Scale = read(path)?.parse()?;
+    match option(args, "--to")
+    {
+        Some("scl") => print!("{}", scale),
+        Some("tun") =>
+        {
+            let mapping = match option(args, "--kbm")
+            {
+                Some(kbm) => read(kbm)?.parse()?,
+                None => KeyboardMapping::default()
+            };
+            print!("{}", Tun::from(Tuning::new(scale, mapping)))
+        },
+        _ =>
+        {
+            eprintln!("{}", USAGE);
+            return Ok(false)
+        }
+    }
+    Ok(true)
+}
+
+fn generate(args: &[String]) -> bool
+{
+    match args
+    {
+        [kind, divisions] if kind == "edo" => match divisions.parse()
+        {
+            Ok(divisions) if divisions > 0 =>
+            {
+                print!("{}", Scale::edo(divisions));
+                true
+            },
+            _ =>
+            {
+                eprintln!("invalid number of divisions: {}", divisions);
+                false
+            }
+        },
+        _ =>
+        {
+            eprintln!("{}", USAGE);
+            false
+        }
+    }
+}
+
+fn analyze(path: &str, args: &[String]) -> Result<bool, SerdeScalaError>
+{
+    let scale: Scale = read(path)?.parse()?;
+    match option(args, "--show").unwrap_or("summary")
+    {
+        "summary" =>
+        {
+            println!("{}", scale.name);
+            println!("notes: {}", scale.pitches.len());
+            if let Some(stats) = scale.stats()
+            {
+                println!("steps: {:.3} to {:.3} cents, {} distinct", stats.smallest_step, stats.largest_step, stats.distinct_steps);
+            }
+            println!("propriety: {:?}", scale.propriety());
+            println!("constant structure: {}", scale.is_constant_structure(1e-3));
+            if let Some(limit) = scale.prime_limit()
+            {
+                println!("prime limit: {}", limit);
+            }
+            if let Some(mos) = scale.mos(1e-3)
+            {
+                println!("mos: {}", mos.pattern);
+            }
+        },
+        "intervals" => print!("{}", scale.interval_matrix()),
+        "spectrum" => for size in scale.interval_spectrum(1e-3)
+        {
+            println!("{:>9.3} x{}", size.cents, size.count);
+        },
+        "edo" => for approximation in scale.best_edos(5..=72, EdoWeighting::Unweighted).iter().take(5)
+        {
+            println!("{:>3}-EDO  max {:.3}  mean {:.3}", approximation.edo, approximation.max_error, approximation.mean_error);
+        },
+        _ =>
+        {
+            eprintln!("{}", USAGE);
+            return Ok(false)
+        }
+    }
+    Ok(true)
+}
+
+fn main() -> ExitCode
+{
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.as_slice()
+    {
+        [command, path, rest @ ..] if command == "validate" && rest.is_empty() => validate(path),
+        [command, path, rest @ ..] if command == "convert" => convert(path, rest),
+        [command, rest @ ..] if command == "generate" => Ok(generate(rest)),
+        [command, path, rest @ ..] if command == "analyze" => analyze(path, rest),
+        _ =>
+        {
+            eprintln!("{}", USAGE);
+            Ok(false)
+        }
+    };
+
+    match result
+    {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) =>
+        {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use std::{error::Error, fmt::Display};
+
+use num_rational::Ratio;
+
+use crate::{Pitch, Scale};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildScaleError
+{
+    /// The scale would have no pitches at all.
+    Empty,
+    /// The pitch at this index has a zero numerator or denominator.
+    ZeroRatio(usize),
+    /// The pitch at this index is NaN or infinite.
+    NonFiniteCents(usize),
+    /// The pitch at this index is not below the period.
+    NotBelowPeriod(usize)
+}
+impl Display for BuildScaleError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Self::Empty => write!(f, "scale has no pitches"),
+            Self::ZeroRatio(i) => write!(f, "pitch {} is a ratio with a zero term", i),
+            Self::NonFiniteCents(i) => write!(f, "pitch {} is not a finite cents value", i),
+            Self::NotBelowPeriod(i) => write!(f, "pitch {} is not below the period", i)
+        }
+    }
+}
+impl Error for BuildScaleError {}
+
+/// A typed alternative to the [`scl!`](crate::scl) macro for scales assembled at runtime.
+///
+/// ```
+/// use serde_scala::ScaleBuilder;
+///
+/// let scale = ScaleBuilder::new()
+///     .name("fifth and a tempered third")
+///     .cents(386.0)
+///     .ratio(3, 2)
+///     .period_octave()
+///     .build()
+///     .unwrap();
+/// assert_eq!(scale.pitches.len(), 3);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScaleBuilder
+{
+    name: String,
+    pitches: Vec<Pitch>,
+    period: Option<Pitch>
+}
+
+impl ScaleBuilder
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self
+    {
+        self.name = name.into();
+        self
+    }
+
+    pub fn pitch(mut self, pitch: Pitch) -> Self
+    {
+        self.pitches.push(pitch);
+        self
+    }
+
+    pub fn ratio(self, numer: u128, denom: u128) -> Self
+    {
+        self.pitch(Pitch::Ratio(Ratio::new_raw(numer, denom)))
+    }
+
+    pub fn cents(self, cents: f64) -> Self
+    {
+        self.pitch(Pitch::Cents(cents))
+    }
+
+    pub fn pitches(mut self, pitches: impl IntoIterator<Item = Pitch>) -> Self
+    {
+        self.pitches.extend(pitches);
+        self
+    }
+
+    /// Sets the interval of repetition, which is appended as the last pitch and must lie above all other pitches.
+    pub fn period(mut self, period: Pitch) -> Self
+    {
+        self.period = Some(period);
+        self
+    }
+
+    pub fn period_octave(self) -> Self
+    {
+        self.period(Pitch::ratio(2, 1))
+    }
+
+    pub fn build(self) -> Result<Scale, BuildScaleError>
+    {
+        let mut pitches = self.pitches;
+        pitches.extend(self.period);
+        if pitches.is_empty()
+        {
+            return Err(BuildScaleError::Empty)
+        }
+
+        for (i, pitch) in pitches.iter_mut().enumerate()
+        {
+            match *pitch
+            {
+                Pitch::Ratio(ratio) => {
+                    if *ratio.numer() == 0 || *ratio.denom() == 0
+                    {
+                        return Err(BuildScaleError::ZeroRatio(i))
+                    }
+                    *pitch = Pitch::Ratio(Ratio::new(*ratio.numer(), *ratio.denom()));
+                },
+                Pitch::Cents(cents) => if !cents.is_finite()
+                {
+                    return Err(BuildScaleError::NonFiniteCents(i))
+                }
+            }
+        }
+
+        if let Some(period) = self.period
+        {
+            let period = period.to_cents();
+            if let Some(i) = pitches[..pitches.len() - 1].iter().position(|pitch| pitch.to_cents() >= period)
+            {
+                return Err(BuildScaleError::NotBelowPeriod(i))
+            }
+        }
+
+        Ok(Scale::new(self.name, pitches))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, BuildScaleError, ScaleBuilder};
+
+    #[test]
+    fn build()
+    {
+        let scale = ScaleBuilder::new()
+            .name("pentatonic")
+            .ratio(18, 16)
+            .ratio(5, 4)
+            .ratio(3, 2)
+            .cents(884.359)
+            .period_octave()
+            .build();
+        assert_eq!(scale, Ok(scl!{"pentatonic" 9/8 5/4 3/2 884.359 2/1}));
+
+        assert_eq!(ScaleBuilder::new().build(), Err(BuildScaleError::Empty));
+        assert_eq!(ScaleBuilder::new().ratio(3, 0).build(), Err(BuildScaleError::ZeroRatio(0)));
+        assert_eq!(ScaleBuilder::new().ratio(3, 2).cents(f64::NAN).build(), Err(BuildScaleError::NonFiniteCents(1)));
+        assert_eq!(ScaleBuilder::new().ratio(9, 4).period_octave().build(), Err(BuildScaleError::NotBelowPeriod(0)));
+    }
+}
@@ -0,0 +1,150 @@
+use std::cmp::Ordering;
+
+use num_rational::Ratio;
+
+use crate::{Pitch, Scale};
+
+/// A pitch in a form that can be hashed and compared exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CanonicalPitch
+{
+    /// Millionths of a cent.
+    Cents(i64),
+    /// A reduced ratio.
+    Ratio(u128, u128)
+}
+
+impl CanonicalPitch
+{
+    pub fn to_pitch(self) -> Pitch
+    {
+        match self
+        {
+            Self::Cents(micro_cents) => Pitch::Cents(micro_cents as f64/1e6),
+            Self::Ratio(numer, denom) => Pitch::Ratio(Ratio::new_raw(numer, denom))
+        }
+    }
+}
+
+impl From<Pitch> for CanonicalPitch
+{
+    fn from(value: Pitch) -> Self
+    {
+        match value
+        {
+            Pitch::Cents(cents) => Self::Cents((cents*1e6).round() as i64),
+            Pitch::Ratio(ratio) =>
+            {
+                let ratio = ratio.reduced();
+                Self::Ratio(*ratio.numer(), *ratio.denom())
+            }
+        }
+    }
+}
+
+/// The pitches of a scale, sorted and without duplicates, so that scales listing the same pitches are equal.
+///
+/// The name is left out. Cents are rounded to millionths, and are never equal to a ratio.
+///
+/// ```
+/// use std::collections::HashSet;
+///
+/// use serde_scala::Scale;
+///
+/// let a = Scale::from_ratios("a", [(5, 4), (3, 2), (2, 1)]);
+/// let b = Scale::from_ratios("b", [(10, 8), (2, 1), (3, 2)]);
+///
+/// let unique: HashSet<_> = [a, b].iter().map(Scale::canonical).collect();
+/// assert_eq!(unique.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CanonicalScale
+{
+    pub pitches: Vec<CanonicalPitch>
+}
+
+impl CanonicalScale
+{
+    pub fn to_scale(&self, name: impl Into<String>) -> Scale
+    {
+        Scale::new(name.into(), self.pitches.iter().map(|pitch| pitch.to_pitch()).collect())
+    }
+}
+
+impl Scale
+{
+    pub fn canonical(&self) -> CanonicalScale
+    {
+        let mut pitches: Vec<CanonicalPitch> = self.pitches.iter().map(|&pitch| pitch.into()).collect();
+        pitches.sort_by(|a, b| a.to_pitch()
+            .to_cents()
+            .total_cmp(&b.to_pitch().to_cents())
+            .then_with(|| match (a, b)
+            {
+                (CanonicalPitch::Cents(_), CanonicalPitch::Ratio(..)) => Ordering::Less,
+                (CanonicalPitch::Ratio(..), CanonicalPitch::Cents(_)) => Ordering::Greater,
+                _ => Ordering::Equal
+            })
+        );
+        pitches.dedup();
+        CanonicalScale {
+            pitches
+        }
+    }
+
+    /// Whether two scales have as many pitches, each within `tolerance` cents of the other's in order,
+    /// whether they're written as cents or ratios. The names are left out.
+    ///
+    /// ```
+    /// use serde_scala::{scl, Scale};
+    ///
+    /// let parsed: Scale = "12-EDO\n12\n100.0\n200.0\n300.0\n400.0\n500.0\n600.0\n700.0\n800.0\n900.0\n1000.0\n1100.0\n2/1\n".parse().unwrap();
+    /// assert_ne!(parsed, Scale::edo(12));
+    /// assert!(parsed.approx_eq(&Scale::edo(12), 1e-9));
+    /// ```
+    pub fn approx_eq(&self, other: &Scale, tolerance: f64) -> bool
+    {
+        self.pitches.len() == other.pitches.len()
+            && self.pitches.iter()
+                .zip(other.pitches.iter())
+                .all(|(a, b)| (a.to_cents() - b.to_cents()).abs() <= tolerance)
+    }
+
+    /// Like [`Scale::approx_eq`], but with the pitches of both sorted first and pitches within `tolerance` cents
+    /// of the one before left out, so scales listing the same pitches in another order are equal.
+    pub fn approx_eq_normalized(&self, other: &Scale, tolerance: f64) -> bool
+    {
+        let normalized = |scale: &Scale| {
+            let mut cents: Vec<f64> = scale.pitches.iter().map(|pitch| pitch.to_cents()).collect();
+            cents.sort_by(f64::total_cmp);
+            cents.dedup_by(|b, a| (*b - *a).abs() <= tolerance);
+            cents
+        };
+        let (a, b) = (normalized(self), normalized(other));
+        a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| (a - b).abs() <= tolerance)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, CanonicalPitch};
+
+    #[test]
+    fn canonical_form()
+    {
+        let a = scl!{"a" 3/2 9/8 702.0 2/1 3/2};
+        let b = scl!{"b" 9/8 6/4 702.0000000001 2/1};
+
+        assert_eq!(a.canonical(), b.canonical());
+        assert_eq!(a.canonical().pitches[1], CanonicalPitch::Ratio(3, 2));
+        assert_eq!(a.canonical().to_scale("a"), scl!{"a" 9/8 3/2 702.0 2/1});
+        assert_ne!(a.canonical(), scl!{"c" 9/8 3/2 2/1}.canonical());
+
+        assert!(!a.approx_eq(&b, 0.01));
+        assert!(a.approx_eq_normalized(&b, 0.01));
+        assert!(scl!{"c" 9/8 3/2 2/1}.approx_eq(&scl!{"d" 203.91 701.955 1200.0}, 0.001));
+        assert!(!scl!{"c" 9/8 3/2 2/1}.approx_eq(&scl!{"d" 203.91 701.955 1200.0}, 1e-6));
+        assert!(!a.approx_eq_normalized(&scl!{"c" 9/8 3/2 2/1}, 0.01));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{Degree, Pitch, Scale};
+
+/// A small interval with a name, as they are tempered out or told apart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comma
+{
+    pub name: &'static str,
+    pub pitch: Pitch
+}
+
+impl Comma
+{
+    pub const fn new(name: &'static str, numer: u128, denom: u128) -> Self
+    {
+        Self {
+            name,
+            pitch: Pitch::ratio(numer, denom)
+        }
+    }
+}
+
+pub const PYTHAGOREAN_COMMA: Comma = Comma::new("Pythagorean comma", 531441, 524288);
+pub const SYNTONIC_COMMA: Comma = Comma::new("syntonic comma", 81, 80);
+pub const SCHISMA: Comma = Comma::new("schisma", 32805, 32768);
+pub const DIASCHISMA: Comma = Comma::new("diaschisma", 2048, 2025);
+pub const KLEISMA: Comma = Comma::new("kleisma", 15625, 15552);
+pub const LESSER_DIESIS: Comma = Comma::new("lesser diesis", 128, 125);
+pub const GREATER_DIESIS: Comma = Comma::new("greater diesis", 648, 625);
+pub const MAGIC_COMMA: Comma = Comma::new("magic comma", 3125, 3072);
+pub const SEPTIMAL_COMMA: Comma = Comma::new("septimal comma", 64, 63);
+pub const SEPTIMAL_KLEISMA: Comma = Comma::new("septimal kleisma", 225, 224);
+pub const SEPTIMAL_QUARTER_TONE: Comma = Comma::new("septimal quarter tone", 36, 35);
+pub const JUBILISMA: Comma = Comma::new("jubilisma", 50, 49);
+pub const UNDECIMAL_QUARTER_TONE: Comma = Comma::new("undecimal quarter tone", 33, 32);
+pub const PTOLEMISMA: Comma = Comma::new("ptolemisma", 100, 99);
+
+/// Every named comma, by prime limit.
+pub const COMMAS: [Comma; 14] = [
+    PYTHAGOREAN_COMMA,
+    SYNTONIC_COMMA,
+    SCHISMA,
+    DIASCHISMA,
+    KLEISMA,
+    LESSER_DIESIS,
+    GREATER_DIESIS,
+    MAGIC_COMMA,
+    SEPTIMAL_COMMA,
+    SEPTIMAL_KLEISMA,
+    SEPTIMAL_QUARTER_TONE,
+    JUBILISMA,
+    UNDECIMAL_QUARTER_TONE,
+    PTOLEMISMA
+];
+
+/// A named comma found between two degrees, the interval from `from` up to `to`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CommaMatch
+{
+    pub comma: Comma,
+    pub from: Degree,
+    pub to: Degree
+}
+
+impl Scale
+{
+    /// Every pair of degrees an exact named comma apart, considering only commas within the prime limit `limit`.
+    ///
+    /// ```
+    /// use serde_scala::{scl, Degree, SYNTONIC_COMMA};
+    ///
+    /// let scale = scl!{"two seconds" 10/9 9/8 2/1};
+    /// let found = scale.find_commas(5);
+    /// assert_eq!(found[0].comma, SYNTONIC_COMMA);
+    /// assert_eq!((found[0].from, found[0].to), (Degree(1), Degree(2)));
+    /// ```
+    pub fn find_commas(&self, limit: u128) -> Vec<CommaMatch>
+    {
+        let commas: Vec<Comma> = COMMAS.into_iter()
+            .filter(|comma| comma.pitch.prime_limit().is_some_and(|prime_limit| prime_limit <= limit))
+            .collect();
+        let degrees: Vec<Pitch> = self.degrees().collect();
+
+        let mut found = vec![];
+        for (i, &a) in degrees.iter().enumerate()
+        {
+            for (j, &b) in degrees.iter().enumerate().skip(i + 1)
+            {
+                let (from, to, interval) = match b/a
+                {
+                    Pitch::Cents(_) => continue,
+                    interval @ Pitch::Ratio(ratio) if *ratio.numer() >= *ratio.denom() => (i, j, interval),
+                    interval => (j, i, Pitch::unison()/interval)
+                };
+                if let Some(&comma) = commas.iter().find(|comma| comma.pitch == interval)
+                {
+                    found.push(CommaMatch {
+                        comma,
+                        from: Degree(from),
+                        to: Degree(to)
+                    })
+                }
+            }
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, Degree, LESSER_DIESIS, SEPTIMAL_KLEISMA};
+
+    #[test]
+    fn find_commas()
+    {
+        let scale = scl!{"commas" 16/15 15/14 5/4 32/25 3/2 700.0 2/1};
+
+        let found = scale.find_commas(7);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].comma, SEPTIMAL_KLEISMA);
+        assert_eq!((found[0].from, found[0].to), (Degree(1), Degree(2)));
+        assert_eq!(found[1].comma, LESSER_DIESIS);
+        assert_eq!((found[1].from, found[1].to), (Degree(3), Degree(4)));
+
+        assert_eq!(scale.find_commas(5).len(), 1);
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{Pitch, Scale, EPSILON};
+
+impl Scale
+{
+    /// The cross set of two scales: every degree of one stacked on every degree of the other, octave reduced,
+    /// with duplicates removed and 2/1 as the period.
+    ///
+    /// Products of ratios stay exact.
+    ///
+    /// ```
+    /// use serde_scala::scl;
+    ///
+    /// let fifths = scl!{"fifths" 3/2 2/1};
+    /// let thirds = scl!{"thirds" 5/4 2/1};
+    /// assert_eq!(fifths.cross(&thirds).pitches, scl!{"" 5/4 3/2 15/8 2/1}.pitches);
+    /// ```
+    pub fn cross(&self, other: &Scale) -> Scale
+    {
+        let octave = Pitch::ratio(2, 1);
+        let mut pitches: Vec<Pitch> = self.degrees()
+            .flat_map(|a| other.degrees().map(move |b| (a*b).reduced_into(octave)))
+            .filter(|pitch| (EPSILON..octave.to_cents() - EPSILON).contains(&pitch.to_cents()))
+            .collect();
+        pitches.sort_by(|a, b| a.to_cents()
+            .total_cmp(&b.to_cents())
+            .then_with(|| matches!(a, Pitch::Cents(_)).cmp(&matches!(b, Pitch::Cents(_))))
+        );
+        pitches.dedup_by(|b, a| b.to_cents() - a.to_cents() < EPSILON);
+        pitches.push(octave);
+
+        Scale::new(format!("Cross set of {} and {}", self.name, other.name), pitches)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::scl;
+
+    #[test]
+    fn cross_set()
+    {
+        let triad = scl!{"triad" 5/4 3/2 2/1};
+        let product = triad.cross(&scl!{"dyad" 7/4 2/1});
+
+        assert_eq!(product, scl!{"Cross set of triad and dyad" 35/32 5/4 21/16 3/2 7/4 2/1});
+
+        let tempered = triad.cross(&scl!{"tempered" 700.0 1200.0});
+        assert_eq!(tempered.pitches.len(), 6);
+        assert!((tempered.pitches[1].to_cents() - 386.314).abs() < 1e-3);
+        assert!((tempered.pitches[0].to_cents() - 200.0 - 1.955).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{Degree, Pitch, Scale};
+
+impl Scale
+{
+    /// A copy with every pitch replaced by what `detune` makes of it, given its degree.
+    ///
+    /// ```
+    /// use serde_scala::{scl, Pitch};
+    ///
+    /// let stretched = scl!{"ji" 5/4 3/2 2/1}.detuned(|degree, pitch| Pitch::cents(pitch.to_cents() + degree.0 as f64));
+    /// assert_eq!(stretched.pitches[2].to_cents(), 1203.0);
+    /// ```
+    pub fn detuned(&self, mut detune: impl FnMut(Degree, Pitch) -> Pitch) -> Scale
+    {
+        let mut scale = self.clone();
+        for (i, pitch) in scale.pitches.iter_mut().enumerate()
+        {
+            *pitch = detune(Degree::from_pitch_index(i), *pitch);
+        }
+        scale
+    }
+
+    /// A copy with every pitch but the period moved by the cents `offset` gives its degree.
+    pub fn detuned_by(&self, mut offset: impl FnMut(Degree) -> f64) -> Scale
+    {
+        let period = self.pitches.len().checked_sub(1).map(Degree::from_pitch_index);
+        self.detuned(|degree, pitch| if Some(degree) == period
+        {
+            pitch
+        }
+        else
+        {
+            Pitch::Cents(pitch.to_cents() + offset(degree))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, Pitch};
+
+    #[test]
+    fn detune()
+    {
+        let scale = scl!{"ji" 9/8 5/4 2/1};
+
+        let detuned = scale.detuned_by(|degree| [0.0, 3.0, -2.0, 50.0][degree.0]);
+        assert!((detuned.pitches[0].to_cents() - 206.91).abs() < 1e-2);
+        assert!((detuned.pitches[1].to_cents() - 384.314).abs() < 1e-3);
+        assert_eq!(detuned.pitches[2], Pitch::ratio(2, 1));
+
+        assert_eq!(scale.detuned(|_, pitch| pitch), scale);
+    }
+}
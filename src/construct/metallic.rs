@@ -0,0 +1,97 @@
+use num_rational::Ratio;
+
+use crate::{Pitch, Scale};
+
+/// The golden ratio.
+pub const PHI: f64 = 1.618033988749895;
+
+/// The `n`th metallic mean, `(n + √(n² + 4))/2`: 1 for n = 0, the golden ratio for n = 1, the silver ratio for n = 2, ...
+pub fn metallic_mean(n: u32) -> f64
+{
+    let n = n as f64;
+    (n + (n*n + 4.0).sqrt())/2.0
+}
+
+/// The noble mediant of two fractions, `(a + φc)/(b + φd)` for `a/b` and `c/d`, the "most irrational" number
+/// between them.
+///
+/// ```
+/// use num_rational::Ratio;
+/// use serde_scala::noble_mediant;
+///
+/// // The golden generator of the diatonic scale as a fraction of the octave, between 2/5 and 3/7.
+/// let fraction = noble_mediant(Ratio::new(2, 5), Ratio::new(3, 7));
+/// assert!((fraction*1200.0 - 503.786).abs() < 1e-3);
+/// ```
+pub fn noble_mediant(a: Ratio<u128>, b: Ratio<u128>) -> f64
+{
+    (*a.numer() as f64 + PHI * *b.numer() as f64)/(*a.denom() as f64 + PHI * *b.denom() as f64)
+}
+
+impl Scale
+{
+    /// The scale made by stacking `notes - 1` generators above the unison, reduced by the period, which closes the scale.
+    /// Stays exact if the generator and the period are ratios.
+    ///
+    /// ```
+    /// use serde_scala::{scl, Pitch, Scale};
+    ///
+    /// let pentatonic = Scale::generated(Pitch::ratio(3, 2), Pitch::ratio(2, 1), 5);
+    /// assert_eq!(pentatonic.pitches, scl!{"" 9/8 81/64 3/2 27/16 2/1}.pitches);
+    /// ```
+    pub fn generated(generator: Pitch, period: Pitch, notes: usize) -> Scale
+    {
+        let mut pitches: Vec<Pitch> = generator.stack(period).take(notes.saturating_sub(1)).collect();
+        pitches.sort_by(|a, b| a.to_cents().total_cmp(&b.to_cents()));
+        if notes > 0
+        {
+            pitches.push(period);
+        }
+        Scale::new(format!("{} generated by {} in {}", notes, generator, period), pitches)
+    }
+
+    /// The scale generated by dividing the period logarithmically in the `n`th metallic mean,
+    /// so the generator and the rest of the period are in that proportion.
+    pub fn metallic(n: u32, period: Pitch, notes: usize) -> Scale
+    {
+        let generator = period.to_cents()/(1.0 + metallic_mean(n));
+        let mut scale = Self::generated(Pitch::Cents(generator), period, notes);
+        scale.name = format!("{} of metallic mean {} in {}", notes, n, period);
+        scale
+    }
+
+    /// The scale generated by dividing the period logarithmically in the golden ratio, the quintessential
+    /// moment of symmetry with steps in that same proportion at Fibonacci numbers of notes.
+    pub fn golden(period: Pitch, notes: usize) -> Scale
+    {
+        let mut scale = Self::metallic(1, period, notes);
+        scale.name = format!("{} golden in {}", notes, period);
+        scale
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{metallic_mean, Pitch, Scale, PHI};
+
+    #[test]
+    fn golden_scales()
+    {
+        assert!((metallic_mean(1) - PHI).abs() < 1e-15);
+        assert!((metallic_mean(2) - (1.0 + 2f64.sqrt())).abs() < 1e-15);
+
+        let golden = Scale::golden(Pitch::ratio(2, 1), 8);
+        assert_eq!(golden.pitches.len(), 8);
+        let cents: Vec<f64> = golden.degrees().map(|pitch| pitch.to_cents()).collect();
+        let mut steps: Vec<f64> = cents.windows(2).map(|w| w[1] - w[0]).collect();
+        steps.sort_by(f64::total_cmp);
+        steps.dedup_by(|a, b| (*a - *b).abs() < 1e-6);
+        assert_eq!(steps.len(), 2);
+        assert!((steps[1]/steps[0] - PHI).abs() < 1e-9);
+
+        let blackwood = Scale::generated(Pitch::cents(80.0), Pitch::cents(240.0), 3);
+        assert_eq!(blackwood.pitches, [Pitch::Cents(80.0), Pitch::Cents(160.0), Pitch::Cents(240.0)]);
+        assert!(Scale::generated(Pitch::ratio(3, 2), Pitch::ratio(2, 1), 0).pitches.is_empty());
+    }
+}
@@ -0,0 +1,9 @@
+mod cross;
+mod detune;
+mod metallic;
+mod morph;
+mod presets;
+mod stack;
+
+pub use metallic::*;
+pub use stack::*;
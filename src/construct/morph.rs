@@ -0,0 +1,97 @@
+use std::cmp::Ordering;
+
+use crate::Scale;
+
+impl Scale
+{
+    /// A scale between this one at `t = 0` and `other` at `t = 1`, interpolating corresponding degrees in cents.
+    ///
+    /// Scales with the same number of pitches are paired degree by degree. Otherwise, every degree of the larger one is paired with the degree
+    /// of the smaller one closest to it relative to the period, the periods with each other, so the result has as many
+    /// pitches as the larger.
+    ///
+    /// ```
+    /// use serde_scala::scl;
+    ///
+    /// let ji = scl!{"ji" 5/4 3/2 2/1};
+    /// let tempered = scl!{"tempered" 400.0 700.0 1200.0};
+    /// let halfway = ji.morph(&tempered, 0.5);
+    /// assert!((halfway.pitches[0].to_cents() - 393.157).abs() < 1e-3);
+    /// ```
+    pub fn morph(&self, other: &Scale, t: f64) -> Scale
+    {
+        let cents = |scale: &Scale| -> Vec<f64> { scale.pitches.iter().map(|pitch| pitch.to_cents()).collect() };
+        let (a, b) = (cents(self), cents(other));
+
+        let (from, to) = match a.len().cmp(&b.len())
+        {
+            Ordering::Equal => (a, b),
+            Ordering::Greater =>
+            {
+                let paired = Self::pair(&a, &b);
+                (a, paired)
+            },
+            Ordering::Less => (Self::pair(&b, &a), b)
+        };
+
+        Scale::from_cents(
+            format!("Morph of {} and {}", self.name, other.name),
+            from.iter().zip(to.iter()).map(|(x, y)| x + (y - x)*t)
+        )
+    }
+
+    /// Pairs every degree of `larger` with the degree of `smaller` closest to it relative to the period.
+    fn pair(larger: &[f64], smaller: &[f64]) -> Vec<f64>
+    {
+        let (Some(&larger_period), Some(&smaller_period)) = (larger.last(), smaller.last())
+        else
+        {
+            return vec![]
+        };
+        larger.iter()
+            .enumerate()
+            .map(|(i, &cents)| {
+                if i + 1 == larger.len()
+                {
+                    return smaller_period
+                }
+                let position = cents/larger_period;
+                smaller[..smaller.len() - 1].iter()
+                    .copied()
+                    .min_by(|x, y| (x/smaller_period - position).abs().total_cmp(&(y/smaller_period - position).abs()))
+                    .unwrap_or(0.0)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, Scale};
+
+    #[test]
+    fn morph()
+    {
+        let ji = scl!{"ji" 9/8 5/4 3/2 2/1};
+        let edo = Scale::edo(12);
+
+        let start = ji.morph(&edo, 0.0);
+        assert_eq!(start.pitches.len(), 12);
+        assert_eq!(edo.morph(&ji, 1.0).pitches[1].to_cents(), ji.pitches[0].to_cents());
+        assert!((start.pitches[4].to_cents() - 386.314).abs() < 1e-3);
+        assert_eq!(start.pitches[11].to_cents(), 1200.0);
+
+        let triad = scl!{"triad" 5/4 3/2 2/1};
+        let stretched = scl!{"stretched" 400.0 700.0 1210.0};
+        let morphed = triad.morph(&stretched, 0.25);
+        assert!((morphed.pitches[1].to_cents() - (701.955 - 0.489)).abs() < 1e-3);
+        assert!((morphed.pitches[2].to_cents() - 1202.5).abs() < 1e-9);
+
+        let spread = Scale::from_cents("spread", [100.0, 200.0, 1200.0]);
+        let clustered = Scale::from_cents("clustered", [900.0, 1000.0, 1200.0]);
+        let target: Vec<f64> = spread.morph(&clustered, 1.0).pitches.iter().map(|pitch| pitch.to_cents()).collect();
+        assert_eq!(target, [900.0, 1000.0, 1200.0]);
+        assert!(Scale::from_cents("empty", []).morph(&triad, 0.5).pitches.is_empty());
+    }
+}
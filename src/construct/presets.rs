@@ -0,0 +1,77 @@
+use crate::{Pitch, Scale};
+
+/// The step fitting the just fifth 3/2, major third 5/4 and minor third 6/5 best in the least-squares sense,
+/// given how many steps each spans.
+fn least_squares_step(steps: [u32; 3]) -> f64
+{
+    let targets = [Pitch::ratio(3, 2), Pitch::ratio(5, 4), Pitch::ratio(6, 5)];
+    let weighted: f64 = steps.iter().zip(targets.iter()).map(|(&n, target)| n as f64*target.to_cents()).sum();
+    weighted/steps.iter().map(|&n| (n*n) as f64).sum::<f64>()
+}
+
+fn carlos(name: &str, steps: [u32; 3]) -> Scale
+{
+    Scale::from_cents(
+        format!("Wendy Carlos' {} scale, the fifth divided in {}", name, steps[0]),
+        [least_squares_step(steps)]
+    )
+}
+
+impl Scale
+{
+    /// Wendy Carlos' alpha scale, with 9 steps to the fifth, 5 to the major and 4 to the minor third.
+    ///
+    /// Like the Carlos scales in the archive, it holds one step, which is its period, so it doesn't repeat at the octave.
+    pub fn carlos_alpha() -> Scale
+    {
+        carlos("alpha", [9, 5, 4])
+    }
+
+    /// Wendy Carlos' beta scale, with 11 steps to the fifth, 6 to the major and 5 to the minor third.
+    pub fn carlos_beta() -> Scale
+    {
+        carlos("beta", [11, 6, 5])
+    }
+
+    /// Wendy Carlos' gamma scale, with 20 steps to the fifth, 11 to the major and 9 to the minor third.
+    pub fn carlos_gamma() -> Scale
+    {
+        carlos("gamma", [20, 11, 9])
+    }
+
+    /// The Lambda mode of the Bohlen-Pierce scale, nine of the thirteen equal divisions of the tritave 3/1,
+    /// which is its period.
+    pub fn bohlen_pierce_lambda() -> Scale
+    {
+        let step = Pitch::ratio(3, 1).to_cents()/13.0;
+        let mut pitches: Vec<Pitch> = [2, 3, 4, 6, 7, 9, 10, 12].iter()
+            .map(|&steps| Pitch::Cents(steps as f64*step))
+            .collect();
+        pitches.push(Pitch::ratio(3, 1));
+        Scale::new("Bohlen-Pierce scale, Lambda mode".to_string(), pitches)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{KeyboardMapping, Pitch, Scale, Tuning};
+
+    #[test]
+    fn presets()
+    {
+        let steps = [Scale::carlos_alpha(), Scale::carlos_beta(), Scale::carlos_gamma()].map(|scale| scale.pitches[0].to_cents());
+        assert!((steps[0] - 77.965).abs() < 1e-3);
+        assert!((steps[1] - 63.833).abs() < 1e-3);
+        assert!((steps[2] - 35.099).abs() < 1e-3);
+        assert!(!Scale::carlos_alpha().is_octave_repeating(1.0));
+        let alpha = Tuning::new(Scale::carlos_alpha(), KeyboardMapping::default());
+        assert!((alpha.frequency(78).unwrap() - 440.0*(9.0*steps[0]/1200.0).exp2()).abs() < 1e-9);
+
+        let lambda = Scale::bohlen_pierce_lambda();
+        assert_eq!(lambda.pitches.len(), 9);
+        assert_eq!(lambda.period(), Some(Pitch::ratio(3, 1)));
+        assert!(!lambda.is_octave_repeating(1.0));
+        assert!((lambda.pitches[0].to_cents() - 292.608).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,60 @@
+use crate::Pitch;
+
+/// The generator stacked once, twice, three times and so on, each reduced into the period with
+/// [`Pitch::reduced_into`]. Made with [`Pitch::stack`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stack
+{
+    generator: Pitch,
+    period: Pitch,
+    pitch: Pitch
+}
+
+impl Iterator for Stack
+{
+    type Item = Pitch;
+
+    fn next(&mut self) -> Option<Pitch>
+    {
+        self.pitch = (self.pitch*self.generator).reduced_into(self.period);
+        Some(self.pitch)
+    }
+}
+
+impl Pitch
+{
+    /// An endless chain of this interval stacked on itself within a period, exact if both are ratios.
+    ///
+    /// ```
+    /// use serde_scala::Pitch;
+    ///
+    /// let fifths: Vec<Pitch> = Pitch::ratio(3, 2).stack(Pitch::ratio(2, 1)).take(3).collect();
+    /// assert_eq!(fifths, [Pitch::ratio(3, 2), Pitch::ratio(9, 8), Pitch::ratio(27, 16)]);
+    /// ```
+    pub fn stack(self, period: Pitch) -> Stack
+    {
+        Stack {
+            generator: self,
+            period,
+            pitch: Pitch::unison()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::Pitch;
+
+    #[test]
+    fn stack()
+    {
+        let edo_fifths: Vec<f64> = Pitch::Cents(700.0).stack(Pitch::Cents(1200.0)).take(12).map(Pitch::to_cents).collect();
+        assert_eq!(edo_fifths[..3], [700.0, 200.0, 900.0]);
+        assert_eq!(edo_fifths[11], 1200.0);
+
+        let mut thirds = Pitch::ratio(5, 4).stack(Pitch::ratio(2, 1));
+        assert_eq!(thirds.nth(2), Some(Pitch::ratio(125, 64)));
+        assert_eq!(Pitch::ratio(3, 1).stack(Pitch::ratio(3, 1)).nth(5), Some(Pitch::ratio(3, 1)));
+    }
+}
@@ -0,0 +1,159 @@
+use std::{fmt::Display, ops::Index};
+
+use crate::{Pitch, Scale};
+
+static UNISON: Pitch = Pitch::unison();
+
+/// A scale degree, counting the implicit unison as degree 0, so degree `n` is `pitches[n - 1]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Degree(pub usize);
+
+impl Degree
+{
+    pub const UNISON: Degree = Degree(0);
+
+    /// The position of this degree in `Scale::pitches`, or `None` for the unison.
+    pub fn pitch_index(self) -> Option<usize>
+    {
+        self.0.checked_sub(1)
+    }
+
+    pub fn from_pitch_index(index: usize) -> Self
+    {
+        Self(index + 1)
+    }
+}
+
+impl From<usize> for Degree
+{
+    fn from(value: usize) -> Self
+    {
+        Self(value)
+    }
+}
+impl From<Degree> for usize
+{
+    fn from(value: Degree) -> Self
+    {
+        value.0
+    }
+}
+
+impl Display for Degree
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Scale
+{
+    /// The pitch of a degree, where degree 0 is the unison, or `None` past the last degree.
+    pub fn degree(&self, degree: impl Into<Degree>) -> Option<Pitch>
+    {
+        match degree.into().pitch_index()
+        {
+            None => Some(Pitch::unison()),
+            Some(i) => self.pitches.get(i).copied()
+        }
+    }
+
+    /// Every degree from the unison up to and including the last pitch, [`Scale::degree_count`] in all.
+    pub fn degrees(&self) -> impl Iterator<Item = Pitch> + '_
+    {
+        [Pitch::unison()].into_iter().chain(self.pitches.iter().copied())
+    }
+
+    /// How many notes the scale has per period, the note count of its .scl file: the listed pitches,
+    /// leaving out the unison, which is the same note as the period.
+    pub fn note_count(&self) -> usize
+    {
+        self.pitches.len()
+    }
+
+    /// How many degrees the scale has from the unison up to and including the period.
+    ///
+    /// This is one more than [`Scale::note_count`], as the unison and the period are both degrees but one note.
+    pub fn degree_count(&self) -> usize
+    {
+        self.pitches.len() + 1
+    }
+
+    /// The pitches [`Scale::degrees`] yields, counting the unison, unlike `pitches.len()`.
+    /// Another name for [`Scale::degree_count`].
+    pub fn pitch_count(&self) -> usize
+    {
+        self.degree_count()
+    }
+
+    /// The degree closest to `pitch`, if it lies within `tolerance` cents.
+    pub fn find(&self, pitch: Pitch, tolerance: f64) -> Option<Degree>
+    {
+        let cents = pitch.to_cents();
+        self.degrees()
+            .map(|degree| (degree.to_cents() - cents).abs())
+            .enumerate()
+            .filter(|&(_, deviation)| deviation <= tolerance)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| Degree(i))
+    }
+
+    /// The interval from degree `from` up to degree `to`, exact if both are ratios,
+    /// or `None` if either lies past the last degree.
+    pub fn interval(&self, from: impl Into<Degree>, to: impl Into<Degree>) -> Option<Pitch>
+    {
+        Some(self.degree(to)?/self.degree(from)?)
+    }
+}
+
+/// Panics past the last degree.
+impl Index<Degree> for Scale
+{
+    type Output = Pitch;
+
+    fn index(&self, degree: Degree) -> &Pitch
+    {
+        match degree.pitch_index()
+        {
+            None => &UNISON,
+            Some(i) => &self.pitches[i]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, Degree, Pitch};
+
+    #[test]
+    fn degrees_include_unison()
+    {
+        let scale = scl!{"ji" 5/4 3/2 2/1};
+
+        assert_eq!(scale.degree(0), Some(Pitch::unison()));
+        assert_eq!(scale.degree(2), Some(Pitch::ratio(3, 2)));
+        assert_eq!(scale.degree(4), None);
+        assert_eq!(scale[Degree(0)], Pitch::unison());
+        assert_eq!(scale[Degree(3)], Pitch::ratio(2, 1));
+        assert_eq!(scale.degrees().collect::<Vec<_>>(), vec![Pitch::unison(), Pitch::ratio(5, 4), Pitch::ratio(3, 2), Pitch::ratio(2, 1)]);
+        assert_eq!((scale.note_count(), scale.degree_count()), (3, 4));
+        assert_eq!(scale.degrees().count(), scale.degree_count());
+        assert_eq!(scale.pitch_count(), scale.degree_count());
+    }
+
+    #[test]
+    fn interval_queries()
+    {
+        let scale = scl!{"mixed" 5/4 3/2 1000.0 2/1};
+
+        assert_eq!(scale.find(Pitch::cents(386.0), 1.0), Some(Degree(1)));
+        assert_eq!(scale.find(Pitch::cents(3.0), 5.0), Some(Degree(0)));
+        assert_eq!(scale.find(Pitch::ratio(7, 4), 10.0), None);
+        assert_eq!(scale.interval(1, 2), Some(Pitch::ratio(6, 5)));
+        assert_eq!(scale.interval(2, 0), Some(Pitch::ratio(2, 3)));
+        assert!((scale.interval(2, 3).unwrap().to_cents() - 298.045).abs() < 1e-3);
+        assert_eq!(scale.interval(0, 5), None);
+    }
+}
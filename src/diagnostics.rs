@@ -0,0 +1,152 @@
+use crate::{parser::lines, ParseScaleError, Scale, ScaleParser};
+
+/// How bad a problem found while parsing is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity
+{
+    /// An oddity that the Scala program loads the file despite, like a note count off by one or unsorted pitches.
+    Warning,
+    /// A violation of the file format, like a malformed number.
+    Error
+}
+
+/// A problem found while parsing, and the line it was found on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic
+{
+    /// One-based. Problems with the file as a whole point past its last line.
+    pub line: usize,
+    pub error: ParseScaleError,
+    pub severity: Severity
+}
+
+impl Diagnostic
+{
+    pub fn error(line: usize, error: ParseScaleError) -> Self
+    {
+        Self {line, error, severity: Severity::Error}
+    }
+
+    pub fn warning(line: usize, error: ParseScaleError) -> Self
+    {
+        Self {line, error, severity: Severity::Warning}
+    }
+
+    pub fn is_error(&self) -> bool
+    {
+        self.severity == Severity::Error
+    }
+}
+
+impl Scale
+{
+    /// Parses as much of a scale as possible, collecting every problem instead of stopping at the first.
+    ///
+    /// Invalid pitch lines are skipped, a missing description leaves the name empty,
+    /// and the pitches found are kept even if their number doesn't match the note count, which skipped lines still count towards.
+    /// A note count off by one and pitches lower than the one before are only warnings.
+    ///
+    /// ```
+    /// use serde_scala::{Diagnostic, ParseScaleError, Scale};
+    ///
+    /// let (scale, diagnostics) = Scale::parse_with_diagnostics("broken\n3\n9/8\n5/4x\n3/2\n");
+    ///
+    /// assert_eq!(scale.pitches.len(), 2);
+    /// assert_eq!(diagnostics, vec![
+    ///     Diagnostic::error(4, ParseScaleError::TrailingCharacters("x".to_string()))
+    /// ]);
+    /// ```
+    pub fn parse_with_diagnostics(s: &str) -> (Scale, Vec<Diagnostic>)
+    {
+        let mut parser = ScaleParser::new();
+        let mut pitch_count_line = 0;
+        let mut diagnostics = vec![];
+        let mut line_count = 0;
+        // Pitch lines that couldn't be read still count towards the note count, as they're already reported.
+        let mut rejected_pitches = 0;
+
+        for (i, s) in lines(s).enumerate()
+        {
+            let line = i + 1;
+            line_count = line;
+
+            let counting = parser.name.is_some() && parser.pitch_count.is_none();
+            let listing = parser.pitch_count.is_some();
+            let pitch_count = parser.pitches.len();
+            if let Err(error) = parser.feed_line(s)
+            {
+                rejected_pitches += usize::from(listing);
+                diagnostics.push(Diagnostic::error(line, error))
+            }
+            if let [.., previous, pitch] = parser.pitches[..]
+            {
+                if parser.pitches.len() > pitch_count && pitch.to_cents() < previous.to_cents()
+                {
+                    diagnostics.push(Diagnostic::warning(line, ParseScaleError::DescendingPitch))
+                }
+            }
+            if counting && parser.pitch_count.is_some()
+            {
+                pitch_count_line = line;
+            }
+        }
+
+        let end = line_count + 1;
+        if parser.name.is_none()
+        {
+            diagnostics.push(Diagnostic::error(end, ParseScaleError::MissingDescription))
+        }
+        match parser.pitch_count
+        {
+            None => diagnostics.push(Diagnostic::error(end, ParseScaleError::MissingNoteCount)),
+            Some(Some(count)) if count != parser.pitches.len() + rejected_pitches =>
+            {
+                let found = parser.pitches.len() + rejected_pitches;
+                let error = ParseScaleError::WrongPitchCount(found);
+                diagnostics.push(match count.abs_diff(found)
+                {
+                    1 => Diagnostic::warning(pitch_count_line, error),
+                    _ => Diagnostic::error(pitch_count_line, error)
+                })
+            },
+            Some(_) => ()
+        }
+
+        (parser.into_scale(), diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{Diagnostic, ParseScaleError, Pitch, Scale, Severity};
+
+    #[test]
+    fn collects_every_problem()
+    {
+        let (scale, diagnostics) = Scale::parse_with_diagnostics("! empty.scl\n!\n");
+
+        assert_eq!(scale, Scale::new(String::new(), vec![]));
+        assert_eq!(diagnostics, vec![
+            Diagnostic::error(3, ParseScaleError::MissingDescription),
+            Diagnostic::error(3, ParseScaleError::MissingNoteCount)
+        ]);
+
+        let (scale, diagnostics) = Scale::parse_with_diagnostics("bad\nthree\n\n2/1/1\n1200.0\n");
+
+        assert_eq!(scale.pitches, vec![Pitch::Cents(1200.0)]);
+        assert_eq!(diagnostics.iter().map(|d| d.line).collect::<Vec<_>>(), vec![2, 4]);
+        assert_eq!(diagnostics[1].error, ParseScaleError::MultipleSlashes);
+
+        let (_, diagnostics) = Scale::parse_with_diagnostics("odd\n4\n3/2\n9/8\n2/1\n");
+        assert_eq!(diagnostics, vec![
+            Diagnostic::warning(4, ParseScaleError::DescendingPitch),
+            Diagnostic::warning(2, ParseScaleError::WrongPitchCount(3))
+        ]);
+        let (_, diagnostics) = Scale::parse_with_diagnostics("short\n5\n9/8\n2/1\n");
+        assert_eq!(diagnostics.iter().map(|d| d.severity).collect::<Vec<_>>(), vec![Severity::Error]);
+
+        let (_, diagnostics) = Scale::parse_with_diagnostics("typo\n2\n3/\n2/1\n");
+        assert_eq!(diagnostics.iter().map(|d| (d.line, d.severity)).collect::<Vec<_>>(), vec![(3, Severity::Error)]);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{KeyboardMapping, Scale, SerdeScalaError, Tun};
+
+/// Any of the tuning file formats this crate reads.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Document
+{
+    Scale(Scale),
+    KeyboardMapping(KeyboardMapping),
+    Tun(Box<Tun>),
+    /// An Ableton .ascl file, a .scl file with extra `! @ABL` directive comments.
+    Ascl
+    {
+        scale: Scale,
+        /// Each directive, without the `@ABL` prefix.
+        directives: Vec<String>
+    }
+}
+
+/// Works out which format a file is in and parses it. Invalid UTF-8 and a leading byte order mark are tolerated.
+///
+/// Files with `[Tuning]`-style sections are .tun files, .scl files with `@ABL` directives are .ascl files,
+/// and files whose first line is a whole number followed by a valid keyboard mapping header are .kbm files.
+/// Anything else is read as a .scl file.
+///
+/// ```
+/// use serde_scala::{parse_any, Document};
+///
+/// let document = parse_any(b"! fifth.scl\nJust fifth\n2\n3/2\n2/1\n").unwrap();
+///
+/// assert!(matches!(document, Document::Scale(scale) if scale.name == "Just fifth"));
+/// ```
+pub fn parse_any(bytes: &[u8]) -> Result<Document, SerdeScalaError>
+{
+    let bytes = bytes.strip_prefix("\u{feff}".as_bytes()).unwrap_or(bytes);
+    let text = String::from_utf8_lossy(bytes);
+
+    let is_tun = text.lines()
+        .map(|line| line.trim().to_lowercase())
+        .any(|line| matches!(line.as_str(), "[tuning]" | "[exact tuning]" | "[scale begin]"));
+    if is_tun
+    {
+        return Ok(Document::Tun(Box::new(text.parse()?)))
+    }
+
+    let directives: Vec<String> = text.lines()
+        .filter_map(|line| line.trim_start().strip_prefix("!")?.trim_start().strip_prefix("@ABL"))
+        .map(|directive| directive.trim().to_string())
+        .collect();
+    if !directives.is_empty()
+    {
+        return Ok(Document::Ascl {
+            scale: text.parse()?,
+            directives
+        })
+    }
+
+    let first_line = text.lines()
+        .map(|line| line.split_once("!").map(|(line, _)| line).unwrap_or(line))
+        .find(|line| !line.is_empty());
+    if first_line.is_some_and(|line| line.trim().parse::<usize>().is_ok())
+    {
+        if let Ok(mapping) = text.parse()
+        {
+            return Ok(Document::KeyboardMapping(mapping))
+        }
+    }
+
+    Ok(Document::Scale(text.parse()?))
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{parse_any, Document, KeyboardMapping, Pitch, Scale, Tun, Tuning};
+
+    #[test]
+    fn detect_formats()
+    {
+        let scale = Scale::edo(12);
+        let mapping = KeyboardMapping::default();
+
+        assert_eq!(parse_any(scale.to_string().as_bytes()).unwrap(), Document::Scale(scale.clone()));
+        assert_eq!(parse_any(mapping.to_string().as_bytes()).unwrap(), Document::KeyboardMapping(mapping.clone()));
+        assert!(matches!(
+            parse_any(Tun::from(Tuning::new(scale, mapping)).to_string().as_bytes()).unwrap(),
+            Document::Tun(_)
+        ));
+
+        let ascl = "\u{feff}! pelog.ascl\n12\n1\n2/1\n! @ABL NOTE_NAMES A\n";
+        assert_eq!(parse_any(ascl.as_bytes()).unwrap(), Document::Ascl {
+            scale: Scale::new("12".to_string(), vec![Pitch::ratio(2, 1)]),
+            directives: vec!["NOTE_NAMES A".to_string()]
+        });
+    }
+}
@@ -0,0 +1,167 @@
+use std::{fmt::Display, str::FromStr};
+
+use num_rational::Ratio;
+
+use crate::{ParsePitchError, Pitch};
+
+/// A pitch with cents stored as an exact decimal, so that it can be hashed and compared exactly.
+///
+/// ```
+/// use std::collections::HashSet;
+///
+/// use serde_scala::ExactPitch;
+///
+/// let pitches: HashSet<ExactPitch> = ["701.955", "701.9550", "3/2"].iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+/// assert_eq!(pitches.len(), 2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExactPitch
+{
+    Cents(Ratio<i64>),
+    Ratio(Ratio<u128>)
+}
+
+impl ExactPitch
+{
+    /// `None` for cents that are not finite or too large to store exactly.
+    pub fn from_pitch(pitch: Pitch) -> Option<Self>
+    {
+        match pitch
+        {
+            Pitch::Cents(cents) if cents.is_finite() => parse_decimal(&cents.to_string()).ok().map(Self::Cents),
+            Pitch::Cents(_) => None,
+            Pitch::Ratio(ratio) => Some(Self::Ratio(ratio.reduced()))
+        }
+    }
+
+    pub fn to_pitch(self) -> Pitch
+    {
+        match self
+        {
+            Self::Cents(cents) => Pitch::Cents(*cents.numer() as f64/ *cents.denom() as f64),
+            Self::Ratio(ratio) => Pitch::Ratio(ratio)
+        }
+    }
+}
+
+/// Reads a decimal like `-701.955` or `7.01955e2` as an exact ratio.
+fn parse_decimal(s: &str) -> Result<Ratio<i64>, ParsePitchError>
+{
+    let (mantissa, exponent) = s.split_once(['e', 'E']).unwrap_or((s, "0"));
+    let exponent: i32 = exponent.parse()?;
+    let (integer, fraction) = mantissa.split_once(".").unwrap_or((mantissa, ""));
+    let numer: i64 = format!("{}{}", integer, fraction).parse()?;
+
+    let too_precise = || ParsePitchError::TrailingCharacters(fraction.to_string());
+    let decimals = fraction.len() as i32 - exponent;
+    let scale = 10i64.checked_pow(decimals.unsigned_abs()).ok_or_else(too_precise)?;
+    if decimals >= 0
+    {
+        Ok(Ratio::new(numer, scale))
+    }
+    else
+    {
+        Ok(Ratio::from_integer(numer.checked_mul(scale).ok_or_else(too_precise)?))
+    }
+}
+
+impl From<ExactPitch> for Pitch
+{
+    fn from(value: ExactPitch) -> Self
+    {
+        value.to_pitch()
+    }
+}
+
+/// Parses like [`Pitch`], reading cents digit for digit.
+impl FromStr for ExactPitch
+{
+    type Err = ParsePitchError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        match s.parse()?
+        {
+            Pitch::Cents(_) =>
+            {
+                let s = s.replace(" ", "");
+                let s = s.strip_suffix("cents").unwrap_or(&s);
+                Ok(Self::Cents(parse_decimal(s)?))
+            },
+            Pitch::Ratio(ratio) => Ok(Self::Ratio(ratio))
+        }
+    }
+}
+
+/// Cents are written out in full if they are a decimal fraction, and to five decimals otherwise.
+impl Display for ExactPitch
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Self::Cents(cents) =>
+            {
+                let mut denom = *cents.denom();
+                let mut twos = 0;
+                let mut fives = 0;
+                while denom % 2 == 0
+                {
+                    denom /= 2;
+                    twos += 1;
+                }
+                while denom % 5 == 0
+                {
+                    denom /= 5;
+                    fives += 1;
+                }
+                let decimals = u32::max(1, u32::max(twos, fives));
+                let unit = 10i128.pow(decimals.min(38));
+                let value = if denom == 1 && decimals < 38
+                {
+                    (*cents.numer() as i128).checked_mul(unit/ *cents.denom() as i128)
+                }
+                else
+                {
+                    None
+                };
+                let Some(value) = value
+                else
+                {
+                    return write!(f, "{}", self.to_pitch())
+                };
+                let sign = if value < 0 {"-"} else {""};
+                let value = value.unsigned_abs();
+                write!(f, "{}{}.{:0width$}", sign, value/unit as u128, value % unit as u128, width = decimals as usize)
+            },
+            Self::Ratio(ratio) => write!(f, "{}/{}", ratio.numer(), ratio.denom())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use num_rational::Ratio;
+
+    use crate::{ExactPitch, Pitch};
+
+    #[test]
+    fn exact_cents()
+    {
+        let fifth: ExactPitch = "701.955".parse().unwrap();
+
+        assert_eq!(fifth, ExactPitch::Cents(Ratio::new(701955, 1000)));
+        assert_eq!(ExactPitch::from_pitch(Pitch::Cents(701.955)), Some(fifth));
+        assert_eq!(ExactPitch::from_pitch(Pitch::Cents(f64::NAN)), None);
+        assert_eq!(fifth.to_string(), "701.955");
+        assert_eq!("-100.50 cents".parse::<ExactPitch>().unwrap().to_string(), "-100.5");
+        assert_eq!("7.01955e2".parse::<ExactPitch>(), Ok(fifth));
+        assert_eq!("3/2".parse::<ExactPitch>().unwrap(), ExactPitch::Ratio(Ratio::new(3, 2)));
+
+        let huge = ExactPitch::Cents(Ratio::new(i64::MAX, 1 << 37));
+        assert_eq!(huge.to_string(), huge.to_pitch().to_string());
+    }
+}
@@ -0,0 +1,270 @@
+use std::{error::Error, fmt::Display, num::ParseIntError};
+
+use num_rational::Ratio;
+
+use crate::Pitch;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseExprError
+{
+    ParseInt(ParseIntError),
+    /// A character that doesn't belong at this byte offset.
+    Unexpected(char, usize),
+    UnexpectedEnd,
+    DivisionByZero,
+    /// The expression doesn't evaluate to a positive, finite frequency ratio.
+    NotPositive
+}
+impl From<ParseIntError> for ParseExprError
+{
+    fn from(value: ParseIntError) -> Self
+    {
+        Self::ParseInt(value)
+    }
+}
+impl Display for ParseExprError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Self::ParseInt(err) => write!(f, "invalid number: {}", err),
+            Self::Unexpected(c, offset) => write!(f, "unexpected {:?} at {}", c, offset),
+            Self::UnexpectedEnd => write!(f, "expression ends unexpectedly"),
+            Self::DivisionByZero => write!(f, "division by zero"),
+            Self::NotPositive => write!(f, "expression is not a positive ratio")
+        }
+    }
+}
+impl Error for ParseExprError
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)>
+    {
+        match self
+        {
+            Self::ParseInt(err) => Some(err),
+            _ => None
+        }
+    }
+}
+
+/// A frequency ratio, kept exact for as long as possible.
+#[derive(Debug, Clone, Copy)]
+enum Value
+{
+    Exact(Ratio<u128>),
+    Real(f64)
+}
+
+impl Value
+{
+    fn to_f64(self) -> f64
+    {
+        match self
+        {
+            Self::Exact(ratio) => *ratio.numer() as f64/ *ratio.denom() as f64,
+            Self::Real(real) => real
+        }
+    }
+
+    fn mul(self, rhs: Self) -> Self
+    {
+        match (self, rhs)
+        {
+            (Self::Exact(a), Self::Exact(b)) => match Pitch::Ratio(a)*Pitch::Ratio(b)
+            {
+                Pitch::Ratio(product) => Self::Exact(product),
+                Pitch::Cents(_) => Self::Real(self.to_f64()*rhs.to_f64())
+            },
+            _ => Self::Real(self.to_f64()*rhs.to_f64())
+        }
+    }
+
+    fn div(self, rhs: Self) -> Result<Self, ParseExprError>
+    {
+        match rhs
+        {
+            Self::Exact(ratio) if *ratio.numer() == 0 => Err(ParseExprError::DivisionByZero),
+            Self::Exact(ratio) => Ok(self.mul(Self::Exact(ratio.recip()))),
+            Self::Real(real) => match self.to_f64()/real
+            {
+                quotient if quotient.is_finite() => Ok(Self::Real(quotient)),
+                _ => Err(ParseExprError::DivisionByZero)
+            }
+        }
+    }
+
+    fn pow(self, exponent: Self) -> Self
+    {
+        if let (Self::Exact(base), Self::Exact(exponent)) = (self, exponent)
+        {
+            if exponent.is_integer()
+            {
+                let exact = u32::try_from(*exponent.numer())
+                    .ok()
+                    .and_then(|n| Some(Ratio::new_raw(base.numer().checked_pow(n)?, base.denom().checked_pow(n)?)));
+                if let Some(power) = exact
+                {
+                    return Self::Exact(power)
+                }
+            }
+        }
+        Self::Real(self.to_f64().powf(exponent.to_f64()))
+    }
+}
+
+struct ExprParser<'a>
+{
+    s: &'a str,
+    offset: usize
+}
+
+impl ExprParser<'_>
+{
+    fn peek(&mut self) -> Option<char>
+    {
+        let rest = &self.s[self.offset..];
+        let trimmed = rest.trim_start();
+        self.offset += rest.len() - trimmed.len();
+        trimmed.chars().next()
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ParseExprError>
+    {
+        match self.peek()
+        {
+            Some(found) if found == c =>
+            {
+                self.offset += c.len_utf8();
+                Ok(())
+            },
+            Some(found) => Err(ParseExprError::Unexpected(found, self.offset)),
+            None => Err(ParseExprError::UnexpectedEnd)
+        }
+    }
+
+    /// `factor (('*' | '/') factor)*`
+    fn product(&mut self) -> Result<Value, ParseExprError>
+    {
+        let mut value = self.power()?;
+        loop
+        {
+            match self.peek()
+            {
+                Some('*') =>
+                {
+                    self.offset += 1;
+                    value = value.mul(self.power()?);
+                },
+                Some('/') =>
+                {
+                    self.offset += 1;
+                    value = value.div(self.power()?)?;
+                },
+                _ => return Ok(value)
+            }
+        }
+    }
+
+    /// `atom ('^' power)?`, binding to the right.
+    fn power(&mut self) -> Result<Value, ParseExprError>
+    {
+        let base = self.atom()?;
+        if self.peek() == Some('^')
+        {
+            self.offset += 1;
+            return Ok(base.pow(self.power()?))
+        }
+        Ok(base)
+    }
+
+    /// A number like `3` or `1.5`, or a parenthesized expression.
+    fn atom(&mut self) -> Result<Value, ParseExprError>
+    {
+        match self.peek()
+        {
+            Some('(') =>
+            {
+                self.offset += 1;
+                let value = self.product()?;
+                self.expect(')')?;
+                Ok(value)
+            },
+            Some(c) if c.is_ascii_digit() =>
+            {
+                let rest = &self.s[self.offset..];
+                let len = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len());
+                self.offset += len;
+
+                let number = &rest[..len];
+                let (integer, fraction) = number.split_once(".").unwrap_or((number, ""));
+                let numer: u128 = format!("{}{}", integer, fraction).parse()?;
+                match 10u128.checked_pow(fraction.len() as u32)
+                {
+                    Some(denom) => Ok(Value::Exact(Ratio::new(numer, denom))),
+                    None => Ok(Value::Real(number.parse().map_err(|_| ParseExprError::NotPositive)?))
+                }
+            },
+            Some(c) => Err(ParseExprError::Unexpected(c, self.offset)),
+            None => Err(ParseExprError::UnexpectedEnd)
+        }
+    }
+}
+
+impl Pitch
+{
+    /// Evaluates an expression of frequency ratios, like `2^(7/12)`, `3^4/2^6` or `5/4 * 81/80`.
+    ///
+    /// Numbers are multiplied, divided and raised to powers with `*`, `/` and `^`, grouped with parentheses.
+    /// The result is an exact ratio if every step was, and cents otherwise.
+    ///
+    /// ```
+    /// use serde_scala::Pitch;
+    ///
+    /// assert_eq!(Pitch::from_expr("3^4/2^6"), Ok(Pitch::ratio(81, 64)));
+    /// assert_eq!(Pitch::from_expr("5/4 * 81/80"), Ok(Pitch::ratio(81, 64)));
+    /// assert!((Pitch::from_expr("2^(7/12)").unwrap().to_cents() - 700.0).abs() < 1e-9);
+    /// ```
+    pub fn from_expr(s: &str) -> Result<Pitch, ParseExprError>
+    {
+        let mut parser = ExprParser {
+            s,
+            offset: 0
+        };
+        let value = parser.product()?;
+        if let Some(c) = parser.peek()
+        {
+            return Err(ParseExprError::Unexpected(c, parser.offset))
+        }
+
+        match value
+        {
+            Value::Exact(ratio) if *ratio.numer() == 0 => Err(ParseExprError::NotPositive),
+            Value::Exact(ratio) => Ok(Pitch::Ratio(ratio)),
+            Value::Real(real) if real > 0.0 && real.is_finite() => Ok(Pitch::Cents(1200.0*real.log2())),
+            Value::Real(_) => Err(ParseExprError::NotPositive)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, ParseExprError, Pitch};
+
+    #[test]
+    fn expressions()
+    {
+        assert_eq!(Pitch::from_expr("(3/2)^2 / 2"), Ok(Pitch::ratio(9, 8)));
+        assert_eq!(Pitch::from_expr("2^2^3"), Ok(Pitch::ratio(256, 1)));
+        assert_eq!(Pitch::from_expr("1.5"), Ok(Pitch::ratio(3, 2)));
+        assert_eq!(Pitch::from_expr("3/0"), Err(ParseExprError::DivisionByZero));
+        assert_eq!(Pitch::from_expr("3/2)"), Err(ParseExprError::Unexpected(')', 3)));
+        assert_eq!(Pitch::from_expr("(3/2"), Err(ParseExprError::UnexpectedEnd));
+        assert_eq!(Pitch::from_expr("0"), Err(ParseExprError::NotPositive));
+
+        let scale = scl!{"expressions" [2^(1/2)] [3^4/2^6] [5/4 * 81/80] 2/1};
+        assert!((scale.pitches[0].to_cents() - 600.0).abs() < 1e-9);
+        assert_eq!(scale.pitches[1], scale.pitches[2]);
+    }
+}
@@ -0,0 +1,85 @@
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{KeyboardMapping, Pitch, Scale};
+
+/// Generated pitches survive being written out and parsed back:
+/// cents have at most five decimals and ratios are reduced and within `u32` range.
+impl<'a> Arbitrary<'a> for Pitch
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self>
+    {
+        if u.arbitrary()?
+        {
+            Ok(Pitch::cents(u.arbitrary::<i32>()? as f64/1e5))
+        }
+        else
+        {
+            let numer: u32 = u.int_in_range(1..=u32::MAX)?;
+            let denom: u32 = u.int_in_range(1..=u32::MAX)?;
+            Ok(Pitch::ratio(numer as u128, denom as u128))
+        }
+    }
+}
+
+/// Generated names are a single line without comments, so they survive being written out and parsed back.
+impl<'a> Arbitrary<'a> for Scale
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self>
+    {
+        let mut name: String = u.arbitrary::<String>()?
+            .chars()
+            .filter(|&c| c != '!' && c != '\n' && c != '\r')
+            .collect();
+        if name.is_empty()
+        {
+            name = "scale".to_string()
+        }
+        Ok(Scale::new(name, u.arbitrary()?))
+    }
+}
+
+/// Generated mappings survive being written out and parsed back.
+impl<'a> Arbitrary<'a> for KeyboardMapping
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self>
+    {
+        let size = u.int_in_range(0..=128)?;
+        let mapping_len = u.int_in_range(0..=size)?;
+        let mapping = (0..mapping_len)
+            .map(|_| u.arbitrary::<Option<u8>>().map(|degree| degree.map(usize::from)))
+            .collect::<Result<_>>()?;
+        Ok(KeyboardMapping::new(
+            size,
+            u.int_in_range(0..=127)?,
+            u.int_in_range(0..=127)?,
+            u.int_in_range(0..=127)?,
+            u.int_in_range(0..=127)?,
+            u.int_in_range(1..=20_000_000u32)? as f64/1e3,
+            u.int_in_range(0..=size)?,
+            mapping
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use arbitrary::{Arbitrary, Unstructured};
+
+    use crate::{KeyboardMapping, Scale};
+
+    #[test]
+    fn round_trips()
+    {
+        let bytes: Vec<u8> = (0..4096u32).map(|i| (i.wrapping_mul(2654435761) >> 13) as u8).collect();
+        let mut u = Unstructured::new(&bytes);
+        while !u.is_empty()
+        {
+            let scale = Scale::arbitrary(&mut u).unwrap();
+            assert_eq!(scale.to_string().parse::<Scale>().unwrap(), scale);
+
+            let mapping = KeyboardMapping::arbitrary(&mut u).unwrap();
+            assert_eq!(mapping.to_string().parse::<KeyboardMapping>().unwrap(), mapping);
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use crate::{parser::{lines, LineReader, ScaleLine}, ParseScaleError, Pitch, Scale};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseHeaplessScaleError
+{
+    ParseScale(ParseScaleError),
+    /// The description is longer than the name buffer.
+    NameTooLong,
+    /// There are more pitches than fit in the pitch buffer.
+    TooManyPitches
+}
+impl From<ParseScaleError> for ParseHeaplessScaleError
+{
+    fn from(value: ParseScaleError) -> Self
+    {
+        Self::ParseScale(value)
+    }
+}
+impl Display for ParseHeaplessScaleError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Self::ParseScale(err) => write!(f, "{}", err),
+            Self::NameTooLong => write!(f, "description does not fit the name buffer"),
+            Self::TooManyPitches => write!(f, "pitches do not fit the pitch buffer")
+        }
+    }
+}
+impl Error for ParseHeaplessScaleError
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)>
+    {
+        match self
+        {
+            Self::ParseScale(err) => Some(err),
+            Self::NameTooLong | Self::TooManyPitches => None
+        }
+    }
+}
+
+/// A scale stored in fixed-capacity buffers, holding at most `N` pitches and a name of at most `NAME` bytes.
+/// Labels are skipped.
+///
+/// Parsing a valid file never touches the heap, though errors may hold the offending text.
+/// The crate itself still links `std`.
+///
+/// ```
+/// use serde_scala::{HeaplessScale, Pitch};
+///
+/// let scale: HeaplessScale<12> = "Just fifth\n2\n3/2\n2/1\n".parse().unwrap();
+///
+/// assert_eq!(scale.name.as_str(), "Just fifth");
+/// assert_eq!(scale.pitches, [Pitch::ratio(3, 2), Pitch::ratio(2, 1)]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeaplessScale<const N: usize, const NAME: usize = 64>
+{
+    pub name: heapless::String<NAME>,
+    pub pitches: heapless::Vec<Pitch, N>
+}
+
+impl<const N: usize, const NAME: usize> HeaplessScale<N, NAME>
+{
+    pub fn to_scale(&self) -> Scale
+    {
+        Scale::new(self.name.to_string(), self.pitches.to_vec())
+    }
+}
+
+impl<const N: usize, const NAME: usize> FromStr for HeaplessScale<N, NAME>
+{
+    type Err = ParseHeaplessScaleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let mut reader = LineReader::default();
+        let mut name = None;
+        let mut pitch_count = None;
+        let mut pitches = heapless::Vec::new();
+
+        for line in lines(s)
+        {
+            match reader.read(line).map_err(ParseScaleError::from)?
+            {
+                ScaleLine::Blank | ScaleLine::Comment(..) => (),
+                ScaleLine::Description(description) =>
+                {
+                    name = Some(heapless::String::try_from(description).map_err(|_| ParseHeaplessScaleError::NameTooLong)?)
+                },
+                ScaleLine::NoteCount(count) =>
+                {
+                    pitch_count = Some(count.map_err(ParseScaleError::from)?);
+                    if pitch_count > Some(N)
+                    {
+                        return Err(ParseHeaplessScaleError::TooManyPitches)
+                    }
+                },
+                ScaleLine::Pitch(pitch, _, _) => pitches.push(pitch).map_err(|_| ParseHeaplessScaleError::TooManyPitches)?
+            }
+        }
+
+        let name = name.ok_or(ParseScaleError::MissingDescription)?;
+        let pitch_count = pitch_count.ok_or(ParseScaleError::MissingNoteCount)?;
+
+        if pitches.len() != pitch_count
+        {
+            return Err(ParseScaleError::WrongPitchCount(pitches.len()).into())
+        }
+
+        Ok(Self {
+            name,
+            pitches
+        })
+    }
+}
+
+impl<const N: usize, const NAME: usize> From<HeaplessScale<N, NAME>> for Scale
+{
+    fn from(value: HeaplessScale<N, NAME>) -> Self
+    {
+        value.to_scale()
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::fs;
+
+    use crate::{HeaplessScale, ParseHeaplessScaleError, Scale};
+
+    #[test]
+    fn fixed_capacity()
+    {
+        let text = fs::read_to_string("scl/ptolemy.scl").unwrap();
+        let scale: HeaplessScale<12> = text.parse().unwrap();
+
+        assert_eq!(Scale::from(scale), text.parse::<Scale>().unwrap());
+        assert_eq!(text.parse::<HeaplessScale<4>>(), Err(ParseHeaplessScaleError::TooManyPitches));
+        assert_eq!(text.parse::<HeaplessScale<12, 4>>(), Err(ParseHeaplessScaleError::NameTooLong));
+    }
+}
@@ -0,0 +1,273 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{KeyboardMapping, Pitch, Scale, Tuning};
+
+/// A pitch as `{"kind": "ratio", "value": "3/2"}` or `{"kind": "cents", "value": 701.955}`.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "lowercase")]
+enum JsonPitch
+{
+    Cents(f64),
+    Ratio(String)
+}
+
+impl From<Pitch> for JsonPitch
+{
+    fn from(value: Pitch) -> Self
+    {
+        match value
+        {
+            Pitch::Cents(cents) => Self::Cents(cents),
+            Pitch::Ratio(_) => Self::Ratio(value.to_string())
+        }
+    }
+}
+
+impl JsonPitch
+{
+    fn to_pitch<E: serde::de::Error>(&self) -> Result<Pitch, E>
+    {
+        match self
+        {
+            Self::Cents(cents) => Ok(Pitch::Cents(*cents)),
+            Self::Ratio(ratio) => match ratio.parse().map_err(E::custom)?
+            {
+                pitch @ Pitch::Ratio(_) => Ok(pitch),
+                Pitch::Cents(_) => Err(E::custom(format!("`{}` is not a ratio", ratio)))
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonScalePitch
+{
+    #[serde(flatten)]
+    pitch: JsonPitch,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonScale
+{
+    name: String,
+    pitches: Vec<JsonScalePitch>,
+    /// Written for convenience as the last pitch, and ignored when read.
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    period: Option<JsonPitch>
+}
+
+impl From<&Scale> for JsonScale
+{
+    fn from(value: &Scale) -> Self
+    {
+        Self {
+            name: value.name.clone(),
+            pitches: value.pitches.iter()
+                .enumerate()
+                .map(|(i, &pitch)| JsonScalePitch {
+                    pitch: pitch.into(),
+                    label: value.label(i).map(str::to_string)
+                })
+                .collect(),
+            period: value.period().map(JsonPitch::from)
+        }
+    }
+}
+
+impl JsonScale
+{
+    fn to_scale<E: serde::de::Error>(&self) -> Result<Scale, E>
+    {
+        let pitches = self.pitches.iter()
+            .map(|pitch| pitch.pitch.to_pitch())
+            .collect::<Result<_, E>>()?;
+        let mut scale = Scale::new(self.name.clone(), pitches);
+        for (i, pitch) in self.pitches.iter().enumerate()
+        {
+            if let Some(label) = &pitch.label
+            {
+                scale.set_label(i, label.clone());
+            }
+        }
+        Ok(scale)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonMapping
+{
+    size: usize,
+    first_note: u8,
+    last_note: u8,
+    middle_note: u8,
+    reference_note: u8,
+    reference_frequency: f64,
+    formal_octave: usize,
+    /// `null` for unmapped keys.
+    mapping: Vec<Option<usize>>
+}
+
+impl From<&KeyboardMapping> for JsonMapping
+{
+    fn from(value: &KeyboardMapping) -> Self
+    {
+        Self {
+            size: value.size,
+            first_note: value.first_note,
+            last_note: value.last_note,
+            middle_note: value.middle_note,
+            reference_note: value.reference_note,
+            reference_frequency: value.reference_frequency,
+            formal_octave: value.formal_octave,
+            mapping: value.mapping.clone()
+        }
+    }
+}
+
+impl From<JsonMapping> for KeyboardMapping
+{
+    fn from(value: JsonMapping) -> Self
+    {
+        KeyboardMapping::new(
+            value.size,
+            value.first_note,
+            value.last_note,
+            value.middle_note,
+            value.reference_note,
+            value.reference_frequency,
+            value.formal_octave,
+            value.mapping
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonTuning
+{
+    scale: JsonScale,
+    mapping: JsonMapping
+}
+
+impl Serialize for Pitch
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        JsonPitch::from(*self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Pitch
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        JsonPitch::deserialize(deserializer)?.to_pitch()
+    }
+}
+
+/// As `{"name": ..., "pitches": [{"kind": "ratio", "value": "9/8", "label": ...}, ...], "period": ...}`,
+/// each pitch written like a [`Pitch`] and labels left out where there are none.
+impl Serialize for Scale
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        JsonScale::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Scale
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        JsonScale::deserialize(deserializer)?.to_scale()
+    }
+}
+
+impl Serialize for KeyboardMapping
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        JsonMapping::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyboardMapping
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        Ok(JsonMapping::deserialize(deserializer)?.into())
+    }
+}
+
+/// As `{"scale": ..., "mapping": ...}`, the mapping's fields named like those of [`KeyboardMapping`].
+impl Serialize for Tuning
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        JsonTuning {
+            scale: (&self.scale).into(),
+            mapping: (&self.mapping).into()
+        }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Tuning
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        let tuning = JsonTuning::deserialize(deserializer)?;
+        Ok(Tuning::new(tuning.scale.to_scale()?, tuning.mapping.into()))
+    }
+}
+
+impl Scale
+{
+    pub fn to_json(&self) -> String
+    {
+        serde_json::to_string(self).expect("scales always serialize")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error>
+    {
+        serde_json::from_str(json)
+    }
+}
+
+impl Tuning
+{
+    pub fn to_json(&self) -> String
+    {
+        serde_json::to_string(self).expect("tunings always serialize")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error>
+    {
+        serde_json::from_str(json)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, KeyboardMapping, Scale, Tuning};
+
+    #[test]
+    fn json()
+    {
+        let mut scale = scl!{"ji" 9/8 386.3 2/1};
+        scale.set_label(1, "third");
+        let json = scale.to_json();
+        assert_eq!(json, concat!(
+            r#"{"name":"ji","pitches":[{"kind":"ratio","value":"9/8"},{"kind":"cents","value":386.3,"label":"third"},"#,
+            r#"{"kind":"ratio","value":"2/1"}],"period":{"kind":"ratio","value":"2/1"}}"#
+        ));
+        assert_eq!(Scale::from_json(&json).unwrap(), scale);
+        assert!(Scale::from_json(r#"{"name":"bad","pitches":[{"kind":"ratio","value":"1.5"}]}"#).is_err());
+
+        let tuning = Tuning::new(scale, KeyboardMapping::default());
+        let json = tuning.to_json();
+        assert!(json.contains(r#""mapping":{"size":0,"first_note":0,"last_note":127,"middle_note":60,"reference_note":69,"reference_frequency":440.0,"formal_octave":0,"mapping":[]}"#));
+        assert_eq!(Tuning::from_json(&json).unwrap(), tuning);
+    }
+}
@@ -0,0 +1,407 @@
+use std::{error::Error, fmt::Display, num::{ParseFloatError, ParseIntError}, str::FromStr};
+
+use crate::{parser::lines, Scale};
+
+/// A Scala .kbm keyboard mapping, assigning scale degrees to MIDI keys.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct KeyboardMapping
+{
+    /// How many keys the mapping pattern spans before it repeats, or 0 for a linear mapping.
+    pub size: usize,
+    pub first_note: u8,
+    pub last_note: u8,
+    /// The key the first entry of the mapping is mapped to.
+    pub middle_note: u8,
+    pub reference_note: u8,
+    pub reference_frequency: f64,
+    /// The scale degree the mapping pattern advances by every time it repeats.
+    pub formal_octave: usize,
+    /// The scale degree of each key in the pattern, or `None` for an unmapped key.
+    pub mapping: Vec<Option<usize>>
+}
+
+impl KeyboardMapping
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        size: usize,
+        first_note: u8,
+        last_note: u8,
+        middle_note: u8,
+        reference_note: u8,
+        reference_frequency: f64,
+        formal_octave: usize,
+        mapping: Vec<Option<usize>>
+    ) -> Self
+    {
+        Self {
+            size,
+            first_note,
+            last_note,
+            middle_note,
+            reference_note,
+            reference_frequency,
+            formal_octave,
+            mapping
+        }
+    }
+
+    /// Every degree of a scale on consecutive keys from middle C, with A4 at 440 Hz.
+    pub fn linear(scale_size: usize) -> Self
+    {
+        Self::new(scale_size, 0, 127, 60, 69, 440.0, scale_size, (0..scale_size).map(Some).collect())
+    }
+
+    /// A seven-note scale on the white keys from middle C, leaving the black keys unmapped, with A4 at 440 Hz.
+    pub fn white_keys() -> Self
+    {
+        let mapping = [0, 2, 4, 5, 7, 9, 11].into_iter()
+            .fold(vec![None; 12], |mut mapping, key| {
+                mapping[key] = Some(mapping.iter().flatten().count());
+                mapping
+            });
+        Self::new(12, 0, 127, 60, 69, 440.0, 7, mapping)
+    }
+}
+
+/// A way a keyboard mapping doesn't fit the scale it's used with, found by [`KeyboardMapping::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MappingIssue
+{
+    /// The scale has no pitches to map.
+    EmptyScale,
+    /// The first note to retune is after the last.
+    ReversedRange
+    {
+        first_note: u8,
+        last_note: u8
+    },
+    /// Fewer entries are listed than the map size, leaving the rest unmapped.
+    MissingEntries(usize),
+    /// An entry maps to a degree the scale doesn't have, being its note count or more.
+    DegreeOutOfRange
+    {
+        entry: usize,
+        degree: usize
+    },
+    /// The formal octave is past the last degree of the scale.
+    FormalOctaveOutOfRange(usize),
+    /// The reference note is unmapped, so the middle note plays the reference frequency instead.
+    UnmappedReference(u8)
+}
+impl Display for MappingIssue
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Self::EmptyScale => write!(f, "the scale has no pitches to map"),
+            Self::ReversedRange { first_note, last_note } => write!(f, "first note {} to retune is after the last note {}", first_note, last_note),
+            Self::MissingEntries(count) => write!(f, "only {} mapping entries listed, leaving the rest of the map unmapped", count),
+            Self::DegreeOutOfRange { entry, degree } => write!(f, "entry {} maps to degree {}, which the scale doesn't have", entry, degree),
+            Self::FormalOctaveOutOfRange(degree) => write!(f, "formal octave degree {} is past the end of the scale", degree),
+            Self::UnmappedReference(note) => write!(f, "reference note {} is unmapped", note)
+        }
+    }
+}
+impl Error for MappingIssue {}
+
+impl KeyboardMapping
+{
+    /// Checks that the mapping makes sense for a scale, listing every problem found.
+    ///
+    /// ```
+    /// use serde_scala::{kbm, KeyboardMapping, MappingIssue, Scale};
+    ///
+    /// let mapping = kbm!{3 0 127 60 60 261.0 5 0 1 7};
+    /// assert_eq!(mapping.validate(&Scale::edo(5)), Err(vec![MappingIssue::DegreeOutOfRange { entry: 2, degree: 7 }]));
+    /// ```
+    pub fn validate(&self, scale: &Scale) -> Result<(), Vec<MappingIssue>>
+    {
+        let count = scale.pitches.len();
+        let mut issues = vec![];
+        if count == 0
+        {
+            issues.push(MappingIssue::EmptyScale);
+        }
+        if self.first_note > self.last_note
+        {
+            issues.push(MappingIssue::ReversedRange {
+                first_note: self.first_note,
+                last_note: self.last_note
+            });
+        }
+        if self.size > 0
+        {
+            if self.mapping.len() < self.size
+            {
+                issues.push(MappingIssue::MissingEntries(self.mapping.len()));
+            }
+            if count > 0
+            {
+                issues.extend(self.mapping.iter()
+                    .enumerate()
+                    .filter_map(|(entry, &degree)| Some(MappingIssue::DegreeOutOfRange { entry, degree: degree.filter(|&degree| degree >= count)? })));
+                if self.formal_octave > count
+                {
+                    issues.push(MappingIssue::FormalOctaveOutOfRange(self.formal_octave));
+                }
+            }
+
+            let offset = self.reference_note as i64 - self.middle_note as i64;
+            let entry = offset.rem_euclid(self.size as i64) as usize;
+            if self.mapping.get(entry).copied().flatten().is_none()
+            {
+                issues.push(MappingIssue::UnmappedReference(self.reference_note));
+            }
+        }
+
+        if issues.is_empty()
+        {
+            Ok(())
+        }
+        else
+        {
+            Err(issues)
+        }
+    }
+}
+
+/// The mapping Scala assumes when there is no .kbm file: every key in order, with A4 at 440 Hz.
+impl Default for KeyboardMapping
+{
+    fn default() -> Self
+    {
+        Self::new(0, 0, 127, 60, 69, 440.0, 0, vec![])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseKeyboardMappingError
+{
+    ParseFloat(ParseFloatError),
+    ParseInt(ParseIntError),
+    MissingHeader,
+    TooManyEntries(usize)
+}
+impl From<ParseFloatError> for ParseKeyboardMappingError
+{
+    fn from(value: ParseFloatError) -> Self
+    {
+        Self::ParseFloat(value)
+    }
+}
+impl From<ParseIntError> for ParseKeyboardMappingError
+{
+    fn from(value: ParseIntError) -> Self
+    {
+        Self::ParseInt(value)
+    }
+}
+impl Display for ParseKeyboardMappingError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Self::ParseFloat(err) => write!(f, "invalid reference frequency: {}", err),
+            Self::ParseInt(err) => write!(f, "invalid header value or mapping entry: {}", err),
+            Self::MissingHeader => write!(f, "header is incomplete"),
+            Self::TooManyEntries(count) => write!(f, "{} mapping entries listed, more than the map size", count)
+        }
+    }
+}
+impl Error for ParseKeyboardMappingError
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)>
+    {
+        match self
+        {
+            Self::ParseFloat(err) => Some(err),
+            Self::ParseInt(err) => Some(err),
+            Self::MissingHeader | Self::TooManyEntries(_) => None
+        }
+    }
+}
+
+impl FromStr for KeyboardMapping
+{
+    type Err = ParseKeyboardMappingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let mut lines = lines(s)
+            .map(|s| s.split_once("!").map(|(s, _)| s).unwrap_or(s).trim())
+            .filter(|s| !s.is_empty());
+        let mut header = || lines.next().ok_or(ParseKeyboardMappingError::MissingHeader);
+
+        let size = header()?.parse()?;
+        let first_note = header()?.parse()?;
+        let last_note = header()?.parse()?;
+        let middle_note = header()?.parse()?;
+        let reference_note = header()?.parse()?;
+        let reference_frequency = header()?.parse()?;
+        let formal_octave = header()?.parse()?;
+
+        let mapping = lines.map(|s| match s
+            {
+                "x" | "X" => Ok(None),
+                s => s.parse().map(Some)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if mapping.len() > size
+        {
+            return Err(ParseKeyboardMappingError::TooManyEntries(mapping.len()))
+        }
+
+        Ok(Self::new(size, first_note, last_note, middle_note, reference_note, reference_frequency, formal_octave, mapping))
+    }
+}
+
+impl Display for KeyboardMapping
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        writeln!(f, "! Generated keyboard mapping:")?;
+        writeln!(f, "! Size of map:")?;
+        writeln!(f, "{}", self.size)?;
+        writeln!(f, "! First MIDI note number to retune:")?;
+        writeln!(f, "{}", self.first_note)?;
+        writeln!(f, "! Last MIDI note number to retune:")?;
+        writeln!(f, "{}", self.last_note)?;
+        writeln!(f, "! Middle note where the first entry of the mapping is mapped to:")?;
+        writeln!(f, "{}", self.middle_note)?;
+        writeln!(f, "! Reference note for which frequency is given:")?;
+        writeln!(f, "{}", self.reference_note)?;
+        writeln!(f, "! Frequency to tune the above note to:")?;
+        writeln!(f, "{:.6}", self.reference_frequency)?;
+        writeln!(f, "! Scale degree to consider as formal octave:")?;
+        writeln!(f, "{}", self.formal_octave)?;
+        writeln!(f, "! Mapping.")?;
+
+        for degree in self.mapping.iter()
+        {
+            match degree
+            {
+                Some(degree) => writeln!(f, "{}", degree)?,
+                None => writeln!(f, "x")?
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[allow(unused_macros)]
+macro map_degree {
+    (x) => {
+        None
+    },
+    ($degree:literal) => {
+        Some($degree)
+    },
+    ($unexpected:tt) => {
+        compile_error!(concat!("expected a scale degree or `x`, found `", stringify!($unexpected), "`"))
+    }
+}
+
+/// Declares a keyboard mapping inline, laid out like a .kbm file.
+///
+/// ```
+/// #![feature(decl_macro)]
+///
+/// use serde_scala::kbm;
+///
+/// let white_keys = kbm!{
+///     12
+///     0 127
+///     60
+///     69 440.0
+///     7
+///     0 x 1 x 2 3 x 4 x 5 x 6
+/// };
+/// assert_eq!(white_keys.mapping[1], None);
+/// assert_eq!(white_keys.mapping[11], Some(6));
+/// ```
+pub macro kbm {
+    {
+        $size:literal
+        $first_note:literal $last_note:literal
+        $middle_note:literal
+        $reference_note:literal $reference_frequency:literal
+        $formal_octave:literal
+        $($degrees:tt)*
+    } => {
+        KeyboardMapping::new(
+            $size,
+            $first_note,
+            $last_note,
+            $middle_note,
+            $reference_note,
+            $reference_frequency as f64,
+            $formal_octave,
+            vec![$(map_degree!($degrees)),*]
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{kbm, scl, KeyboardMapping, MappingIssue, ParseKeyboardMappingError, ReferencePitch, Scale};
+
+    #[test]
+    fn parse_and_write()
+    {
+        let text = "! example.kbm
+!
+12
+0
+127
+60
+69
+440.0
+12
+! Mapping.
+0
+1
+2
+x
+4
+";
+        let mapping: KeyboardMapping = text.parse().unwrap();
+        assert_eq!(mapping, kbm!{12 0 127 60 69 440 12 0 1 2 x 4});
+
+        let reparsed: KeyboardMapping = mapping.to_string().parse().unwrap();
+        assert_eq!(reparsed, mapping);
+
+        assert_eq!("3\n0\n127\n60\n69\n440.0\n3\n0\n1\n2\n3\n".parse::<KeyboardMapping>(), Err(ParseKeyboardMappingError::TooManyEntries(4)));
+        assert_eq!("12\n0\n127\n".parse::<KeyboardMapping>(), Err(ParseKeyboardMappingError::MissingHeader));
+    }
+
+    #[test]
+    fn generators()
+    {
+        assert_eq!(KeyboardMapping::linear(3), kbm!{3 0 127 60 69 440.0 3 0 1 2});
+        assert_eq!(KeyboardMapping::white_keys(), kbm!{12 0 127 60 69 440.0 7 0 x 1 x 2 3 x 4 x 5 x 6});
+        assert_eq!(KeyboardMapping::white_keys().with_reference(ReferencePitch::new(60, 261.0)).reference_frequency, 261.0);
+    }
+
+    #[test]
+    fn validate()
+    {
+        let pentatonic = scl!{"pentatonic" 9/8 5/4 3/2 5/3 2/1};
+        assert_eq!(kbm!{12 0 127 60 60 261.0 5 0 x 1 x 2 3 x 4 x x x x}.validate(&pentatonic), Ok(()));
+        assert_eq!(KeyboardMapping::default().validate(&Scale::edo(12)), Ok(()));
+
+        assert_eq!(kbm!{4 100 20 60 61 261.0 9 0 x 5}.validate(&pentatonic), Err(vec![
+            MappingIssue::ReversedRange { first_note: 100, last_note: 20 },
+            MappingIssue::MissingEntries(3),
+            MappingIssue::DegreeOutOfRange { entry: 2, degree: 5 },
+            MappingIssue::FormalOctaveOutOfRange(9),
+            MappingIssue::UnmappedReference(61)
+        ]));
+        assert_eq!(KeyboardMapping::default().validate(&Scale::new("empty".to_string(), vec![])), Err(vec![MappingIssue::EmptyScale]));
+    }
+}
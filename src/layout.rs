@@ -0,0 +1,222 @@
+use std::fmt::Write;
+
+use crate::{Pitch, Scale};
+
+/// How many keys a Lumatone has on each of its boards.
+pub const LUMATONE_BOARD_KEYS: usize = 56;
+/// How many boards a Lumatone has.
+pub const LUMATONE_BOARDS: usize = 5;
+
+/// How many keys each row of a Lumatone board has, top to bottom, and the column each row starts at,
+/// with every odd row shifted half a key to the right.
+const LUMATONE_ROWS: [(usize, i64); 11] = [(2, 0), (5, 0), (6, 0), (6, 0), (6, 0), (6, 0), (6, 0), (6, 0), (6, 0), (5, 1), (2, 4)];
+
+/// A hexagonal key layout where moving one key to the right or one key up and to the right always moves
+/// by the same number of scale degrees, so every chord and scale has the same shape everywhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IsomorphicLayout
+{
+    /// The degrees one key to the right.
+    pub right: i64,
+    /// The degrees one key up and to the right.
+    pub up_right: i64
+}
+
+/// A key of an isomorphic layout, at axial hex coordinates with `q` increasing to the right and `r` downwards.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutKey
+{
+    pub q: i64,
+    pub r: i64,
+    /// The degree the key plays, extended past the period.
+    pub degree: i64,
+    pub cents: f64
+}
+
+/// The cents of a degree extended past the period, or `None` for a scale without pitches.
+fn degree_cents(scale: &Scale, degree: i64) -> Option<f64>
+{
+    let count = scale.pitches.len() as i64;
+    let period = scale.period()?.to_cents();
+    let cents = scale.degree(degree.rem_euclid(count) as usize)?.to_cents();
+    Some(cents + degree.div_euclid(count) as f64*period)
+}
+
+fn json_string(s: &str) -> String
+{
+    let mut json = String::from("\"");
+    for c in s.chars()
+    {
+        match c
+        {
+            '"' => json += "\\\"",
+            '\\' => json += "\\\\",
+            c if (c as u32) < 0x20 => json += &format!("\\u{:04x}", c as u32),
+            c => json.push(c)
+        }
+    }
+    json.push('"');
+    json
+}
+
+impl IsomorphicLayout
+{
+    pub fn new(right: i64, up_right: i64) -> Self
+    {
+        Self {
+            right,
+            up_right
+        }
+    }
+
+    /// The generalized Wicki-Hayden layout of a scale generated by `generator`: up and to the right by a generator,
+    /// and to the right by two generators less a period.
+    ///
+    /// Returns `None` if no degree is within `tolerance` cents of the generator reduced by the period.
+    ///
+    /// ```
+    /// use serde_scala::{IsomorphicLayout, Pitch, Scale};
+    ///
+    /// let layout = IsomorphicLayout::from_generator(&Scale::edo(12), Pitch::ratio(3, 2), 5.0).unwrap();
+    /// assert_eq!(layout, IsomorphicLayout::new(2, 7));
+    /// ```
+    pub fn from_generator(scale: &Scale, generator: Pitch, tolerance: f64) -> Option<Self>
+    {
+        let period = scale.period()?.to_cents();
+        let reduced = Pitch::Cents(generator.to_cents().rem_euclid(period));
+        let steps = scale.find(reduced, tolerance)?.0 as i64;
+        let notes = scale.pitches.len() as i64;
+        Some(Self::new(2*steps - notes, steps))
+    }
+
+    /// The degree played at axial hex coordinates, relative to the key playing the unison.
+    pub fn degree(&self, q: i64, r: i64) -> i64
+    {
+        // (q, r) is q + r steps right and r steps down-left, which undoes r steps up and to the right.
+        self.right*(q + r) - self.up_right*r
+    }
+
+    pub fn key(&self, scale: &Scale, q: i64, r: i64) -> Option<LayoutKey>
+    {
+        let degree = self.degree(q, r);
+        Some(LayoutKey {
+            q,
+            r,
+            degree,
+            cents: degree_cents(scale, degree)?
+        })
+    }
+
+    /// A generic JSON description of a rectangle of keys, `width` keys to the right and `height` rows down
+    /// from the unison, with every odd row shifted half a key to the right.
+    pub fn to_json(&self, scale: &Scale, width: usize, height: usize) -> String
+    {
+        let mut json = format!(
+            "{{\"name\":{},\"right\":{},\"up_right\":{},\"keys\":[",
+            json_string(&scale.name),
+            self.right,
+            self.up_right
+        );
+        let mut first = true;
+        for row in 0..height as i64
+        {
+            for column in 0..width as i64
+            {
+                let Some(key) = self.key(scale, column - row.div_euclid(2), row)
+                else
+                {
+                    continue
+                };
+                if !first
+                {
+                    json.push(',');
+                }
+                first = false;
+                let _ = write!(
+                    json,
+                    "{{\"row\":{},\"column\":{},\"q\":{},\"r\":{},\"degree\":{},\"cents\":{}}}",
+                    row, column, key.q, key.r, key.degree, key.cents
+                );
+            }
+        }
+        json += "]}";
+        json
+    }
+
+    /// A Lumatone key map preset (.ltn), assigning every key a MIDI note and channel so that the key playing the unison,
+    /// the first key of the middle row of the middle board, sends note 60 on channel 1 and every degree above it counts
+    /// up through notes and then channels.
+    ///
+    /// Keys playing the unison or one of its repetitions at the period are colored.
+    pub fn to_ltn(&self, scale: &Scale) -> String
+    {
+        let notes = scale.pitches.len().max(1) as i64;
+        let (origin_q, origin_r) = lumatone_key_coordinates(LUMATONE_BOARDS/2, 25);
+
+        let mut ltn = String::new();
+        for board in 0..LUMATONE_BOARDS
+        {
+            let _ = writeln!(ltn, "[Board{}]", board);
+            for key in 0..LUMATONE_BOARD_KEYS
+            {
+                let (q, r) = lumatone_key_coordinates(board, key);
+                let index = (60 + self.degree(q - origin_q, r - origin_r)).clamp(0, 16*128 - 1);
+                let degree = index - 60;
+                let color = if degree.rem_euclid(notes) == 0 { "ffd700" } else { "ffffff" };
+                let _ = writeln!(ltn, "Key_{}={}", key, index % 128);
+                let _ = writeln!(ltn, "Chan_{}={}", key, index/128 + 1);
+                let _ = writeln!(ltn, "Col_{}={}", key, color);
+            }
+        }
+        ltn
+    }
+}
+
+/// The axial hex coordinates of a key on a Lumatone, each board lying two rows down and six keys right of the one before.
+fn lumatone_key_coordinates(board: usize, key: usize) -> (i64, i64)
+{
+    let mut remaining = key;
+    let (mut row, mut column) = (0, 0);
+    for (r, &(length, start)) in LUMATONE_ROWS.iter().enumerate()
+    {
+        if remaining < length
+        {
+            row = r as i64;
+            column = start + remaining as i64;
+            break
+        }
+        remaining -= length;
+    }
+    let row = row + 2*board as i64;
+    let column = column + 6*board as i64;
+    (column - row.div_euclid(2), row)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, IsomorphicLayout, Pitch, Scale};
+
+    #[test]
+    fn isomorphic_layout()
+    {
+        let meantone = scl!{"meantone[7]" 193.2 386.3 503.4 696.6 889.7 1082.9 1200.0};
+        let layout = IsomorphicLayout::from_generator(&meantone, Pitch::cents(696.6), 0.1).unwrap();
+        assert_eq!(layout, IsomorphicLayout::new(1, 4));
+
+        assert_eq!(layout.degree(1, 0), 1);
+        assert_eq!(layout.degree(1, -1), 4);
+        let key = layout.key(&meantone, 2, -2).unwrap();
+        assert_eq!(key.degree, 8);
+        assert!((key.cents - 1393.2).abs() < 1e-9);
+
+        let json = layout.to_json(&meantone, 2, 2);
+        assert!(json.starts_with("{\"name\":\"meantone[7]\",\"right\":1,\"up_right\":4,\"keys\":[{\"row\":0,\"column\":0,\"q\":0,\"r\":0,\"degree\":0,\"cents\":0}"));
+        assert_eq!(json.matches("\"degree\"").count(), 4);
+
+        let ltn = IsomorphicLayout::new(2, 7).to_ltn(&Scale::edo(12));
+        assert_eq!(ltn.lines().filter(|line| line.starts_with("[Board")).count(), 5);
+        assert_eq!(ltn.lines().filter(|line| line.starts_with("Key_")).count(), 5*56);
+        assert!(ltn.contains("[Board2]\n") && ltn.split("[Board2]").nth(1).unwrap().contains("Key_25=60\nChan_25=1\nCol_25=ffd700\n"));
+    }
+}
@@ -1,18 +1,207 @@
 #![feature(decl_macro)]
 
-use std::{fmt::{Display, write}, str::FromStr, num::{ParseFloatError, ParseIntError}, string::FromUtf8Error};
+use std::{error::Error, fmt::Display, ops::{Div, Index, IndexMut, Mul}, str::FromStr, num::{ParseFloatError, ParseIntError}, string::FromUtf8Error};
 
 use num_rational::Ratio;
 
+mod analysis;
+#[cfg(feature = "network")]
+mod archive;
+#[cfg(feature = "tokio")]
+mod async_io;
+mod bank;
+mod builder;
+mod canonical;
+mod comma;
+mod construct;
+mod degree;
+mod diagnostics;
+mod document;
+#[cfg(feature = "exact-cents")]
+mod exact;
+mod expr;
+#[cfg(feature = "fuzz")]
+mod fuzz;
+#[cfg(feature = "heapless")]
+mod heapless_scale;
+#[cfg(feature = "serde")]
+mod json;
+mod kbm;
+mod layout;
+mod library;
+mod linalg;
+mod metadata;
+mod midi;
+mod midi2;
+#[cfg(feature = "midly")]
+mod midly;
+mod monzo;
+mod mts;
+#[cfg(feature = "mts-esp")]
+mod mts_esp;
+mod notation;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod parser;
+mod prime;
+#[cfg(feature = "pyo3")]
+mod python;
+#[cfg(feature = "rand")]
+mod random;
+mod reference;
+mod report;
+mod retuner;
+mod scale_ref;
+#[cfg(feature = "midly")]
+mod smf;
+mod static_scale;
+mod svg;
+mod temperament;
+mod tun;
+#[cfg(feature = "tune")]
+mod tune;
+mod tuning;
+mod tuning_map;
+mod val;
+mod validate;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "notify")]
+mod watch;
+mod write;
+#[cfg(feature = "rkyv")]
+mod zero_copy;
+
+pub use analysis::*;
+#[cfg(feature = "network")]
+pub use archive::*;
+pub use bank::*;
+pub use builder::*;
+pub use canonical::*;
+pub use comma::*;
+pub use construct::*;
+pub use degree::*;
+pub use diagnostics::*;
+pub use document::*;
+#[cfg(feature = "exact-cents")]
+pub use exact::*;
+pub use expr::*;
+#[cfg(feature = "heapless")]
+pub use heapless_scale::*;
+pub use kbm::*;
+pub use layout::*;
+pub use library::*;
+pub use metadata::*;
+pub use midi2::*;
+pub use monzo::*;
+pub use mts::*;
+#[cfg(feature = "mts-esp")]
+pub use mts_esp::*;
+pub use notation::*;
+#[cfg(feature = "rayon")]
+pub use parallel::*;
+pub use parser::*;
+#[cfg(feature = "pyo3")]
+pub use python::*;
+#[cfg(feature = "rand")]
+pub use random::*;
+pub use reference::*;
+pub use retuner::*;
+pub use scale_ref::*;
+#[cfg(feature = "midly")]
+pub use smf::*;
+pub use static_scale::*;
+pub use svg::*;
+pub use temperament::*;
+pub use tun::*;
+pub use tuning::*;
+pub use tuning_map::*;
+pub use val::*;
+pub use validate::*;
+#[cfg(feature = "wasm")]
+pub use wasm::*;
+#[cfg(feature = "notify")]
+pub use watch::*;
+pub use write::*;
+#[cfg(feature = "rkyv")]
+pub use zero_copy::*;
+
+/// Cents values closer than this are considered the same pitch or interval size.
+pub(crate) const EPSILON: f64 = 1e-6;
+
+#[doc(hidden)]
+pub mod __private
+{
+    pub use num_rational::Ratio;
+
+    pub trait PitchLiteral
+    {
+        fn into_pitch(self) -> crate::Pitch;
+    }
+    impl PitchLiteral for f64
+    {
+        fn into_pitch(self) -> crate::Pitch
+        {
+            crate::Pitch::Cents(self)
+        }
+    }
+    /// Only cents can be negative, so a negated integer is rejected instead of being read as cents.
+    pub trait CentsLiteral
+    {
+        fn into_cents(self) -> f64;
+    }
+    impl CentsLiteral for f64
+    {
+        fn into_cents(self) -> f64
+        {
+            self
+        }
+    }
+    macro impl_pitch_literal_int($($t:ty),*) {
+        $(
+            impl PitchLiteral for $t
+            {
+                fn into_pitch(self) -> crate::Pitch
+                {
+                    crate::Pitch::Ratio(Ratio::new(self as u128, 1))
+                }
+            }
+        )*
+    }
+    impl_pitch_literal_int!(u8, u16, u32, u64, u128, usize, i32);
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub enum Pitch
 {
     Cents(f64),
-    Ratio(Ratio<u128>)
+    Ratio(#[cfg_attr(feature = "rkyv", rkyv(with = RatioDef))] Ratio<u128>)
 }
 
 impl Pitch
 {
+    pub const fn unison() -> Self
+    {
+        Self::Ratio(Ratio::new_raw(1, 1))
+    }
+
+    pub const fn cents(cents: f64) -> Self
+    {
+        Self::Cents(cents)
+    }
+
+    /// A reduced ratio, usable in `const` contexts.
+    ///
+    /// Panics if `denom` is zero.
+    pub const fn ratio(numer: u128, denom: u128) -> Self
+    {
+        assert!(denom != 0, "denominator == 0");
+        let gcd = prime::gcd(numer, denom);
+        Self::Ratio(Ratio::new_raw(numer/gcd, denom/gcd))
+    }
+
+    /// The size in cents, unrounded. `Display` rounds cents to five decimals, see [`Pitch::cents_rounded`] for other precisions.
     pub fn to_cents(self) -> f64
     {
         match self
@@ -22,6 +211,13 @@ impl Pitch
         }
     }
 
+    /// The size in cents, rounded half away from zero to `decimals` decimal places.
+    pub fn cents_rounded(self, decimals: u32) -> f64
+    {
+        let scale = 10f64.powi(decimals as i32);
+        (self.to_cents()*scale).round()/scale
+    }
+
     pub fn to_note_offset(self) -> f64
     {
         match self
@@ -30,6 +226,80 @@ impl Pitch
             Self::Ratio(ratio) => (*ratio.numer() as f64/ *ratio.denom() as f64).log2()*12.0
         }
     }
+
+    /// Like `to_note_offset`, but with `period` split into `divisions` notes instead of the octave into 12.
+    pub fn to_note_offset_in(self, period: Pitch, divisions: u32) -> f64
+    {
+        self.to_cents()/period.to_cents()*divisions as f64
+    }
+
+    /// Moves the pitch by whole periods to above the unison, up to and including `period`, the way scale degrees
+    /// are listed. Exact if both are ratios, unless the ratios outgrow a `u128`.
+    ///
+    /// Pitches are left as they are by periods no larger than the unison.
+    pub fn reduced_into(self, period: Pitch) -> Pitch
+    {
+        let period_cents = period.to_cents();
+        if period_cents.is_nan() || period_cents <= 0.0 || self == Pitch::ratio(0, 1)
+        {
+            return self
+        }
+
+        let unison = Ratio::from_integer(1);
+        let mut pitch = self;
+        loop
+        {
+            match (pitch, period)
+            {
+                (Self::Ratio(ratio), Self::Ratio(period_ratio)) if ratio > period_ratio => pitch = pitch/period,
+                (Self::Ratio(ratio), Self::Ratio(_)) if ratio <= unison => pitch = pitch*period,
+                (Self::Ratio(_), Self::Ratio(_)) => return pitch,
+                _ =>
+                {
+                    let cents = pitch.to_cents();
+                    let periods = (cents/period_cents).ceil() - 1.0;
+                    return Self::Cents(cents - periods*period_cents)
+                }
+            }
+        }
+    }
+}
+
+/// Stacks two intervals, exactly if both are ratios and the result fits in a `u128`, otherwise in cents.
+impl Mul for Pitch
+{
+    type Output = Pitch;
+
+    fn mul(self, rhs: Pitch) -> Pitch
+    {
+        if let (Self::Ratio(a), Self::Ratio(b)) = (self, rhs)
+        {
+            let g1 = prime::gcd(*a.numer(), *b.denom());
+            let g2 = prime::gcd(*b.numer(), *a.denom());
+            let numer = (a.numer()/g1).checked_mul(b.numer()/g2);
+            let denom = (a.denom()/g2).checked_mul(b.denom()/g1);
+            if let (Some(numer), Some(denom)) = (numer, denom)
+            {
+                return Self::Ratio(Ratio::new_raw(numer, denom))
+            }
+        }
+        Self::Cents(self.to_cents() + rhs.to_cents())
+    }
+}
+
+/// The interval from `rhs` up to `self`, exactly if both are ratios and the result fits in a `u128`, otherwise in cents.
+impl Div for Pitch
+{
+    type Output = Pitch;
+
+    fn div(self, rhs: Pitch) -> Pitch
+    {
+        match rhs
+        {
+            Self::Ratio(ratio) if *ratio.numer() != 0 => self*Self::Ratio(Ratio::new_raw(*ratio.denom(), *ratio.numer())),
+            _ => Self::Cents(self.to_cents() - rhs.to_cents())
+        }
+    }
 }
 
 impl Display for Pitch
@@ -48,7 +318,15 @@ impl Display for Pitch
 pub enum ParsePitchError
 {
     ParseFloat(ParseFloatError),
-    ParseInt(ParseIntError)
+    ParseInt(ParseIntError),
+    /// There was nothing to parse.
+    Empty,
+    /// A ratio had more than one `/`.
+    MultipleSlashes,
+    /// A ratio had a denominator of 0.
+    ZeroDenominator,
+    /// Text was left over after the numeric value.
+    TrailingCharacters(String)
 }
 impl From<ParseFloatError> for ParsePitchError
 {
@@ -64,6 +342,33 @@ impl From<ParseIntError> for ParsePitchError
         Self::ParseInt(value)
     }
 }
+impl Display for ParsePitchError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Self::ParseFloat(err) => write!(f, "invalid cents value: {}", err),
+            Self::ParseInt(err) => write!(f, "invalid ratio: {}", err),
+            Self::Empty => write!(f, "pitch is empty"),
+            Self::MultipleSlashes => write!(f, "ratio has more than one '/'"),
+            Self::ZeroDenominator => write!(f, "ratio has a denominator of 0"),
+            Self::TrailingCharacters(rest) => write!(f, "unexpected {:?} after pitch value", rest)
+        }
+    }
+}
+impl Error for ParsePitchError
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)>
+    {
+        match self
+        {
+            Self::ParseFloat(err) => Some(err),
+            Self::ParseInt(err) => Some(err),
+            Self::Empty | Self::MultipleSlashes | Self::ZeroDenominator | Self::TrailingCharacters(_) => None
+        }
+    }
+}
 
 impl FromStr for Pitch
 {
@@ -71,16 +376,62 @@ impl FromStr for Pitch
 
     fn from_str(s: &str) -> Result<Self, Self::Err>
     {
-        let s = s.replace(" ", "");
+        let mut buffer = [0; parser::MAX_SPACED_NUMBER];
+        let s = if s.contains(" ")
+        {
+            parser::without(s, |c| c == ' ', &mut buffer).unwrap_or(s)
+        }
+        else
+        {
+            s
+        };
+        if s.is_empty()
+        {
+            return Err(ParsePitchError::Empty)
+        }
+        let mut end = s.find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '/' | '-' | '+')))
+            .unwrap_or(s.len());
+        // Cents may be in scientific notation, like `1.0e3`.
+        if s[..end].contains(".")
+        {
+            let rest = &s[end..];
+            if let Some(exponent) = rest.strip_prefix(['e', 'E'])
+            {
+                let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+                let count = digits.find(|c: char| !c.is_ascii_digit()).unwrap_or(digits.len());
+                if count > 0
+                {
+                    end += rest.len() - digits.len() + count;
+                }
+            }
+        }
+        let (s, rest) = s.split_at(end);
+        if s.matches("/").count() > 1
+        {
+            return Err(ParsePitchError::MultipleSlashes)
+        }
+
         if s.contains(".")
         {
-            let s = s.replace("cents", "");
+            if !rest.is_empty() && rest != "cents"
+            {
+                return Err(ParsePitchError::TrailingCharacters(rest.to_string()))
+            }
             Ok(Self::Cents(s.parse()?))
         }
+        else if !rest.is_empty()
+        {
+            Err(ParsePitchError::TrailingCharacters(rest.to_string()))
+        }
         else if s.contains("/")
         {
             let (numer, denom) = s.split_once("/").unwrap();
-            Ok(Self::Ratio(Ratio::new(numer.parse()?, denom.parse()?)))
+            let (numer, denom) = (numer.parse()?, denom.parse()?);
+            if denom == 0
+            {
+                return Err(ParsePitchError::ZeroDenominator)
+            }
+            Ok(Self::Ratio(Ratio::new(numer, denom)))
         }
         else
         {
@@ -89,59 +440,172 @@ impl FromStr for Pitch
     }
 }
 
+#[allow(unused_macros)]
 macro add_pitch {
     (
-        $pitches:expr;
+        $scale:ident;
+    ) => {},
+    (
+        $scale:ident;
+        ! $label:literal
+        $($($more:tt)+)?
+    ) => {
+        if let Some(index) = $scale.pitches.len().checked_sub(1)
+        {
+            $scale.set_label(index, $label);
+        }
+        $(
+            add_pitch!($scale; $($more)+);
+        )?
+    },
+    (
+        $scale:ident;
         $numer:literal / $denom:literal
         $($($more:tt)+)?
     ) => {
-        $pitches.push(Pitch::Ratio(Ratio::new($numer, $denom)));
+        $scale.pitches.push(Pitch::Ratio(Ratio::new($numer, $denom)));
         $(
-            add_pitch!($pitches; $($more)+);
+            add_pitch!($scale; $($more)+);
         )?
     },
     (
-        $pitches:expr;
-        $cents:literal
+        $scale:ident;
+        - $cents:literal
         $($($more:tt)+)?
     ) => {
-        $pitches.push(Pitch::Cents($cents));
+        $scale.pitches.push(Pitch::Cents(-__private::CentsLiteral::into_cents($cents)));
         $(
-            add_pitch!($pitches; $($more)+);
+            add_pitch!($scale; $($more)+);
         )?
     },
     (
-        $pitches:expr;
-        $numer:literal
+        $scale:ident;
+        [$($expr:tt)*]
         $($($more:tt)+)?
-    ) =>
-    {
-        $pitches.push(Pitch::Ratio(Ratio::new($numer, 1)));
+    ) => {
+        $scale.pitches.push(Pitch::from_expr(stringify!($($expr)*)).expect("invalid pitch expression"));
+        $(
+            add_pitch!($scale; $($more)+);
+        )?
+    },
+    (
+        $scale:ident;
+        ($pitch:expr)
+        $($($more:tt)+)?
+    ) => {
+        $scale.pitches.push($pitch);
         $(
-            add_pitch!($pitches; $($more)+);
+            add_pitch!($scale; $($more)+);
         )?
+    },
+    (
+        $scale:ident;
+        $pitch:literal
+        $($($more:tt)+)?
+    ) => {
+        $scale.pitches.push(__private::PitchLiteral::into_pitch($pitch));
+        $(
+            add_pitch!($scale; $($more)+);
+        )?
+    },
+    (
+        $scale:ident;
+        $unexpected:tt
+        $($more:tt)*
+    ) => {
+        compile_error!(concat!(
+            "expected a pitch like `3/2`, `701.955`, `-50.0`, `[2^(7/12)]` or `(expression)`, found `",
+            stringify!($unexpected),
+            "`"
+        ))
     }
 }
 
+/// Declares a scale inline, laid out like a .scl file.
+///
+/// Pitches are ratios like `3/2` or `2`, cents like `701.955` or `-50.0`, ratio expressions in brackets like `[3^4/2^6]`
+/// (see [`Pitch::from_expr`]), or any parenthesized expression evaluating to a [`Pitch`].
+/// A pitch may be followed by a label like `! "fifth"`.
+/// Use `_` in place of the description for a scale without one.
+///
+/// # Panics
+///
+/// Ratio expressions in brackets are evaluated when the scale is built, panicking if one is not valid, like `[3/0]`.
+///
+/// ```
+/// #![feature(decl_macro)]
+///
+/// use serde_scala::{scl, Pitch};
+///
+/// const FIFTH: Pitch = Pitch::ratio(3, 2);
+///
+/// let scale = scl!{"example"
+///     -10.0
+///     5/4 ! "major third"
+///     (FIFTH)
+///     2
+/// };
+/// assert_eq!(scale.pitches.len(), 4);
+/// assert_eq!(scale.label(1), Some("major third"));
+///
+/// let nameless = scl!{_ 9/8 2/1};
+/// assert_eq!(nameless.name, "");
+/// ```
+///
+/// Negative pitches have to be cents, as ratios can't be negative:
+///
+/// ```compile_fail
+/// #![feature(decl_macro)]
+///
+/// use serde_scala::scl;
+///
+/// let scale = scl!{"negative" -3};
+/// ```
 pub macro scl {
+    {
+        _
+        $($pitches:tt)*
+    } => {
+        {
+            let mut scale = Scale::new(String::new(), vec![]);
+            add_pitch!(scale; $($pitches)*);
+
+            scale
+        }
+    },
     {
         $name:literal
         $($pitches:tt)*
     } => {
         {
-            let mut pitches = vec![];
-            add_pitch!(&mut pitches; $($pitches)*);
+            let mut scale = Scale::new($name.to_string(), vec![]);
+            add_pitch!(scale; $($pitches)*);
 
-            Scale::new($name.to_string(), pitches)
+            scale
         }
+    },
+    {
+        $($unexpected:tt)*
+    } => {
+        compile_error!("expected a description literal or `_` followed by pitches")
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Scales compare equal by name, pitches and labels, whatever text and comments they were parsed from.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct Scale
 {
     pub name: String,
-    pub pitches: Vec<Pitch>
+    pub pitches: Vec<Pitch>,
+    /// The text each cents pitch was parsed from, by index.
+    ///
+    /// It is written back out instead of the formatted value for as long as it still parses to the same pitch.
+    pub cents_text: Vec<Option<String>>,
+    /// Names for pitches, like the text following a pitch in a .scl file, by index.
+    pub labels: Vec<Option<String>>,
+    /// What the comments of the file it was parsed from said.
+    pub metadata: ScaleMetadata
 }
 impl Scale
 {
@@ -149,9 +613,157 @@ impl Scale
     {
         Self {
             name,
-            pitches
+            pitches,
+            cents_text: vec![],
+            labels: vec![],
+            metadata: ScaleMetadata::default()
         }
     }
+
+    pub fn label(&self, index: usize) -> Option<&str>
+    {
+        self.labels.get(index)?.as_deref()
+    }
+
+    pub fn set_label(&mut self, index: usize, label: impl Into<String>)
+    {
+        if self.labels.len() <= index
+        {
+            self.labels.resize(index + 1, None)
+        }
+        self.labels[index] = Some(label.into())
+    }
+
+    /// The text to write a pitch as, if it was parsed from text that still matches it.
+    pub(crate) fn source_text(&self, index: usize) -> Option<&str>
+    {
+        let text = self.cents_text.get(index)?.as_deref()?;
+        match self.pitches.get(index)
+        {
+            Some(&pitch @ Pitch::Cents(_)) if text.parse() == Ok(pitch) => Some(text),
+            _ => None
+        }
+    }
+
+    pub fn from_cents(name: impl Into<String>, cents: impl IntoIterator<Item = f64>) -> Self
+    {
+        Self::new(name.into(), cents.into_iter().map(Pitch::Cents).collect())
+    }
+
+    /// The interval the scale repeats at, which is its last pitch.
+    pub fn period(&self) -> Option<Pitch>
+    {
+        self.pitches.last().copied()
+    }
+
+    /// Whether the period is an octave, within `tolerance` cents.
+    pub fn is_octave_repeating(&self, tolerance: f64) -> bool
+    {
+        self.period().is_some_and(|period| (period.to_cents() - 1200.0).abs() <= tolerance)
+    }
+
+    /// The note offset of every pitch, with the period split into as many notes as the scale has.
+    pub fn note_offsets(&self) -> Vec<f64>
+    {
+        let Some(period) = self.period()
+        else
+        {
+            return vec![]
+        };
+        let divisions = self.pitches.len() as u32;
+        self.pitches.iter()
+            .map(|pitch| pitch.to_note_offset_in(period, divisions))
+            .collect()
+    }
+
+    /// Panics if a denominator is zero.
+    pub fn from_ratios(name: impl Into<String>, ratios: impl IntoIterator<Item = (u128, u128)>) -> Self
+    {
+        Self::new(name.into(), ratios.into_iter().map(|(numer, denom)| Pitch::Ratio(Ratio::new(numer, denom))).collect())
+    }
+
+    /// Equal divisions of the octave, named like the EDO files in the archive.
+    pub fn edo(divisions: u32) -> Self
+    {
+        Self::from_cents(
+            format!("{}-note equal division of octave", divisions),
+            (1..=divisions).map(|i| i as f64/divisions as f64*1200.0)
+        )
+    }
+}
+
+impl PartialEq for Scale
+{
+    fn eq(&self, other: &Self) -> bool
+    {
+        self.name == other.name
+            && self.pitches == other.pitches
+            && (0..self.pitches.len()).all(|i| self.label(i) == other.label(i))
+    }
+}
+
+impl Index<usize> for Scale
+{
+    type Output = Pitch;
+
+    fn index(&self, index: usize) -> &Pitch
+    {
+        &self.pitches[index]
+    }
+}
+impl IndexMut<usize> for Scale
+{
+    fn index_mut(&mut self, index: usize) -> &mut Pitch
+    {
+        &mut self.pitches[index]
+    }
+}
+
+impl IntoIterator for Scale
+{
+    type Item = Pitch;
+    type IntoIter = std::vec::IntoIter<Pitch>;
+
+    fn into_iter(self) -> Self::IntoIter
+    {
+        self.pitches.into_iter()
+    }
+}
+impl<'a> IntoIterator for &'a Scale
+{
+    type Item = &'a Pitch;
+    type IntoIter = std::slice::Iter<'a, Pitch>;
+
+    fn into_iter(self) -> Self::IntoIter
+    {
+        self.pitches.iter()
+    }
+}
+impl<'a> IntoIterator for &'a mut Scale
+{
+    type Item = &'a mut Pitch;
+    type IntoIter = std::slice::IterMut<'a, Pitch>;
+
+    fn into_iter(self) -> Self::IntoIter
+    {
+        self.pitches.iter_mut()
+    }
+}
+
+/// Collects pitches into a scale without a description.
+impl FromIterator<Pitch> for Scale
+{
+    fn from_iter<I: IntoIterator<Item = Pitch>>(iter: I) -> Self
+    {
+        Self::new(String::new(), iter.into_iter().collect())
+    }
+}
+impl Extend<Pitch> for Scale
+{
+    fn extend<I: IntoIterator<Item = Pitch>>(&mut self, iter: I)
+    {
+        self.pitches.extend(iter)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -161,7 +773,13 @@ pub enum ParseScaleError
     ParseInt(ParseIntError),
     MissingDescription,
     MissingNoteCount,
-    WrongPitchCount(usize)
+    WrongPitchCount(usize),
+    EmptyPitch,
+    MultipleSlashes,
+    ZeroDenominator,
+    TrailingCharacters(String),
+    /// A pitch is lower than the one before it. Never fails a parse, only reported by [`Scale::parse_with_diagnostics`].
+    DescendingPitch
 }
 impl From<ParseFloatError> for ParseScaleError
 {
@@ -184,54 +802,59 @@ impl From<ParsePitchError> for ParseScaleError
         match value
         {
             ParsePitchError::ParseFloat(err) => Self::ParseFloat(err),
-            ParsePitchError::ParseInt(err) => Self::ParseInt(err)
+            ParsePitchError::ParseInt(err) => Self::ParseInt(err),
+            ParsePitchError::Empty => Self::EmptyPitch,
+            ParsePitchError::MultipleSlashes => Self::MultipleSlashes,
+            ParsePitchError::ZeroDenominator => Self::ZeroDenominator,
+            ParsePitchError::TrailingCharacters(rest) => Self::TrailingCharacters(rest)
         }
     }
 }
-impl FromStr for Scale
+impl Display for ParseScaleError
 {
-    type Err = ParseScaleError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err>
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
     {
-        let mut name = None;
-        let mut pitch_count = None;
-        let mut pitches = vec![];
-
-        for s in s.lines()
+        match self
         {
-            let s = s.split_once("!").map(|(s, _)| s).unwrap_or(s);
-            if s == ""
-            {
-                continue
-            }
-            else if name.is_none()
-            {
-                name = Some(s.to_string())
-            }
-            else
-            {
-                let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
-                if pitch_count.is_none()
-                {
-                    pitch_count = Some(s.parse()?);
-                }
-                else
-                {
-                    pitches.push(s.parse()?);
-                }
-            }
+            Self::ParseFloat(err) => write!(f, "invalid cents value: {}", err),
+            Self::ParseInt(err) => write!(f, "invalid ratio or note count: {}", err),
+            Self::MissingDescription => write!(f, "missing description line"),
+            Self::MissingNoteCount => write!(f, "missing note count line"),
+            Self::WrongPitchCount(count) => write!(f, "note count does not match the {} pitches listed", count),
+            Self::EmptyPitch => write!(f, "pitch is empty"),
+            Self::MultipleSlashes => write!(f, "ratio has more than one '/'"),
+            Self::ZeroDenominator => write!(f, "ratio has a denominator of 0"),
+            Self::TrailingCharacters(rest) => write!(f, "unexpected {:?} after pitch value", rest),
+            Self::DescendingPitch => write!(f, "pitch is lower than the one before it")
         }
-
-        let name = name.ok_or(ParseScaleError::MissingDescription)?;
-        let pitch_count = pitch_count.ok_or(ParseScaleError::MissingNoteCount)?;
-
-        if pitches.len() != pitch_count
+    }
+}
+impl Error for ParseScaleError
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)>
+    {
+        match self
         {
-            return Err(ParseScaleError::WrongPitchCount(pitches.len()))
+            Self::ParseFloat(err) => Some(err),
+            Self::ParseInt(err) => Some(err),
+            Self::MissingDescription
+                | Self::MissingNoteCount
+                | Self::WrongPitchCount(_)
+                | Self::EmptyPitch
+                | Self::MultipleSlashes
+                | Self::ZeroDenominator
+                | Self::TrailingCharacters(_)
+                | Self::DescendingPitch => None
         }
+    }
+}
+impl FromStr for Scale
+{
+    type Err = ParseScaleError;
 
-        Ok(Scale::new(name, pitches))
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        Self::parse_with(s, &ParseOptions::default())
     }
 }
 
@@ -239,17 +862,7 @@ impl Display for Scale
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
     {
-        writeln!(f, "! Generated scale:")?;
-        writeln!(f, "{}", self.name)?;
-        writeln!(f, "{}", self.pitches.len())?;
-        writeln!(f, "!")?;
-
-        for pitch in self.pitches.iter()
-        {
-            writeln!(f, "{}", pitch)?;
-        }
-
-        Ok(())
+        self.write_with(f, &WriteOptions::default())
     }
 }
 
@@ -258,7 +871,9 @@ pub enum SerdeScalaError
 {
     IO(std::io::Error),
     FromUtf8(FromUtf8Error),
-    ParseScale(ParseScaleError)
+    ParseScale(ParseScaleError),
+    ParseKeyboardMapping(ParseKeyboardMappingError),
+    ParseTun(ParseTunError)
 }
 impl From<std::io::Error> for SerdeScalaError
 {
@@ -281,10 +896,52 @@ impl From<ParseScaleError> for SerdeScalaError
         Self::ParseScale(value)
     }
 }
+impl From<ParseKeyboardMappingError> for SerdeScalaError
+{
+    fn from(value: ParseKeyboardMappingError) -> Self
+    {
+        Self::ParseKeyboardMapping(value)
+    }
+}
+impl From<ParseTunError> for SerdeScalaError
+{
+    fn from(value: ParseTunError) -> Self
+    {
+        Self::ParseTun(value)
+    }
+}
+impl Display for SerdeScalaError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Self::IO(err) => write!(f, "I/O error: {}", err),
+            Self::FromUtf8(err) => write!(f, "file is not valid UTF-8: {}", err),
+            Self::ParseScale(err) => write!(f, "could not parse scale: {}", err),
+            Self::ParseKeyboardMapping(err) => write!(f, "could not parse keyboard mapping: {}", err),
+            Self::ParseTun(err) => write!(f, "could not parse tuning file: {}", err)
+        }
+    }
+}
+impl Error for SerdeScalaError
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)>
+    {
+        match self
+        {
+            Self::IO(err) => Some(err),
+            Self::FromUtf8(err) => Some(err),
+            Self::ParseScale(err) => Some(err),
+            Self::ParseKeyboardMapping(err) => Some(err),
+            Self::ParseTun(err) => Some(err)
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::{BTreeSet, BTreeMap}, fs::{File, self}};
+    use std::fs::{File, self};
 
     use super::*;
 
@@ -312,22 +969,149 @@ mod tests {
     }
 
     #[test]
-    fn it_works() -> Result<(), SerdeScalaError>
+    fn bulk_constructors()
     {
-        let cd = fs::read_dir("scl").or_else(|_| {
-            fs::create_dir("scl")?;
-            fs::read_dir("scl")
-        })?;
-        for entry in cd
+        let edo = Scale::from_cents("5edo", (1..=5).map(|i| i as f64*240.0));
+        assert_eq!(edo, scl!{"5edo" 240.0 480.0 720.0 960.0 1200.0});
+
+        assert_eq!(Scale::edo(5).pitches, edo.pitches);
+
+        let harmonics = Scale::from_ratios("harmonics 8-16", (9..=16).map(|n| (n, 8)));
+        assert_eq!(harmonics, scl!{"harmonics 8-16" 9/8 5/4 11/8 3/2 13/8 7/4 15/8 2/1});
+    }
+
+    #[test]
+    fn collection_traits()
+    {
+        let mut scale: Scale = [Pitch::ratio(5, 4), Pitch::ratio(2, 1)].into_iter().collect();
+        scale.extend([Pitch::ratio(3, 1)]);
+        scale[2] = Pitch::ratio(4, 1);
+
+        assert_eq!(scale, scl!{_ 5/4 2/1 4/1});
+        assert_eq!(scale[0], Pitch::ratio(5, 4));
+        assert_eq!((&scale).into_iter().count(), 3);
+        for pitch in &mut scale
         {
-            let entry = entry?;
-            println!("! {:?}", entry.file_name());
-            let bytes = fs::read(entry.path())?;
-            let contents = String::from_utf8_lossy(&bytes);
-            let scale: Scale = contents.parse()?;
+            *pitch = *pitch/Pitch::ratio(2, 1);
+        }
+        assert_eq!(scale.into_iter().collect::<Vec<_>>(), vec![Pitch::ratio(5, 8), Pitch::ratio(1, 1), Pitch::ratio(2, 1)]);
+    }
+
+    #[test]
+    fn period()
+    {
+        let bohlen_pierce = Scale::from_ratios("BP lambda", [(25, 21), (9, 7), (7, 5), (5, 3), (9, 5), (15, 7), (7, 3), (25, 9), (3, 1)]);
+        assert_eq!(bohlen_pierce.period(), Some(Pitch::ratio(3, 1)));
+        assert!(!bohlen_pierce.is_octave_repeating(1.0));
+
+        let stretched = scl!{"stretched" 600.5 1201.0};
+        assert!(stretched.is_octave_repeating(1.0));
+        assert!(!stretched.is_octave_repeating(0.5));
+        assert_eq!(Scale::from_cents("empty", []).period(), None);
+    }
 
-            //println!("{}", scale)
+    #[test]
+    fn note_offsets()
+    {
+        assert_eq!(Pitch::Cents(700.0).to_note_offset_in(Pitch::ratio(2, 1), 12), 7.0);
+        assert!((Pitch::ratio(3, 1).to_note_offset_in(Pitch::ratio(3, 1), 13) - 13.0).abs() < 1e-12);
+
+        let edo = Scale::from_cents("19edo", (1..=19).map(|i| i as f64*1200.0/19.0));
+        let offsets = edo.note_offsets();
+        assert_eq!(offsets.len(), 19);
+        assert!(offsets.iter().enumerate().all(|(i, offset)| (offset - (i + 1) as f64).abs() < 1e-9));
+    }
+
+    #[test]
+    fn reduced_into()
+    {
+        let octave = Pitch::ratio(2, 1);
+        assert_eq!(Pitch::ratio(9, 4).reduced_into(octave), Pitch::ratio(9, 8));
+        assert_eq!(Pitch::ratio(1, 3).reduced_into(octave), Pitch::ratio(4, 3));
+        assert_eq!(Pitch::ratio(1, 1).reduced_into(octave), octave);
+        assert_eq!(Pitch::ratio(4, 1).reduced_into(octave), octave);
+        assert_eq!(Pitch::ratio(5, 1).reduced_into(Pitch::ratio(3, 1)), Pitch::ratio(5, 3));
+
+        assert_eq!(Pitch::Cents(-100.0).reduced_into(octave), Pitch::Cents(1100.0));
+        assert_eq!(Pitch::Cents(0.0).reduced_into(Pitch::Cents(1200.0)), Pitch::Cents(1200.0));
+        assert!((Pitch::ratio(3, 1).reduced_into(Pitch::Cents(1200.0)).to_cents() - 701.955).abs() < 1e-3);
+        assert_eq!(Pitch::ratio(3, 2).reduced_into(Pitch::ratio(1, 1)), Pitch::ratio(3, 2));
+    }
+
+    #[test]
+    fn rounded_cents()
+    {
+        assert_eq!(Pitch::ratio(3, 2).cents_rounded(2), 701.96);
+        assert_eq!(Pitch::ratio(3, 2).cents_rounded(0), 702.0);
+        assert_eq!(Pitch::ratio(2, 3).cents_rounded(1), -702.0);
+        assert_eq!(Pitch::ratio(5, 4).to_cents(), 1200.0*1.25f64.log2());
+    }
+
+    #[test]
+    fn lossless_cents()
+    {
+        let text = "! Generated scale:\nexact\n4\n!\n100.0\n99.999999\n100.00000 cents\n3/2\n";
+        let mut scale: Scale = text.parse().unwrap();
+
+        assert_eq!(scale.to_string(), text);
+
+        scale[0] = Pitch::cents(150.0);
+        assert!(scale.to_string().contains("\n150.00000\n99.999999\n"));
+    }
+
+    #[test]
+    fn exponent_and_sign_notation()
+    {
+        assert_eq!("1.0e3".parse::<Pitch>(), Ok(Pitch::Cents(1000.0)));
+        assert_eq!("1.5E-1".parse::<Pitch>(), Ok(Pitch::Cents(0.15)));
+        assert_eq!("7.01955e+2 cents".parse::<Pitch>(), Ok(Pitch::Cents(701.955)));
+        assert_eq!("+701.955".parse::<Pitch>(), Ok(Pitch::Cents(701.955)));
+        assert_eq!("+3/2".parse::<Pitch>(), Ok(Pitch::ratio(3, 2)));
+        assert_eq!("1.0e".parse::<Pitch>(), Err(ParsePitchError::TrailingCharacters("e".to_string())));
+
+        let generated = "! generated by a script\nexponents\n3\n!\n+2.4e2\n4.8e2\n1.2e3\n";
+        assert_eq!(generated.parse::<Scale>(), Ok(scl!{"exponents" 240.0 480.0 1200.0}));
+
+        assert_eq!(scl!{"signs" -3.0 3 -1e2}.pitches, [Pitch::Cents(-3.0), Pitch::ratio(3, 1), Pitch::Cents(-100.0)]);
+    }
+
+    #[test]
+    fn pitch_errors()
+    {
+        assert_eq!("".parse::<Pitch>(), Err(ParsePitchError::Empty));
+        assert_eq!("3/2/1".parse::<Pitch>(), Err(ParsePitchError::MultipleSlashes));
+        assert_eq!("3/2x".parse::<Pitch>(), Err(ParsePitchError::TrailingCharacters("x".to_string())));
+        assert_eq!("701.955 c".parse::<Pitch>(), Err(ParsePitchError::TrailingCharacters("c".to_string())));
+        assert_eq!("701.955 cents".parse::<Pitch>(), Ok(Pitch::Cents(701.955)));
+        assert_eq!("test\n1\n3/2/1\n".parse::<Scale>(), Err(ParseScaleError::MultipleSlashes));
+        assert_eq!("1/0".parse::<Pitch>(), Err(ParsePitchError::ZeroDenominator));
+        assert_eq!("test\n1\n1/0\n".parse::<Scale>(), Err(ParseScaleError::ZeroDenominator));
+        assert_eq!(Scale::parse_with_diagnostics("test\n1\n1/0\n").1[0].error, ParseScaleError::ZeroDenominator);
+    }
+
+    #[test]
+    fn error_chain()
+    {
+        let err = SerdeScalaError::from("test\n1\n3/\n".parse::<Scale>().unwrap_err());
+        assert!(err.to_string().starts_with("could not parse scale: invalid ratio"));
+
+        let source = err.source().unwrap();
+        assert!(source.source().unwrap().is::<ParseIntError>());
+
+        let boxed: Box<dyn Error> = Box::new(err);
+        assert!(boxed.downcast_ref::<SerdeScalaError>().is_some());
+    }
+
+    #[test]
+    fn it_works() -> Result<(), SerdeScalaError>
+    {
+        if fs::metadata("scl").is_err()
+        {
+            fs::create_dir("scl")?;
         }
+        let report = validate_dir("scl");
+        print!("{}", report);
+        assert_eq!(report.failed().count(), 0);
 
         Ok(())
     }
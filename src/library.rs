@@ -0,0 +1,217 @@
+use std::{fs, path::{Path, PathBuf}};
+
+use crate::{Pitch, Scale, SerdeScalaError};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LibraryEntry
+{
+    /// Where the scale was loaded from, if it came from a file.
+    pub path: Option<PathBuf>,
+    pub scale: Scale
+}
+
+/// Filters for [`ScaleLibrary::query`]. Fields left as `None` match everything.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScaleQuery
+{
+    /// Case-insensitive substring of the description.
+    pub name: Option<String>,
+    pub note_count: Option<usize>,
+    /// The period and how many cents it may be off by.
+    pub period: Option<(Pitch, f64)>,
+    /// Only scales consisting of ratios within this prime limit.
+    pub prime_limit: Option<u128>,
+    /// Only scales whose steps are all equal within this many cents.
+    pub equal_tempered: Option<f64>
+}
+
+impl ScaleQuery
+{
+    pub fn matches(&self, scale: &Scale) -> bool
+    {
+        if let Some(name) = &self.name
+        {
+            if !scale.name.to_lowercase().contains(&name.to_lowercase())
+            {
+                return false
+            }
+        }
+        if let Some(note_count) = self.note_count
+        {
+            if scale.pitches.len() != note_count
+            {
+                return false
+            }
+        }
+        if let Some((period, tolerance)) = self.period
+        {
+            match scale.period()
+            {
+                Some(last) if (last.to_cents() - period.to_cents()).abs() <= tolerance => (),
+                _ => return false
+            }
+        }
+        if let Some(prime_limit) = self.prime_limit
+        {
+            match scale.prime_limit()
+            {
+                Some(limit) if limit <= prime_limit => (),
+                _ => return false
+            }
+        }
+        if let Some(tolerance) = self.equal_tempered
+        {
+            if !scale.is_equal_tempered(tolerance)
+            {
+                return false
+            }
+        }
+        true
+    }
+}
+
+/// An in-memory collection of scales, such as a directory of the Scala archive.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScaleLibrary
+{
+    pub entries: Vec<LibraryEntry>
+}
+
+impl ScaleLibrary
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    /// Loads every `.scl` file in a directory, sorted by path.
+    pub fn load_dir(path: impl AsRef<Path>) -> Result<Self, SerdeScalaError>
+    {
+        let mut paths = vec![];
+        for entry in fs::read_dir(path)?
+        {
+            let path = entry?.path();
+            if path.is_file() && path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("scl"))
+            {
+                paths.push(path)
+            }
+        }
+        paths.sort();
+
+        let mut library = Self::new();
+        for path in paths
+        {
+            let bytes = fs::read(&path)?;
+            let scale = String::from_utf8_lossy(&bytes).parse()?;
+            library.entries.push(LibraryEntry {
+                path: Some(path),
+                scale
+            })
+        }
+        Ok(library)
+    }
+
+    pub fn push(&mut self, scale: Scale)
+    {
+        self.entries.push(LibraryEntry {
+            path: None,
+            scale
+        })
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.entries.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &LibraryEntry>
+    {
+        self.entries.iter()
+    }
+
+    pub fn query<'a>(&'a self, query: &'a ScaleQuery) -> impl Iterator<Item = &'a LibraryEntry>
+    {
+        self.entries.iter().filter(|entry| query.matches(&entry.scale))
+    }
+
+    pub fn by_name<'a>(&'a self, name: &str) -> impl Iterator<Item = &'a LibraryEntry>
+    {
+        let name = name.to_lowercase();
+        self.entries.iter().filter(move |entry| entry.scale.name.to_lowercase().contains(&name))
+    }
+
+    pub fn with_note_count(&self, note_count: usize) -> impl Iterator<Item = &LibraryEntry>
+    {
+        self.entries.iter().filter(move |entry| entry.scale.pitches.len() == note_count)
+    }
+}
+
+impl FromIterator<Scale> for ScaleLibrary
+{
+    fn from_iter<I: IntoIterator<Item = Scale>>(iter: I) -> Self
+    {
+        let mut library = Self::new();
+        library.extend(iter);
+        library
+    }
+}
+
+impl Extend<Scale> for ScaleLibrary
+{
+    fn extend<I: IntoIterator<Item = Scale>>(&mut self, iter: I)
+    {
+        for scale in iter
+        {
+            self.push(scale)
+        }
+    }
+}
+
+impl Scale
+{
+    /// Whether all steps are the same size within `tolerance` cents.
+    pub fn is_equal_tempered(&self, tolerance: f64) -> bool
+    {
+        self.stats().is_some_and(|stats| stats.largest_step - stats.smallest_step <= tolerance)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use num_rational::Ratio;
+
+    use crate::{scl, Pitch, ScaleLibrary, ScaleQuery};
+
+    #[test]
+    fn query_library()
+    {
+        let library: ScaleLibrary = [
+            scl!{"12-tone equal temperament" 100.0 200.0 300.0 400.0 500.0 600.0 700.0 800.0 900.0 1000.0 1100.0 1200.0},
+            scl!{"Ptolemy's intense diatonic" 9/8 5/4 4/3 3/2 5/3 15/8 2/1},
+            scl!{"Bohlen-Pierce equal temperament" 146.304 292.608 438.913 585.217 731.521 877.825 1024.130 1170.434 1316.738 1463.042 1609.347 1755.651 1901.955}
+        ].into_iter().collect();
+
+        assert_eq!(library.by_name("EQUAL").count(), 2);
+        assert_eq!(library.with_note_count(7).count(), 1);
+
+        let octave_et = ScaleQuery {
+            period: Some((Pitch::Ratio(Ratio::new(2, 1)), 0.01)),
+            equal_tempered: Some(0.01),
+            ..Default::default()
+        };
+        let names: Vec<&str> = library.query(&octave_et).map(|entry| entry.scale.name.as_str()).collect();
+        assert_eq!(names, vec!["12-tone equal temperament"]);
+
+        let five_limit = ScaleQuery {
+            prime_limit: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(library.query(&five_limit).count(), 1);
+    }
+}
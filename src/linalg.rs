@@ -0,0 +1,48 @@
+/// Solves `a x = b` by Gaussian elimination with partial pivoting, or returns `None` if `a` is singular.
+pub(crate) fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>>
+{
+    let n = b.len();
+    for col in 0..n
+    {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-12
+        {
+            return None
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let (above, below) = a.split_at_mut(col + 1);
+        let pivot_row = &above[col];
+        for (i, row) in below.iter_mut().enumerate()
+        {
+            let factor = row[col]/pivot_row[col];
+            for (x, pivot) in row[col..].iter_mut().zip(pivot_row[col..].iter())
+            {
+                *x -= factor*pivot;
+            }
+            b[col + 1 + i] -= factor*b[col];
+        }
+    }
+
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev()
+    {
+        let sum: f64 = (row + 1..n).map(|k| a[row][k]*x[k]).sum();
+        x[row] = (b[row] - sum)/a[row][row];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn linear_system()
+    {
+        let x = solve(vec![vec![2.0, 1.0], vec![1.0, 3.0]], vec![3.0, 5.0]).unwrap();
+        assert!((x[0] - 0.8).abs() < 1e-12 && (x[1] - 1.4).abs() < 1e-12);
+        assert_eq!(solve(vec![vec![1.0, 2.0], vec![2.0, 4.0]], vec![1.0, 2.0]), None);
+    }
+}
@@ -0,0 +1,96 @@
+/// What the comment lines of a .scl file say about it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct ScaleMetadata
+{
+    /// From the conventional `! name.scl` first line.
+    pub file_name: Option<String>,
+    /// From an `! Author: ...` comment.
+    pub author: Option<String>,
+    /// From an `! Source: ...` comment.
+    pub source: Option<String>,
+    /// From a `! Date: ...` comment.
+    pub date: Option<String>,
+    /// Every other comment line, without the `!`.
+    pub comments: Vec<String>
+}
+
+impl ScaleMetadata
+{
+    pub fn is_empty(&self) -> bool
+    {
+        self.file_name.is_none()
+            && self.author.is_none()
+            && self.source.is_none()
+            && self.date.is_none()
+            && self.comments.is_empty()
+    }
+
+    /// Reads a whole-line comment, with the `!` already stripped.
+    pub(crate) fn read_comment(&mut self, comment: &str, is_first: bool)
+    {
+        let comment = comment.trim();
+        if comment.is_empty() || (is_first && comment == "Generated scale:")
+        {
+            return
+        }
+        if is_first && !comment.contains(char::is_whitespace) && comment.to_lowercase().ends_with(".scl")
+        {
+            self.file_name = Some(comment.to_string());
+            return
+        }
+
+        let field = comment.split_once(":")
+            .and_then(|(key, value)| match key.trim().to_lowercase().as_str()
+            {
+                "author" => Some(&mut self.author),
+                "source" => Some(&mut self.source),
+                "date" => Some(&mut self.date),
+                _ => None
+            }.map(|field| (field, value.trim())));
+        match field
+        {
+            Some((field, value)) if field.is_none() => *field = Some(value.to_string()),
+            _ => self.comments.push(comment.to_string())
+        }
+    }
+
+    /// Writes the metadata as comment lines.
+    pub(crate) fn write_comments(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result
+    {
+        for (key, value) in [("Author", &self.author), ("Source", &self.source), ("Date", &self.date)]
+        {
+            if let Some(value) = value
+            {
+                writeln!(f, "! {}: {}", key, value)?;
+            }
+        }
+        for comment in self.comments.iter()
+        {
+            writeln!(f, "! {}", comment)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{Scale, ScaleMetadata};
+
+    #[test]
+    fn comment_metadata()
+    {
+        let text = "! fokker.scl\n!\n! Author: Adriaan Fokker\n! date: 1955\n! 31-tone organ\nFokker\n1\n!\n2/1\n";
+        let scale: Scale = text.parse().unwrap();
+
+        assert_eq!(scale.metadata, ScaleMetadata {
+            file_name: Some("fokker.scl".to_string()),
+            author: Some("Adriaan Fokker".to_string()),
+            source: None,
+            date: Some("1955".to_string()),
+            comments: vec!["31-tone organ".to_string()]
+        });
+        assert_eq!(scale.to_string().parse::<Scale>().unwrap().metadata, scale.metadata);
+    }
+}
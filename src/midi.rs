@@ -0,0 +1,47 @@
+use crate::Pitch;
+
+impl Pitch
+{
+    /// Splits this pitch, taken above the MIDI note `reference`, into the nearest 12edo note
+    /// and the deviation from it in cents, which lies within ±50.
+    ///
+    /// The note may fall outside the MIDI range.
+    ///
+    /// ```
+    /// use serde_scala::Pitch;
+    ///
+    /// let (note, deviation) = Pitch::ratio(5, 4).to_midi_note_cents(60);
+    /// assert_eq!(note, 64);
+    /// assert!((deviation + 13.686).abs() < 1e-3);
+    /// ```
+    pub fn to_midi_note_cents(self, reference: u8) -> (i32, f64)
+    {
+        let cents = self.to_cents();
+        let steps = (cents/100.0).round();
+        (reference as i32 + steps as i32, cents - steps*100.0)
+    }
+
+    /// The inverse of [`Pitch::to_midi_note_cents`], in cents above the MIDI note `reference`.
+    pub fn from_midi_note_cents(note: i32, deviation: f64, reference: u8) -> Self
+    {
+        Self::Cents((note - reference as i32) as f64*100.0 + deviation)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::Pitch;
+
+    #[test]
+    fn midi_note_cents()
+    {
+        assert_eq!(Pitch::ratio(2, 1).to_midi_note_cents(69), (81, 0.0));
+        assert_eq!(Pitch::cents(-1250.0).to_midi_note_cents(0), (-13, 50.0));
+
+        let (note, deviation) = Pitch::ratio(7, 4).to_midi_note_cents(60);
+        assert_eq!(note, 70);
+        assert!((deviation + 31.174).abs() < 1e-3);
+        assert!((Pitch::from_midi_note_cents(note, deviation, 60).to_cents() - Pitch::ratio(7, 4).to_cents()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{ReferencePitch, Tuning, MIDI_KEYS};
+
+/// The attribute type of a MIDI 2.0 note-on giving the note's pitch in 7.9 fixed point.
+pub const MIDI2_PITCH_ATTRIBUTE: u8 = 0x03;
+
+/// A fractional MIDI note in 7.25 fixed point, the format of the MIDI 2.0 per-note pitch controller,
+/// or `None` outside notes 0 to 128.
+pub fn pitch_7_25(note: f64) -> Option<u32>
+{
+    fixed_point(note, 25).and_then(|value| u32::try_from(value).ok())
+}
+
+/// A fractional MIDI note in 7.9 fixed point, the format of the MIDI 2.0 note-on pitch attribute,
+/// or `None` outside notes 0 to 128.
+pub fn pitch_7_9(note: f64) -> Option<u16>
+{
+    fixed_point(note, 9).and_then(|value| u16::try_from(value).ok())
+}
+
+fn fixed_point(note: f64, fraction_bits: i32) -> Option<u64>
+{
+    let value = (note*2f64.powi(fraction_bits)).round();
+    (value >= 0.0 && value < 128.0*2f64.powi(fraction_bits)).then_some(value as u64)
+}
+
+impl ReferencePitch
+{
+    /// The fractional 12-EDO MIDI note a frequency sounds at.
+    pub fn midi_note(&self, frequency: f64) -> f64
+    {
+        self.note as f64 + 12.0*(frequency/self.frequency).log2()
+    }
+}
+
+impl Tuning
+{
+    /// The pitch of a key for the MIDI 2.0 per-note pitch controller, in 7.25 fixed point against A440.
+    ///
+    /// `None` where there is no frequency, and for frequencies outside the MIDI range.
+    ///
+    /// ```
+    /// use serde_scala::{KeyboardMapping, Scale, Tuning};
+    ///
+    /// let tuning = Tuning::new(Scale::edo(24), KeyboardMapping::default());
+    /// assert_eq!(tuning.midi2_pitch(70), Some((69 << 25) + (1 << 24)));
+    /// ```
+    pub fn midi2_pitch(&self, key: u8) -> Option<u32>
+    {
+        pitch_7_25(ReferencePitch::A440.midi_note(self.frequency(key)?))
+    }
+
+    pub fn midi2_pitches(&self) -> [Option<u32>; MIDI_KEYS]
+    {
+        std::array::from_fn(|key| self.midi2_pitch(key as u8))
+    }
+
+    /// The attribute type and data to send with a MIDI 2.0 note-on for a key, giving its pitch in 7.9 fixed point
+    /// against A440.
+    pub fn midi2_note_on_attribute(&self, key: u8) -> Option<(u8, u16)>
+    {
+        let pitch = pitch_7_9(ReferencePitch::A440.midi_note(self.frequency(key)?))?;
+        Some((MIDI2_PITCH_ATTRIBUTE, pitch))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{kbm, pitch_7_25, pitch_7_9, scl, Tuning, MIDI2_PITCH_ATTRIBUTE};
+
+    #[test]
+    fn midi2_pitches()
+    {
+        assert_eq!(pitch_7_25(60.5), Some((60 << 25) | (1 << 24)));
+        assert_eq!(pitch_7_25(128.0), None);
+        assert_eq!(pitch_7_25(-0.1), None);
+        assert_eq!(pitch_7_9(64.25), Some((64 << 9) | 128));
+
+        let tuning = Tuning::new(
+            scl!{"ji" 9/8 5/4 2/1},
+            kbm!{3 0 127 69 69 440.0 3 0 1 2}
+        );
+        let pitches = tuning.midi2_pitches();
+        assert_eq!(pitches[69], Some(69 << 25));
+        let third = pitches[71].unwrap() as f64/(1u32 << 25) as f64;
+        assert!((third - 69.0 - 3.863137).abs() < 1e-6);
+        assert_eq!(tuning.midi2_note_on_attribute(72), Some((MIDI2_PITCH_ATTRIBUTE, 81 << 9)));
+    }
+}
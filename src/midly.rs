@@ -0,0 +1,52 @@
+use midly::num::u7;
+
+use crate::TuningDump;
+
+impl TuningDump
+{
+    /// The data of a live system exclusive message, to send as
+    /// `LiveEvent::Common(SystemCommon::SysEx(&data))`.
+    ///
+    /// ```
+    /// use midly::live::{LiveEvent, SystemCommon};
+    /// use serde_scala::{KeyboardMapping, Scale, Tuning};
+    ///
+    /// let data = Tuning::new(Scale::edo(19), KeyboardMapping::default()).tuning_dump(0).live_sysex();
+    /// let event = LiveEvent::Common(SystemCommon::SysEx(&data));
+    /// ```
+    pub fn live_sysex(&self) -> Vec<u7>
+    {
+        self.sysex_data().into_iter().map(u7::new).collect()
+    }
+
+    /// The data of a system exclusive event in a MIDI file, to write as `TrackEventKind::SysEx(&data)`.
+    pub fn track_sysex(&self) -> Vec<u8>
+    {
+        let mut data = self.sysex_data();
+        data.push(0xf7);
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use midly::{live::{LiveEvent, SystemCommon}, TrackEventKind};
+
+    use crate::{KeyboardMapping, Scale, Tuning};
+
+    #[test]
+    fn midly_sysex()
+    {
+        let dump = Tuning::new(Scale::edo(31), KeyboardMapping::default()).tuning_dump(5);
+        let sysex = dump.to_sysex();
+
+        let data = dump.live_sysex();
+        let mut bytes = vec![];
+        LiveEvent::Common(SystemCommon::SysEx(&data)).write_std(&mut bytes).unwrap();
+        assert_eq!(bytes, sysex);
+
+        let data = dump.track_sysex();
+        assert_eq!(TrackEventKind::SysEx(&data).as_live_event(), LiveEvent::parse(&sysex).ok());
+    }
+}
@@ -0,0 +1,222 @@
+use std::{fmt::Display, ops::{Add, Mul, Neg, Sub}};
+
+use num_rational::Ratio;
+
+use crate::Pitch;
+
+/// A ratio written as a vector of prime exponents, e.g. `[-4 4 -1>` for the syntonic comma 81/80 in the 5-limit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Monzo
+{
+    pub primes: Vec<u64>,
+    pub exponents: Vec<i32>
+}
+
+impl Monzo
+{
+    pub fn new(primes: Vec<u64>, exponents: Vec<i32>) -> Self
+    {
+        assert_eq!(primes.len(), exponents.len(), "a monzo needs exactly one exponent per prime");
+        Self {
+            primes,
+            exponents
+        }
+    }
+
+    pub fn exponent(&self, prime: u64) -> i32
+    {
+        self.primes.iter()
+            .position(|&p| p == prime)
+            .map(|i| self.exponents[i])
+            .unwrap_or(0)
+    }
+
+    pub fn is_unison(&self) -> bool
+    {
+        self.exponents.iter().all(|&exponent| exponent == 0)
+    }
+
+    pub fn to_cents(&self) -> f64
+    {
+        self.primes.iter()
+            .zip(self.exponents.iter())
+            .map(|(&prime, &exponent)| (prime as f64).log2()*exponent as f64)
+            .sum::<f64>()*1200.0
+    }
+
+    /// Converts back into a ratio, falling back to cents if the ratio doesn't fit in a `u128`.
+    pub fn to_pitch(&self) -> Pitch
+    {
+        let mut numer: u128 = 1;
+        let mut denom: u128 = 1;
+        for (&prime, &exponent) in self.primes.iter().zip(self.exponents.iter())
+        {
+            let side = if exponent >= 0
+            {
+                &mut numer
+            }
+            else
+            {
+                &mut denom
+            };
+            match (prime as u128).checked_pow(exponent.unsigned_abs()).and_then(|power| side.checked_mul(power))
+            {
+                Some(product) => *side = product,
+                None => return Pitch::Cents(self.to_cents())
+            }
+        }
+        Pitch::Ratio(Ratio::new(numer, denom))
+    }
+
+    fn zip_with(&self, rhs: &Monzo, f: impl Fn(i32, i32) -> i32) -> Monzo
+    {
+        let mut primes: Vec<u64> = self.primes.iter().chain(rhs.primes.iter()).copied().collect();
+        primes.sort_unstable();
+        primes.dedup();
+        let exponents = primes.iter()
+            .map(|&prime| f(self.exponent(prime), rhs.exponent(prime)))
+            .collect();
+        Monzo::new(primes, exponents)
+    }
+}
+
+impl Pitch
+{
+    /// Factors a ratio over the given primes, or returns `None` for cents, for zero or if some other prime is involved.
+    pub fn to_monzo(self, primes: &[u64]) -> Option<Monzo>
+    {
+        let Self::Ratio(ratio) = self
+        else
+        {
+            return None
+        };
+        let mut numer = *ratio.numer();
+        let mut denom = *ratio.denom();
+        if numer == 0
+        {
+            return None
+        }
+        let exponents = primes.iter()
+            .map(|&prime| {
+                let prime = prime as u128;
+                let mut exponent = 0;
+                if prime >= 2
+                {
+                    while numer.is_multiple_of(prime)
+                    {
+                        numer /= prime;
+                        exponent += 1;
+                    }
+                    while denom.is_multiple_of(prime)
+                    {
+                        denom /= prime;
+                        exponent -= 1;
+                    }
+                }
+                exponent
+            })
+            .collect();
+        if numer != 1 || denom != 1
+        {
+            return None
+        }
+        Some(Monzo::new(primes.to_vec(), exponents))
+    }
+}
+
+impl Add for &Monzo
+{
+    type Output = Monzo;
+
+    fn add(self, rhs: &Monzo) -> Monzo
+    {
+        self.zip_with(rhs, |a, b| a + b)
+    }
+}
+impl Add for Monzo
+{
+    type Output = Monzo;
+
+    fn add(self, rhs: Monzo) -> Monzo
+    {
+        &self + &rhs
+    }
+}
+impl Sub for &Monzo
+{
+    type Output = Monzo;
+
+    fn sub(self, rhs: &Monzo) -> Monzo
+    {
+        self.zip_with(rhs, |a, b| a - b)
+    }
+}
+impl Sub for Monzo
+{
+    type Output = Monzo;
+
+    fn sub(self, rhs: Monzo) -> Monzo
+    {
+        &self - &rhs
+    }
+}
+impl Neg for Monzo
+{
+    type Output = Monzo;
+
+    fn neg(self) -> Monzo
+    {
+        Monzo::new(self.primes, self.exponents.into_iter().map(|exponent| -exponent).collect())
+    }
+}
+impl Mul<i32> for Monzo
+{
+    type Output = Monzo;
+
+    fn mul(self, rhs: i32) -> Monzo
+    {
+        Monzo::new(self.primes, self.exponents.into_iter().map(|exponent| exponent*rhs).collect())
+    }
+}
+
+impl Display for Monzo
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "[")?;
+        for (i, exponent) in self.exponents.iter().enumerate()
+        {
+            if i > 0
+            {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", exponent)?;
+        }
+        write!(f, ">")
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use num_rational::Ratio;
+
+    use crate::{Monzo, Pitch};
+
+    #[test]
+    fn comma_arithmetic()
+    {
+        let primes = [2, 3, 5];
+        let fifth = Pitch::Ratio(Ratio::new(3, 2)).to_monzo(&primes).unwrap();
+        let third = Pitch::Ratio(Ratio::new(5, 4)).to_monzo(&primes).unwrap();
+
+        let syntonic = fifth*4 - third - Monzo::new(vec![2], vec![2]);
+        assert_eq!(syntonic.to_string(), "[-4 4 -1>");
+        assert_eq!(syntonic.to_pitch(), Pitch::Ratio(Ratio::new(81, 80)));
+        assert!((syntonic.to_cents() - 21.506).abs() < 1e-3);
+
+        assert_eq!(Pitch::Ratio(Ratio::new(7, 4)).to_monzo(&primes), None);
+        assert_eq!(Pitch::Cents(700.0).to_monzo(&primes), None);
+        assert_eq!("0/1".parse::<Pitch>().unwrap().to_monzo(&primes), None);
+    }
+}
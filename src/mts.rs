@@ -0,0 +1,113 @@
+use crate::{FrequencyTable, ReferencePitch, Tuning};
+
+/// The device ID addressing every device listening.
+pub const MTS_ALL_DEVICES: u8 = 0x7f;
+
+/// Encodes a frequency the way the MIDI Tuning Standard does, as a 12-EDO MIDI note against A440 and
+/// how far above it the frequency lies in 14-bit fractions of a semitone.
+///
+/// Frequencies outside the MIDI range are clamped to it. `7f 7f 7f` is left for keys not to retune.
+pub fn mts_frequency(frequency: f64) -> [u8; 3]
+{
+    let max = 127.0 + 16382.0/16384.0;
+    let note = ReferencePitch::A440.midi_note(frequency);
+    let steps = (note.clamp(0.0, max)*16384.0).round() as u32;
+    [(steps >> 14) as u8, ((steps >> 7) & 0x7f) as u8, (steps & 0x7f) as u8]
+}
+
+/// A bulk tuning dump of the MIDI Tuning Standard, retuning every key of a tuning program at once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TuningDump
+{
+    pub device: u8,
+    /// Sent as a key-based tuning dump if set, the kind that has a bank.
+    pub bank: Option<u8>,
+    pub program: u8,
+    /// Sent as the first 16 ASCII characters.
+    pub name: String,
+    pub frequencies: FrequencyTable
+}
+
+impl TuningDump
+{
+    /// The message without its leading `f0` and trailing `f7`.
+    pub fn sysex_data(&self) -> Vec<u8>
+    {
+        let mut data = vec![0x7e, self.device & 0x7f, 0x08];
+        match self.bank
+        {
+            Some(bank) => data.extend([0x04, bank & 0x7f]),
+            None => data.push(0x01)
+        }
+        data.push(self.program & 0x7f);
+        let name = self.name.chars()
+            .map(|c| if c.is_ascii() && !c.is_ascii_control() { c as u8 } else { b'?' })
+            .chain(std::iter::repeat(b' '))
+            .take(16);
+        data.extend(name);
+        for frequency in self.frequencies.frequencies
+        {
+            data.extend(frequency.map(mts_frequency).unwrap_or([0x7f; 3]));
+        }
+        let checksum = data.iter().fold(0, |checksum, byte| checksum ^ byte) & 0x7f;
+        data.push(checksum);
+        data
+    }
+
+    /// The whole system exclusive message, `f0` to `f7`.
+    pub fn to_sysex(&self) -> Vec<u8>
+    {
+        let mut sysex = vec![0xf0];
+        sysex.extend(self.sysex_data());
+        sysex.push(0xf7);
+        sysex
+    }
+}
+
+/// The registered parameter messages selecting a tuning bank and then a tuning program on a channel.
+pub fn mts_tuning_select(channel: u8, bank: u8, program: u8) -> Vec<u8>
+{
+    let status = 0xb0 | (channel & 0x0f);
+    [(0x04, bank), (0x03, program)].into_iter()
+        .flat_map(|(parameter, value)| [status, 0x64, parameter, status, 0x65, 0x00, status, 0x06, value & 0x7f])
+        .collect()
+}
+
+impl Tuning
+{
+    /// A bulk dump to every device of this tuning as a tuning program, leaving untuned keys as they are.
+    pub fn tuning_dump(&self, program: u8) -> TuningDump
+    {
+        TuningDump {
+            device: MTS_ALL_DEVICES,
+            bank: None,
+            program,
+            name: self.scale.name.clone(),
+            frequencies: self.frequencies()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{mts_frequency, KeyboardMapping, Scale, Tuning};
+
+    #[test]
+    fn bulk_dump()
+    {
+        assert_eq!(mts_frequency(440.0), [69, 0, 0]);
+        assert_eq!(mts_frequency(440.0*2f64.powf(0.5/12.0)), [69, 0x40, 0]);
+        assert_eq!(mts_frequency(1e9), [127, 0x7f, 0x7e]);
+
+        let mapping = KeyboardMapping::new(0, 0, 100, 60, 69, 440.0, 0, vec![]);
+        let sysex = Tuning::new(Scale::edo(24), mapping).tuning_dump(3).to_sysex();
+        assert_eq!(sysex.len(), 408);
+        assert_eq!(sysex[..6], [0xf0, 0x7e, 0x7f, 0x08, 0x01, 3]);
+        assert_eq!(&sysex[6..22], b"24-note equal di");
+        assert_eq!(sysex[22 + 3*70..22 + 3*71], [69, 0x40, 0]);
+        assert_eq!(sysex[22 + 3*101..22 + 3*102], [0x7f; 3]);
+        assert_eq!(sysex[406], sysex[1..406].iter().fold(0, |checksum, byte| checksum ^ byte) & 0x7f);
+        assert_eq!(sysex[407], 0xf7);
+    }
+}
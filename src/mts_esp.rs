@@ -0,0 +1,103 @@
+use std::{error::Error, ffi::{c_char, c_double, c_schar, CString}, fmt::Display};
+
+use crate::{ReferencePitch, Tuning, MIDI_KEYS};
+
+// The master side of the MTS-ESP SDK, built from `libMTSMaster.cpp` and linked as `MTSMaster`.
+#[link(name = "MTSMaster")]
+extern "C"
+{
+    fn MTS_CanRegisterMaster() -> bool;
+    fn MTS_RegisterMaster();
+    fn MTS_DeregisterMaster();
+    fn MTS_GetNumClients() -> i32;
+    fn MTS_SetNoteTunings(frequencies: *const c_double);
+    fn MTS_SetScaleName(name: *const c_char);
+    // The header's `char` is signed here, as a channel of -1 means every channel.
+    fn MTS_FilterNote(filter: bool, note: c_schar, channel: c_schar);
+    fn MTS_ClearNoteFilter();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MtsEspError
+{
+    /// Another master is already registered, or the MTS-ESP library isn't installed.
+    CannotRegister
+}
+impl Display for MtsEspError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Self::CannotRegister => write!(f, "cannot register as MTS-ESP master")
+        }
+    }
+}
+impl Error for MtsEspError {}
+
+/// This process registered as the MTS-ESP master, broadcasting tunings to every connected plugin until dropped.
+#[derive(Debug)]
+pub struct MtsEspMaster
+{
+    _private: ()
+}
+
+impl MtsEspMaster
+{
+    pub fn register() -> Result<Self, MtsEspError>
+    {
+        // SAFETY: the SDK functions take no pointers, and registration is checked before it's made.
+        unsafe {
+            if !MTS_CanRegisterMaster()
+            {
+                return Err(MtsEspError::CannotRegister)
+            }
+            MTS_RegisterMaster();
+        }
+        Ok(Self {
+            _private: ()
+        })
+    }
+
+    /// How many plugins are listening.
+    pub fn clients(&self) -> usize
+    {
+        // SAFETY: takes no arguments.
+        unsafe { MTS_GetNumClients() }.max(0) as usize
+    }
+
+    /// Sends the frequency of every key and the scale name to all clients.
+    ///
+    /// Keys left untuned keep their 12-EDO frequency at A440 and are filtered, so clients don't play them.
+    pub fn set_tuning(&self, tuning: &Tuning)
+    {
+        let table = tuning.frequencies();
+        let frequencies: [c_double; MIDI_KEYS] = std::array::from_fn(|key| table.frequencies[key]
+            .unwrap_or_else(|| ReferencePitch::A440.note_frequency(key as f64)));
+        let name = CString::new(tuning.scale.name.replace('\0', "")).unwrap_or_default();
+
+        // SAFETY: the SDK copies all 128 frequencies and the name before returning.
+        unsafe {
+            MTS_SetNoteTunings(frequencies.as_ptr());
+            MTS_SetScaleName(name.as_ptr());
+            MTS_ClearNoteFilter();
+            for (key, frequency) in table.frequencies.iter().enumerate()
+            {
+                if frequency.is_none()
+                {
+                    // Channel -1 filters the key on every channel.
+                    MTS_FilterNote(true, key as c_schar, -1);
+                }
+            }
+        }
+    }
+}
+
+impl Drop for MtsEspMaster
+{
+    fn drop(&mut self)
+    {
+        // SAFETY: only a registered master is ever dropped.
+        unsafe { MTS_DeregisterMaster() }
+    }
+}
@@ -0,0 +1,284 @@
+use std::{error::Error, fmt::Display, num::ParseIntError};
+
+use crate::{prime::prime_factors, Monzo, Pitch};
+
+/// The radius of tolerance, 65/63 in cents, within which a prime's formal comma must lie.
+fn radius_of_tolerance() -> f64
+{
+    1200.0*(65.0f64/63.0).log2()
+}
+
+/// The FJS formal comma of a prime above 3: the prime over the nearest 3-limit interval,
+/// searching fifths in the order 0, 1, -1, 2, -2, ...
+fn formal_comma(prime: u64) -> Monzo
+{
+    let log3 = 3f64.log2();
+    let log_prime = (prime as f64).log2();
+    let fifths = (0i32..).flat_map(|k| [k, -k])
+        .skip(1)
+        .find(|&k| {
+            let octaves = log_prime - k as f64*log3;
+            (octaves - octaves.round()).abs()*1200.0 < radius_of_tolerance()
+        })
+        .unwrap_or(0);
+    let octaves = (log_prime - fifths as f64*log3).round() as i32;
+    Monzo::new(vec![2, 3, prime], vec![-octaves, -fifths, 1])
+}
+
+/// The diatonic steps spanned by a 3-limit interval of `fifths` fifths and `octaves` octaves.
+fn steps(fifths: i32, octaves: i32) -> i32
+{
+    11*fifths + 7*octaves
+}
+
+/// How many fifths the major or perfect interval of each diatonic step class spans.
+const BASE_FIFTHS: [i32; 7] = [0, 2, 4, -1, 1, 3, 5];
+
+fn is_perfect(step_class: i32) -> bool
+{
+    matches!(step_class, 0 | 3 | 4)
+}
+
+fn quality(fifths: i32) -> String
+{
+    match fifths
+    {
+        -1..=1 => "P".to_string(),
+        2..=5 => "M".to_string(),
+        -5..=-2 => "m".to_string(),
+        6.. => "A".repeat(((fifths + 1)/7) as usize),
+        _ => "d".repeat(((1 - fifths)/7) as usize)
+    }
+}
+
+fn accidental(monzo: &Monzo, otonal: bool) -> Option<u128>
+{
+    monzo.primes.iter()
+        .zip(monzo.exponents.iter())
+        .filter(|&(&prime, &exponent)| prime > 3 && (exponent > 0) == otonal)
+        .try_fold(1u128, |product, (&prime, &exponent)| product.checked_mul((prime as u128).checked_pow(exponent.unsigned_abs())?))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseFjsError
+{
+    ParseInt(ParseIntError),
+    /// The quality is unknown, or doesn't apply to the degree, like a major fifth.
+    InvalidQuality,
+    /// There is no degree after the quality, or it is zero.
+    InvalidDegree,
+    /// An accidental is zero or has a factor of 2 or 3.
+    InvalidAccidental(u128),
+    /// The ratio doesn't fit in a `u128`.
+    Overflow
+}
+impl From<ParseIntError> for ParseFjsError
+{
+    fn from(value: ParseIntError) -> Self
+    {
+        Self::ParseInt(value)
+    }
+}
+impl Display for ParseFjsError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Self::ParseInt(err) => write!(f, "invalid number: {}", err),
+            Self::InvalidQuality => write!(f, "invalid interval quality"),
+            Self::InvalidDegree => write!(f, "missing or zero interval degree"),
+            Self::InvalidAccidental(accidental) => write!(f, "invalid accidental {}", accidental),
+            Self::Overflow => write!(f, "ratio does not fit in a u128")
+        }
+    }
+}
+impl Error for ParseFjsError
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)>
+    {
+        match self
+        {
+            Self::ParseInt(err) => Some(err),
+            _ => None
+        }
+    }
+}
+
+impl Pitch
+{
+    /// The name of a ratio in the Functional Just System, like `M3^5` for 5/4 or `m3_5` for 6/5.
+    ///
+    /// Otonal accidentals follow `^` and utonal ones `_`, each as the product of their primes.
+    /// Descending intervals are prefixed with `-`. Returns `None` for cents,
+    /// or if a prime or accidental is too large.
+    ///
+    /// ```
+    /// use serde_scala::Pitch;
+    ///
+    /// assert_eq!(Pitch::ratio(7, 4).fjs_name().as_deref(), Some("m7^7"));
+    /// assert_eq!(Pitch::from_fjs("M3^5"), Ok(Pitch::ratio(5, 4)));
+    /// ```
+    pub fn fjs_name(self) -> Option<String>
+    {
+        let Self::Ratio(ratio) = self
+        else
+        {
+            return None
+        };
+        let mut primes: Vec<u64> = prime_factors(*ratio.numer())?.into_iter()
+            .chain(prime_factors(*ratio.denom())?)
+            .chain([2, 3])
+            .map(u64::try_from)
+            .collect::<Result<_, _>>()
+            .ok()?;
+        primes.sort_unstable();
+        primes.dedup();
+        let mut monzo = self.to_monzo(&primes)?;
+
+        let pythagorean = |monzo: &Monzo| monzo.primes.iter()
+            .zip(monzo.exponents.iter())
+            .filter(|&(&prime, _)| prime > 3)
+            .fold(monzo.clone(), |pythagorean, (&prime, &exponent)| pythagorean - formal_comma(prime)*exponent);
+        let mut py = pythagorean(&monzo);
+        let mut name = String::new();
+        if steps(py.exponent(3), py.exponent(2)) < 0
+        {
+            name.push('-');
+            monzo = -monzo;
+            py = pythagorean(&monzo);
+        }
+
+        let fifths = py.exponent(3);
+        name += &quality(fifths);
+        name += &(steps(fifths, py.exponent(2)) + 1).to_string();
+        for (symbol, otonal) in [('^', true), ('_', false)]
+        {
+            let accidental = accidental(&monzo, otonal)?;
+            if accidental > 1
+            {
+                name.push(symbol);
+                name += &accidental.to_string();
+            }
+        }
+        Some(name)
+    }
+
+    /// Reads a ratio in the notation of [`Pitch::fjs_name`].
+    pub fn from_fjs(s: &str) -> Result<Self, ParseFjsError>
+    {
+        let s = s.trim();
+        let (descending, s) = match s.strip_prefix('-')
+        {
+            Some(s) => (true, s),
+            None => (false, s)
+        };
+        let degree_start = s.find(|c: char| c.is_ascii_digit()).ok_or(ParseFjsError::InvalidDegree)?;
+        let (quality, rest) = s.split_at(degree_start);
+        let degree_end = rest.find(['^', '_']).unwrap_or(rest.len());
+        let (degree, mut accidentals) = rest.split_at(degree_end);
+        let degree: i32 = degree.parse()?;
+        if degree == 0
+        {
+            return Err(ParseFjsError::InvalidDegree)
+        }
+
+        let step = degree - 1;
+        let step_class = step.rem_euclid(7);
+        let base = BASE_FIFTHS[step_class as usize];
+        let count = quality.len() as i32;
+        let fifths = match quality.chars().next()
+        {
+            Some('P') if count == 1 && is_perfect(step_class) => base,
+            Some('M') if count == 1 && !is_perfect(step_class) => base,
+            Some('m') if count == 1 && !is_perfect(step_class) => base - 7,
+            Some('A') if quality.chars().all(|c| c == 'A') => base + 7*count,
+            Some('d') if quality.chars().all(|c| c == 'd') => base - 7*(count + !is_perfect(step_class) as i32),
+            _ => return Err(ParseFjsError::InvalidQuality)
+        };
+        let octaves = (step - 11*fifths)/7;
+        let mut monzo = Monzo::new(vec![2, 3], vec![octaves, fifths]);
+
+        while let Some(symbol) = accidentals.chars().next()
+        {
+            let rest = &accidentals[1..];
+            let end = rest.find(['^', '_']).unwrap_or(rest.len());
+            let accidental: u128 = rest[..end].parse()?;
+            accidentals = &rest[end..];
+            if accidental == 0
+            {
+                return Err(ParseFjsError::InvalidAccidental(accidental))
+            }
+
+            let mut remaining = accidental;
+            let Some(primes) = prime_factors(accidental)
+            else
+            {
+                return Err(ParseFjsError::InvalidAccidental(accidental))
+            };
+            for prime in primes
+            {
+                let comma = match u64::try_from(prime)
+                {
+                    Ok(prime) if prime > 3 => formal_comma(prime),
+                    _ => return Err(ParseFjsError::InvalidAccidental(accidental))
+                };
+                while remaining.is_multiple_of(prime)
+                {
+                    remaining /= prime;
+                    monzo = monzo + comma.clone()*if symbol == '^' { 1 } else { -1 };
+                }
+            }
+        }
+
+        if descending
+        {
+            monzo = -monzo;
+        }
+        match monzo.to_pitch()
+        {
+            pitch @ Self::Ratio(_) => Ok(pitch),
+            Self::Cents(_) => Err(ParseFjsError::Overflow)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{ParseFjsError, Pitch};
+
+    #[test]
+    fn fjs_names()
+    {
+        let names = [
+            ((1, 1), "P1"),
+            ((3, 2), "P5"),
+            ((9, 8), "M2"),
+            ((5, 4), "M3^5"),
+            ((6, 5), "m3_5"),
+            ((7, 4), "m7^7"),
+            ((11, 8), "P4^11"),
+            ((13, 8), "m6^13"),
+            ((25, 24), "A1^25"),
+            ((15, 14), "A1^5_7"),
+            ((2187, 2048), "A1"),
+            ((4, 5), "-M3^5"),
+            ((2, 1), "P8"),
+            ((9, 4), "M9")
+        ];
+        for ((numer, denom), name) in names
+        {
+            let pitch = Pitch::ratio(numer, denom);
+            assert_eq!(pitch.fjs_name().as_deref(), Some(name));
+            assert_eq!(Pitch::from_fjs(name), Ok(pitch));
+        }
+        assert_eq!(Pitch::ratio(0, 1).fjs_name(), None);
+
+        assert_eq!(Pitch::cents(700.0).fjs_name(), None);
+        assert_eq!(Pitch::from_fjs("M5"), Err(ParseFjsError::InvalidQuality));
+        assert_eq!(Pitch::from_fjs("P0"), Err(ParseFjsError::InvalidDegree));
+        assert_eq!(Pitch::from_fjs("M3^6"), Err(ParseFjsError::InvalidAccidental(6)));
+        assert_eq!(Pitch::from_fjs("dd5"), Ok(Pitch::ratio(2097152, 1594323)));
+    }
+}
@@ -0,0 +1,186 @@
+use std::fmt::Display;
+
+use crate::{prime::prime_factors, Monzo, Pitch, Scale};
+
+/// The primes Helmholtz-Ellis notation has accidentals for, each with the comma its accidental inflects a
+/// Pythagorean note by, as exponents of 2 and 3, and its name.
+const HEJI_COMMAS: [(u64, [i32; 2], &str); 7] = [
+    (5, [4, -4], "syntonic comma"),
+    (7, [-6, 2], "septimal comma"),
+    (11, [-5, 1], "undecimal quartertone"),
+    (13, [1, -3], "tridecimal thirdtone"),
+    (17, [7, -7], "17-limit schisma"),
+    (19, [-9, 3], "19-limit schisma"),
+    (23, [5, -6], "23-limit comma")
+];
+
+fn heji_comma(prime: u64) -> Option<(Monzo, &'static str)>
+{
+    HEJI_COMMAS.iter()
+        .find(|&&(p, _, _)| p == prime)
+        .map(|&(p, [twos, threes], name)| (Monzo::new(vec![2, 3, p], vec![twos, threes, 1]), name))
+}
+
+/// A Helmholtz-Ellis accidental applied some number of times, raising if `count` is positive and lowering if negative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HejiInflection
+{
+    pub prime: u64,
+    pub count: i32
+}
+
+impl Display for HejiInflection
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let name = heji_comma(self.prime).map(|(_, name)| name).unwrap_or("comma");
+        let plural = if self.count.abs() == 1 { "" } else { "s" };
+        let direction = if self.count > 0 { "up" } else { "down" };
+        write!(f, "{} {}{} {}", self.count.abs(), name, plural, direction)
+    }
+}
+
+/// A ratio spelled as a Pythagorean note inflected by Helmholtz-Ellis accidentals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HejiSpelling
+{
+    /// The Pythagorean note, like `E♭`.
+    pub nominal: String,
+    pub inflections: Vec<HejiInflection>
+}
+
+/// Like `E, 1 syntonic comma down`.
+impl Display for HejiSpelling
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{}", self.nominal)?;
+        for inflection in &self.inflections
+        {
+            write!(f, ", {}", inflection)?;
+        }
+        Ok(())
+    }
+}
+
+fn nominal(fifths: i32) -> String
+{
+    let letter = "FCGDAEB".as_bytes()[(fifths + 1).rem_euclid(7) as usize] as char;
+    let sharps = (fifths + 1).div_euclid(7);
+    let accidental = if sharps > 0 { "♯" } else { "♭" };
+    format!("{}{}", letter, accidental.repeat(sharps.unsigned_abs() as usize))
+}
+
+impl Pitch
+{
+    /// Spells a ratio in Helmholtz-Ellis notation above a tonic, given as a number of fifths from C,
+    /// like 2 for D or -3 for E♭.
+    ///
+    /// Returns `None` for cents, for zero, and for ratios with primes above 23.
+    ///
+    /// ```
+    /// use serde_scala::Pitch;
+    ///
+    /// assert_eq!(Pitch::ratio(5, 4).heji(0).unwrap().to_string(), "E, 1 syntonic comma down");
+    /// assert_eq!(Pitch::ratio(7, 6).heji(2).unwrap().to_string(), "F, 1 septimal comma down");
+    /// ```
+    pub fn heji(self, tonic: i32) -> Option<HejiSpelling>
+    {
+        let Self::Ratio(ratio) = self
+        else
+        {
+            return None
+        };
+        if *ratio.numer() == 0
+        {
+            return None
+        }
+        let mut primes: Vec<u64> = prime_factors(*ratio.numer())?.into_iter()
+            .chain(prime_factors(*ratio.denom())?)
+            .map(u64::try_from)
+            .collect::<Result<_, _>>()
+            .ok()?;
+        primes.extend([2, 3]);
+        primes.sort_unstable();
+        primes.dedup();
+        let monzo = self.to_monzo(&primes)?;
+
+        let mut pythagorean = monzo.clone();
+        let mut inflections = vec![];
+        for (&prime, &exponent) in monzo.primes.iter().zip(monzo.exponents.iter())
+        {
+            if prime <= 3 || exponent == 0
+            {
+                continue
+            }
+            let (comma, _) = heji_comma(prime)?;
+            let count = if comma.to_cents() > 0.0 { exponent } else { -exponent };
+            pythagorean = pythagorean - comma*exponent;
+            inflections.push(HejiInflection {
+                prime,
+                count
+            });
+        }
+
+        Some(HejiSpelling {
+            nominal: nominal(tonic + pythagorean.exponent(3)),
+            inflections
+        })
+    }
+}
+
+impl Scale
+{
+    /// The Helmholtz-Ellis spelling of every pitch, see [`Pitch::heji`].
+    pub fn heji(&self, tonic: i32) -> Vec<Option<HejiSpelling>>
+    {
+        self.pitches.iter().map(|pitch| pitch.heji(tonic)).collect()
+    }
+
+    /// A copy labelling every ratio with its Helmholtz-Ellis spelling, to annotate the pitch lines when written.
+    /// Pitches without a spelling keep their label.
+    pub fn with_heji_labels(&self, tonic: i32) -> Scale
+    {
+        let mut scale = self.clone();
+        for (i, spelling) in self.heji(tonic).into_iter().enumerate()
+        {
+            if let Some(spelling) = spelling
+            {
+                scale.set_label(i, spelling.to_string());
+            }
+        }
+        scale
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, Pitch};
+
+    #[test]
+    fn heji_spellings()
+    {
+        let scale = scl!{"ji" 9/8 6/5 11/8 13/8 7/4 2/1 700.0};
+        let spellings: Vec<_> = scale.heji(0).into_iter()
+            .map(|spelling| spelling.map(|spelling| spelling.to_string()))
+            .collect();
+        assert_eq!(spellings, [
+            Some("D".to_string()),
+            Some("E♭, 1 syntonic comma up".to_string()),
+            Some("F, 1 undecimal quartertone up".to_string()),
+            Some("A, 1 tridecimal thirdtone down".to_string()),
+            Some("B♭, 1 septimal comma down".to_string()),
+            Some("C".to_string()),
+            None
+        ]);
+
+        assert_eq!(Pitch::ratio(25, 16).heji(-3).unwrap().to_string(), "B, 2 syntonic commas down");
+        assert_eq!(Pitch::ratio(29, 16).heji(0), None);
+        assert_eq!(Pitch::ratio(0, 1).heji(0), None);
+
+        let labelled = scale.with_heji_labels(0);
+        assert!(labelled.to_string().contains("\n7/4 B♭, 1 septimal comma down\n"));
+        assert_eq!(labelled.label(6), None);
+    }
+}
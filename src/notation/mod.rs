@@ -0,0 +1,7 @@
+mod fjs;
+mod heji;
+mod note_name;
+
+pub use fjs::*;
+pub use heji::*;
+pub use note_name::*;
@@ -0,0 +1,89 @@
+use crate::{Pitch, Scale};
+
+const SHARP_NAMES: [&str; 12] = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+const FLAT_NAMES: [&str; 12] = ["C", "Db", "D", "Eb", "E", "F", "Gb", "G", "Ab", "A", "Bb", "B"];
+
+/// How note names are spelled, by default with ASCII sharps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoteNameOptions
+{
+    /// Use `♯`, `♭` and `¢` instead of `#`, `b` and `c`.
+    pub unicode: bool,
+    /// Spell black keys as flats instead of sharps.
+    pub flats: bool
+}
+
+impl NoteNameOptions
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    pub fn unicode(mut self, unicode: bool) -> Self
+    {
+        self.unicode = unicode;
+        self
+    }
+
+    pub fn flats(mut self, flats: bool) -> Self
+    {
+        self.flats = flats;
+        self
+    }
+}
+
+impl Pitch
+{
+    /// The name of the nearest 12-EDO note, taken above the MIDI note `reference`, and the deviation from it in whole cents,
+    /// like `F#-33c` or `E♭+14¢`. A deviation rounding to zero is left out.
+    ///
+    /// ```
+    /// use serde_scala::{NoteNameOptions, Pitch};
+    ///
+    /// assert_eq!(Pitch::ratio(7, 4).note_name(60, &NoteNameOptions::new()), "A#-31c");
+    /// assert_eq!(Pitch::ratio(6, 5).note_name(60, &NoteNameOptions::new().unicode(true).flats(true)), "E♭+16¢");
+    /// ```
+    pub fn note_name(self, reference: u8, options: &NoteNameOptions) -> String
+    {
+        let (note, deviation) = self.to_midi_note_cents(reference);
+        let names = if options.flats { FLAT_NAMES } else { SHARP_NAMES };
+        let mut name = names[note.rem_euclid(12) as usize].to_string();
+        if options.unicode
+        {
+            name = name.replace('#', "♯").replace('b', "♭");
+        }
+
+        let deviation = deviation.round() as i64;
+        if deviation != 0
+        {
+            let unit = if options.unicode { "¢" } else { "c" };
+            name += &format!("{:+}{}", deviation, unit);
+        }
+        name
+    }
+}
+
+impl Scale
+{
+    /// The note name of every pitch, with the unison at the MIDI note `reference`.
+    pub fn note_names(&self, reference: u8, options: &NoteNameOptions) -> Vec<String>
+    {
+        self.pitches.iter().map(|pitch| pitch.note_name(reference, options)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, NoteNameOptions};
+
+    #[test]
+    fn note_names()
+    {
+        let scale = scl!{"ji" 9/8 5/4 3/2 7/4 2/1};
+
+        assert_eq!(scale.note_names(60, &NoteNameOptions::new()), ["D+4c", "E-14c", "G+2c", "A#-31c", "C"]);
+        assert_eq!(scale.note_names(69, &NoteNameOptions::new().unicode(true).flats(true)), ["B+4¢", "D♭-14¢", "E+2¢", "G-31¢", "A"]);
+    }
+}
@@ -0,0 +1,99 @@
+use std::{fs, path::{Path, PathBuf}};
+
+use rayon::prelude::*;
+
+use crate::{KeyboardMapping, LibraryEntry, Scale, ScaleLibrary, SerdeScalaError};
+
+/// Everything [`ScaleLibrary::load_dir_parallel`] found in a directory.
+#[derive(Debug, Default)]
+pub struct DirectoryLoad
+{
+    pub library: ScaleLibrary,
+    /// The `.kbm` files, sorted by path.
+    pub mappings: Vec<(PathBuf, KeyboardMapping)>,
+    /// Files that couldn't be read or parsed, and why.
+    pub rejected: Vec<(PathBuf, SerdeScalaError)>
+}
+
+enum Loaded
+{
+    Scale(Scale),
+    Mapping(KeyboardMapping)
+}
+
+fn load_file(path: &Path, is_scale: bool) -> Result<Loaded, SerdeScalaError>
+{
+    let bytes = fs::read(path)?;
+    let text = String::from_utf8_lossy(&bytes);
+    if is_scale
+    {
+        Ok(Loaded::Scale(text.parse()?))
+    }
+    else
+    {
+        Ok(Loaded::Mapping(text.parse()?))
+    }
+}
+
+impl ScaleLibrary
+{
+    /// Loads every `.scl` and `.kbm` file in a directory across threads.
+    ///
+    /// Files that fail to load don't stop the others, they are set aside in `rejected`.
+    pub fn load_dir_parallel(path: impl AsRef<Path>) -> Result<DirectoryLoad, SerdeScalaError>
+    {
+        let mut paths = vec![];
+        for entry in fs::read_dir(path)?
+        {
+            let path = entry?.path();
+            let Some(extension) = path.extension()
+            else
+            {
+                continue
+            };
+            let is_scale = extension.eq_ignore_ascii_case("scl");
+            if path.is_file() && (is_scale || extension.eq_ignore_ascii_case("kbm"))
+            {
+                paths.push((path, is_scale))
+            }
+        }
+        paths.sort();
+
+        let results: Vec<_> = paths.into_par_iter()
+            .map(|(path, is_scale)| {
+                let result = load_file(&path, is_scale);
+                (path, result)
+            })
+            .collect();
+
+        let mut load = DirectoryLoad::default();
+        for (path, result) in results
+        {
+            match result
+            {
+                Ok(Loaded::Scale(scale)) => load.library.entries.push(LibraryEntry {
+                    path: Some(path),
+                    scale
+                }),
+                Ok(Loaded::Mapping(mapping)) => load.mappings.push((path, mapping)),
+                Err(error) => load.rejected.push((path, error))
+            }
+        }
+        Ok(load)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::ScaleLibrary;
+
+    #[test]
+    fn matches_sequential_load()
+    {
+        let load = ScaleLibrary::load_dir_parallel("scl").unwrap();
+
+        assert!(load.rejected.is_empty());
+        assert_eq!(load.library, ScaleLibrary::load_dir("scl").unwrap());
+    }
+}
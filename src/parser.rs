@@ -0,0 +1,392 @@
+use std::num::ParseIntError;
+
+use crate::{ParsePitchError, ParseScaleError, Pitch, Scale, ScaleMetadata};
+
+/// The most pitches to make room for upfront by the note count, which a malformed file could give as anything.
+const MAX_RESERVED_PITCHES: usize = 4096;
+
+/// Splits text into lines at LF, CRLF and bare CR line endings, skipping a leading byte order mark.
+pub(crate) fn lines(s: &str) -> impl Iterator<Item = &str>
+{
+    s.strip_prefix('\u{feff}')
+        .unwrap_or(s)
+        .lines()
+        .flat_map(|line| line.split('\r'))
+}
+
+/// The longest number that spaces are skipped within, which is far beyond any written in practice.
+pub(crate) const MAX_SPACED_NUMBER: usize = 64;
+
+/// Copies `s` into `buffer` without the characters matching `skip`, to parse numbers written with spaces inside them
+/// without allocating. Returns `None` if what's left doesn't fit.
+pub(crate) fn without<'a>(s: &str, skip: impl Fn(char) -> bool, buffer: &'a mut [u8; MAX_SPACED_NUMBER]) -> Option<&'a str>
+{
+    let mut len = 0;
+    for c in s.chars().filter(|&c| !skip(c))
+    {
+        let end = len + c.len_utf8();
+        c.encode_utf8(buffer.get_mut(len..end)?);
+        len = end;
+    }
+    std::str::from_utf8(&buffer[..len]).ok()
+}
+
+/// Splits a pitch line into the pitch value and the label following it, if any.
+///
+/// A `cents` unit after a cents value belongs to the value, and so do spaces around the slash of a ratio,
+/// like `3 / 2`.
+fn split_pitch_line(line: &str) -> (&str, Option<&str>)
+{
+    let line = line.trim();
+    let mut end = line.find(char::is_whitespace).unwrap_or(line.len());
+    if line[..end].contains(".")
+    {
+        let rest = line[end..].trim_start();
+        if rest.split(char::is_whitespace).next() == Some("cents")
+        {
+            end = line.len() - rest.len() + "cents".len();
+        }
+    }
+    else
+    {
+        loop
+        {
+            let rest = line[end..].trim_start();
+            if rest.is_empty() || !(line[..end].ends_with('/') || rest.starts_with('/'))
+            {
+                break
+            }
+            let start = line.len() - rest.len();
+            end = start + rest.find(char::is_whitespace).unwrap_or(rest.len());
+        }
+    }
+    let (value, label) = line.split_at(end);
+    let label = label.trim();
+    (value, (!label.is_empty()).then_some(label))
+}
+
+/// Reads a note count line, which may have spaces between its digits.
+fn parse_note_count(s: &str) -> Result<usize, ParseIntError>
+{
+    let s = s.trim();
+    let mut buffer = [0; MAX_SPACED_NUMBER];
+    without(s, char::is_whitespace, &mut buffer).unwrap_or(s).parse()
+}
+
+/// Reads a pitch line into the pitch, the text it was written as if it's in cents, and its label.
+fn parse_pitch_line<'a>(s: &'a str, options: &ParseOptions) -> Result<(Pitch, Option<&'a str>, Option<&'a str>), ParsePitchError>
+{
+    let (text, label) = split_pitch_line(s);
+    let pitch = Pitch::parse_with(text, options)?;
+    Ok((pitch, matches!(pitch, Pitch::Cents(_)).then_some(text), label))
+}
+
+/// Leniency settings for parsing, all off by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseOptions
+{
+    /// Accept a comma as the decimal separator, like `701,955`, for values that would otherwise be invalid.
+    pub decimal_comma: bool
+}
+
+impl ParseOptions
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    pub fn decimal_comma(mut self, decimal_comma: bool) -> Self
+    {
+        self.decimal_comma = decimal_comma;
+        self
+    }
+}
+
+impl Pitch
+{
+    pub fn parse_with(s: &str, options: &ParseOptions) -> Result<Self, ParsePitchError>
+    {
+        match s.parse()
+        {
+            Err(err) if options.decimal_comma && s.matches(",").count() == 1 && !s.contains(['.', '/']) =>
+            {
+                s.replace(",", ".").parse().map_err(|_| err)
+            },
+            result => result
+        }
+    }
+}
+
+/// What a line of a .scl file holds, going by the lines read before it. Made by [`LineReader::read`].
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ScaleLine<'a>
+{
+    /// An empty line, or one with only whitespace before a comment.
+    Blank,
+    /// A whole-line comment, without the `!`, and whether it came before any other line.
+    Comment(&'a str, bool),
+    Description(&'a str),
+    NoteCount(Result<usize, ParseIntError>),
+    /// A pitch, the text it was written as if it's in cents, and its label.
+    Pitch(Pitch, Option<&'a str>, Option<&'a str>)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Section
+{
+    #[default]
+    Description,
+    NoteCount,
+    Pitches
+}
+
+/// Tells the lines of a .scl file apart, which every parser of the format reads its lines through.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct LineReader
+{
+    section: Section,
+    /// Whether any line other than a blank one has been read yet.
+    pub(crate) started: bool,
+    pub(crate) options: ParseOptions
+}
+
+impl LineReader
+{
+    pub(crate) fn with_options(options: ParseOptions) -> Self
+    {
+        Self {
+            options,
+            ..Self::default()
+        }
+    }
+
+    /// Reads a line without its line break. A malformed pitch is an error, but a malformed note count is given
+    /// back to the caller, as the pitches that follow it can be read either way.
+    pub(crate) fn read<'a>(&mut self, line: &'a str) -> Result<ScaleLine<'a>, ParsePitchError>
+    {
+        let (s, comment) = line.split_once("!").unwrap_or((line, ""));
+        let is_first = !self.started;
+        self.started |= !line.is_empty();
+        if s.is_empty()
+        {
+            if line.starts_with("!")
+            {
+                return Ok(ScaleLine::Comment(comment, is_first))
+            }
+            return Ok(ScaleLine::Blank)
+        }
+        match self.section
+        {
+            Section::Description =>
+            {
+                self.section = Section::NoteCount;
+                Ok(ScaleLine::Description(s))
+            },
+            Section::NoteCount =>
+            {
+                self.section = Section::Pitches;
+                Ok(ScaleLine::NoteCount(parse_note_count(s)))
+            },
+            Section::Pitches =>
+            {
+                let (pitch, text, label) = parse_pitch_line(s, &self.options)?;
+                Ok(ScaleLine::Pitch(pitch, text, label))
+            }
+        }
+    }
+}
+
+impl Scale
+{
+    pub fn parse_with(s: &str, options: &ParseOptions) -> Result<Self, ParseScaleError>
+    {
+        let mut parser = ScaleParser::with_options(*options);
+        for line in lines(s)
+        {
+            parser.feed_line(line)?;
+        }
+        parser.finish()
+    }
+}
+
+/// Parses a scale one line at a time, for streaming sources where the whole file isn't at hand yet.
+///
+/// ```
+/// use serde_scala::{Pitch, ScaleParser};
+///
+/// let mut parser = ScaleParser::new();
+/// for line in ["! fifth.scl", "Just fifth", "2", "3/2", "2/1"]
+/// {
+///     parser.feed_line(line).unwrap();
+/// }
+/// let scale = parser.finish().unwrap();
+///
+/// assert_eq!(scale.pitches, [Pitch::ratio(3, 2), Pitch::ratio(2, 1)]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScaleParser
+{
+    pub(crate) name: Option<String>,
+    /// `Some(None)` once the note count line has been seen but couldn't be read.
+    pub(crate) pitch_count: Option<Option<usize>>,
+    pub(crate) pitches: Vec<Pitch>,
+    pub(crate) cents_text: Vec<Option<String>>,
+    pub(crate) labels: Vec<Option<String>>,
+    pub(crate) metadata: ScaleMetadata,
+    pub(crate) lines: LineReader
+}
+
+impl ScaleParser
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    pub fn with_options(options: ParseOptions) -> Self
+    {
+        Self {
+            lines: LineReader::with_options(options),
+            ..Self::default()
+        }
+    }
+
+    /// Reads the next line. A trailing line break and a byte order mark starting the first line are ignored,
+    /// and bare CR line endings within the line split it further.
+    ///
+    /// An invalid line is skipped, so parsing can carry on past it.
+    pub fn feed_line(&mut self, line: &str) -> Result<(), ParseScaleError>
+    {
+        let line = line.strip_suffix('\n').unwrap_or(line);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        let line = if self.lines.started
+        {
+            line
+        }
+        else
+        {
+            line.strip_prefix('\u{feff}').unwrap_or(line)
+        };
+        if let Some((line, rest)) = line.split_once('\r')
+        {
+            let result = self.feed_line(line);
+            return result.and(self.feed_line(rest))
+        }
+
+        match self.lines.read(line)?
+        {
+            ScaleLine::Blank => (),
+            ScaleLine::Comment(comment, is_first) => self.metadata.read_comment(comment, is_first),
+            ScaleLine::Description(name) => self.name = Some(name.to_string()),
+            ScaleLine::NoteCount(Ok(count)) =>
+            {
+                let reserved = count.min(MAX_RESERVED_PITCHES);
+                self.pitches.reserve(reserved);
+                self.cents_text.reserve(reserved);
+                self.labels.reserve(reserved);
+                self.pitch_count = Some(Some(count));
+            },
+            ScaleLine::NoteCount(Err(err)) =>
+            {
+                self.pitch_count = Some(None);
+                return Err(err.into())
+            },
+            ScaleLine::Pitch(pitch, text, label) =>
+            {
+                self.cents_text.push(text.map(str::to_string));
+                self.labels.push(label.map(str::to_string));
+                self.pitches.push(pitch);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn finish(self) -> Result<Scale, ParseScaleError>
+    {
+        if self.name.is_none()
+        {
+            return Err(ParseScaleError::MissingDescription)
+        }
+        let pitch_count = self.pitch_count.ok_or(ParseScaleError::MissingNoteCount)?;
+
+        if pitch_count != Some(self.pitches.len())
+        {
+            return Err(ParseScaleError::WrongPitchCount(self.pitches.len()))
+        }
+
+        Ok(self.into_scale())
+    }
+
+    /// Whatever has been parsed so far, even if it isn't a complete scale.
+    pub(crate) fn into_scale(self) -> Scale
+    {
+        let mut scale = Scale::new(self.name.unwrap_or_default(), self.pitches);
+        scale.cents_text = self.cents_text;
+        scale.labels = self.labels;
+        scale.metadata = self.metadata;
+        scale
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+
+    use std::{fs::File, io::{BufRead, BufReader}};
+
+    use crate::{scl, ParseOptions, ParsePitchError, ParseScaleError, Pitch, Scale, ScaleParser};
+
+    #[test]
+    fn streamed_lines()
+    {
+        let mut reader = BufReader::new(File::open("scl/ptolemy.scl").unwrap());
+        let mut parser = ScaleParser::new();
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 0
+        {
+            parser.feed_line(&line).unwrap();
+            line.clear();
+        }
+
+        let text = std::fs::read_to_string("scl/ptolemy.scl").unwrap();
+        assert_eq!(parser.finish(), text.parse::<Scale>());
+
+        let mut parser = ScaleParser::new();
+        for line in ["labelled", "2", "6/5 minor third", "1200.0 cents   octave"]
+        {
+            parser.feed_line(line).unwrap();
+        }
+        let scale = parser.finish().unwrap();
+        assert_eq!(scale.label(0), Some("minor third"));
+        assert_eq!(scale.label(1), Some("octave"));
+        assert_eq!(scale.to_string().lines().last(), Some("1200.0 cents octave"));
+
+        let spaced = "spaced\n3\n3 / 2\n5 /4 third\n2/ 1\n".parse::<Scale>().unwrap();
+        assert_eq!(spaced.pitches, [Pitch::ratio(3, 2), Pitch::ratio(5, 4), Pitch::ratio(2, 1)]);
+        assert_eq!(spaced.labels, [None, Some("third".to_string()), None]);
+
+        let mut parser = ScaleParser::new();
+        parser.feed_line("\u{feff}! mac.scl\rold mac\r1\r2/1\r").unwrap();
+        assert_eq!(parser.finish(), Ok(scl!{"old mac" 2/1}));
+
+        let text = "\u{feff}! windows.scl\r\nwindows\r\n1\r\n2/1\r\n";
+        assert_eq!(text.parse::<Scale>(), Ok(scl!{"windows" 2/1}));
+        assert_eq!(text.replace("\r\n", "\r").parse::<Scale>(), Ok(scl!{"windows" 2/1}));
+
+        let mut parser = ScaleParser::new();
+        parser.feed_line("unfinished\r\n").unwrap();
+        assert_eq!(parser.finish(), Err(ParseScaleError::MissingNoteCount));
+    }
+
+    #[test]
+    fn decimal_comma()
+    {
+        let text = "comma\n2\n701,955\n2/1\n";
+        let lenient = ParseOptions::new().decimal_comma(true);
+
+        assert_eq!(text.parse::<Scale>(), Err(ParseScaleError::TrailingCharacters(",955".to_string())));
+        assert_eq!(Scale::parse_with(text, &lenient), Ok(scl!{"comma" 701.955 2/1}));
+        assert_eq!(Pitch::parse_with("3,2/1", &lenient), Err(ParsePitchError::TrailingCharacters(",2/1".to_string())));
+    }
+}
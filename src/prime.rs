@@ -0,0 +1,208 @@
+pub(crate) fn is_prime(n: u64) -> bool
+{
+    n >= 2 && (2..).take_while(|d| d*d <= n).all(|d| !n.is_multiple_of(d))
+}
+
+pub(crate) fn primes_up_to(limit: u64) -> Vec<u64>
+{
+    (2..=limit).filter(|&n| is_prime(n)).collect()
+}
+
+pub(crate) const fn gcd(mut a: u128, mut b: u128) -> u128
+{
+    while b != 0
+    {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// Divisors tried one by one before telling primes apart with [`is_probable_prime`].
+const TRIAL_LIMIT: u128 = 1 << 16;
+/// Steps of Pollard's rho per attempt at splitting a composite with no factor below [`TRIAL_LIMIT`].
+const RHO_STEPS: usize = 1 << 18;
+/// Fewer steps for composites above `u64::MAX`, which take a much slower [`mul_mod`].
+const WIDE_RHO_STEPS: usize = 1 << 12;
+const RHO_ATTEMPTS: u128 = 2;
+
+fn add_mod(a: u128, b: u128, m: u128) -> u128
+{
+    if a >= m - b
+    {
+        a - (m - b)
+    }
+    else
+    {
+        a + b
+    }
+}
+
+fn mul_mod(mut a: u128, mut b: u128, m: u128) -> u128
+{
+    if m <= u64::MAX as u128
+    {
+        return (a % m)*(b % m) % m
+    }
+    let mut product = 0;
+    a %= m;
+    while b > 0
+    {
+        if b & 1 == 1
+        {
+            product = add_mod(product, a, m);
+        }
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+    product
+}
+
+fn pow_mod(mut base: u128, mut exponent: u128, m: u128) -> u128
+{
+    let mut power = 1 % m;
+    while exponent > 0
+    {
+        if exponent & 1 == 1
+        {
+            power = mul_mod(power, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exponent >>= 1;
+    }
+    power
+}
+
+/// A Miller-Rabin test, exact below 3.3·10²⁴ and with vanishingly rare false positives above it.
+fn is_probable_prime(n: u128) -> bool
+{
+    const BASES: [u128; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+    if n < 2
+    {
+        return false
+    }
+    if let Some(&base) = BASES.iter().find(|&&base| n.is_multiple_of(base))
+    {
+        return n == base
+    }
+    let twos = (n - 1).trailing_zeros();
+    let odd = (n - 1) >> twos;
+    BASES.iter().all(|&base| {
+        let mut x = pow_mod(base, odd, n);
+        if x == 1 || x == n - 1
+        {
+            return true
+        }
+        (1..twos).any(|_| {
+            x = mul_mod(x, x, n);
+            x == n - 1
+        })
+    })
+}
+
+/// A nontrivial factor of the composite `n` found by Pollard's rho, if one turns up within [`RHO_STEPS`].
+fn find_factor(n: u128) -> Option<u128>
+{
+    let steps = match u64::try_from(n)
+    {
+        Ok(_) => RHO_STEPS,
+        Err(_) => WIDE_RHO_STEPS
+    };
+    (1..=RHO_ATTEMPTS).find_map(|c| {
+        let step = |x: u128| add_mod(mul_mod(x, x, n), c, n);
+        let (mut x, mut y) = (2, 2);
+        for _ in 0..steps
+        {
+            x = step(x);
+            y = step(step(y));
+            match gcd(x.abs_diff(y), n)
+            {
+                1 => (),
+                d if d == n => return None,
+                d => return Some(d)
+            }
+        }
+        None
+    })
+}
+
+/// Pushes the prime factors of `n`, which has none below [`TRIAL_LIMIT`], or returns `None` if it can't be split.
+fn split(n: u128, factors: &mut Vec<u128>) -> Option<()>
+{
+    if n == 1
+    {
+        return Some(())
+    }
+    if n < TRIAL_LIMIT*TRIAL_LIMIT || is_probable_prime(n)
+    {
+        factors.push(n);
+        return Some(())
+    }
+    let d = find_factor(n)?;
+    split(d, factors)?;
+    split(n/d, factors)
+}
+
+/// The distinct prime factors of `n`, in ascending order.
+///
+/// Returns `None` for 0, which has no factorization, and if `n` has more than one prime factor too large to be found
+/// in reasonable time, which can only happen above 2³².
+pub(crate) fn prime_factors(mut n: u128) -> Option<Vec<u128>>
+{
+    if n == 0
+    {
+        return None
+    }
+    let mut factors = vec![];
+    let mut d = 2;
+    while d < TRIAL_LIMIT && d*d <= n
+    {
+        if n.is_multiple_of(d)
+        {
+            factors.push(d);
+            while n.is_multiple_of(d)
+            {
+                n /= d;
+            }
+        }
+        d += 1;
+    }
+    let small = factors.len();
+    split(n, &mut factors)?;
+    factors[small..].sort_unstable();
+    factors.dedup();
+    Some(factors)
+}
+
+/// The largest prime factor of `n`, or 1 if `n` is 1. See [`prime_factors`] for when it's `None`.
+pub(crate) fn prime_limit_of(n: u128) -> Option<u128>
+{
+    Some(prime_factors(n)?.last().copied().unwrap_or(1))
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn factorization()
+    {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(primes_up_to(13), vec![2, 3, 5, 7, 11, 13]);
+        assert_eq!(prime_limit_of(1), Some(1));
+        assert_eq!(prime_limit_of(81), Some(3));
+        assert_eq!(prime_limit_of(80), Some(5));
+        assert_eq!(prime_limit_of(2*7*7*13), Some(13));
+        assert_eq!(prime_factors(2*7*7*13), Some(vec![2, 7, 13]));
+        assert_eq!(prime_factors(1), Some(vec![]));
+        assert_eq!(prime_factors(0), None);
+        assert_eq!(prime_limit_of(0), None);
+
+        assert_eq!(prime_limit_of(18446744073709551557), Some(18446744073709551557));
+        assert_eq!(prime_factors(2*4294967279*4294967291), Some(vec![2, 4294967279, 4294967291]));
+        assert_eq!(prime_factors(4294967291*4294967291*65537), Some(vec![65537, 4294967291]));
+        assert_eq!(prime_factors(18446744073709551557*18446744073709551533), None);
+    }
+}
@@ -0,0 +1,218 @@
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::{KeyboardMapping, Pitch, Scale};
+
+fn value_error(err: impl ToString) -> PyErr
+{
+    PyValueError::new_err(err.to_string())
+}
+
+/// A [`Pitch`] as seen from Python.
+#[pyclass(name = "Pitch", frozen, eq)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PyPitch(pub Pitch);
+
+#[pymethods]
+impl PyPitch
+{
+    /// Parses a pitch the way it is written in a .scl file.
+    #[new]
+    fn parse(text: &str) -> PyResult<Self>
+    {
+        text.parse().map(Self).map_err(value_error)
+    }
+
+    #[staticmethod]
+    fn ratio(numer: u128, denom: u128) -> PyResult<Self>
+    {
+        if denom == 0
+        {
+            return Err(value_error("denominator is zero"))
+        }
+        Ok(Self(Pitch::ratio(numer, denom)))
+    }
+
+    #[staticmethod]
+    fn cents(cents: f64) -> Self
+    {
+        Self(Pitch::cents(cents))
+    }
+
+    fn to_cents(&self) -> f64
+    {
+        self.0.to_cents()
+    }
+
+    fn __str__(&self) -> String
+    {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String
+    {
+        format!("Pitch('{}')", self.0)
+    }
+}
+
+/// A [`Scale`] as seen from Python.
+#[pyclass(name = "Scale", eq)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PyScale(pub Scale);
+
+#[pymethods]
+impl PyScale
+{
+    /// Parses the text of a .scl file.
+    #[new]
+    fn parse(text: &str) -> PyResult<Self>
+    {
+        text.parse().map(Self).map_err(value_error)
+    }
+
+    #[staticmethod]
+    fn from_cents(name: String, cents: Vec<f64>) -> Self
+    {
+        Self(Scale::from_cents(name, cents))
+    }
+
+    #[staticmethod]
+    fn from_ratios(name: String, ratios: Vec<(u128, u128)>) -> PyResult<Self>
+    {
+        if ratios.iter().any(|&(_, denom)| denom == 0)
+        {
+            return Err(value_error("denominator is zero"))
+        }
+        Ok(Self(Scale::from_ratios(name, ratios)))
+    }
+
+    /// `n` equal divisions of the octave.
+    #[staticmethod]
+    fn edo(n: u32) -> PyResult<Self>
+    {
+        if n == 0
+        {
+            return Err(value_error("no divisions"))
+        }
+        Ok(Self(Scale::edo(n)))
+    }
+
+    #[getter]
+    fn name(&self) -> String
+    {
+        self.0.name.clone()
+    }
+
+    #[getter]
+    fn pitches(&self) -> Vec<PyPitch>
+    {
+        self.0.pitches.iter().copied().map(PyPitch).collect()
+    }
+
+    fn cents(&self) -> Vec<f64>
+    {
+        self.0.pitches.iter().map(|pitch| pitch.to_cents()).collect()
+    }
+
+    fn interval_matrix(&self) -> Vec<Vec<f64>>
+    {
+        self.0.interval_matrix().to_cents()
+    }
+
+    fn __len__(&self) -> usize
+    {
+        self.0.pitches.len()
+    }
+
+    fn __str__(&self) -> String
+    {
+        self.0.to_string()
+    }
+}
+
+/// A [`KeyboardMapping`] as seen from Python.
+#[pyclass(name = "KeyboardMapping", eq)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PyKeyboardMapping(pub KeyboardMapping);
+
+#[pymethods]
+impl PyKeyboardMapping
+{
+    /// Parses the text of a .kbm file, or gives the default linear mapping without one.
+    #[new]
+    #[pyo3(signature = (text = None))]
+    fn parse(text: Option<&str>) -> PyResult<Self>
+    {
+        match text
+        {
+            Some(text) => text.parse().map(Self).map_err(value_error),
+            None => Ok(Self(KeyboardMapping::default()))
+        }
+    }
+
+    #[getter]
+    fn size(&self) -> usize
+    {
+        self.0.size
+    }
+
+    #[getter]
+    fn middle_note(&self) -> u8
+    {
+        self.0.middle_note
+    }
+
+    #[getter]
+    fn reference_note(&self) -> u8
+    {
+        self.0.reference_note
+    }
+
+    #[getter]
+    fn reference_frequency(&self) -> f64
+    {
+        self.0.reference_frequency
+    }
+
+    #[getter]
+    fn formal_octave(&self) -> usize
+    {
+        self.0.formal_octave
+    }
+
+    #[getter]
+    fn mapping(&self) -> Vec<Option<usize>>
+    {
+        self.0.mapping.clone()
+    }
+
+    fn __str__(&self) -> String
+    {
+        self.0.to_string()
+    }
+}
+
+/// The `serde_scala` Python module. Building it as an importable extension needs a `cdylib`, such as the one maturin produces.
+#[pymodule]
+fn serde_scala(module: &Bound<'_, PyModule>) -> PyResult<()>
+{
+    module.add_class::<PyPitch>()?;
+    module.add_class::<PyScale>()?;
+    module.add_class::<PyKeyboardMapping>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{Pitch, PyScale};
+
+    #[test]
+    fn python_views()
+    {
+        let scale = PyScale::edo(12).unwrap();
+
+        assert_eq!(scale.__len__(), 12);
+        assert_eq!(scale.pitches()[5].0, Pitch::cents(600.0));
+        assert_eq!(PyScale::parse(&scale.__str__()).unwrap().cents(), scale.cents());
+    }
+}
@@ -0,0 +1,175 @@
+use rand::Rng;
+
+use crate::{DetemperOptions, Pitch, Scale};
+
+/// How many times to draw a scale before giving up on constraints that are hard to meet.
+const MAX_ATTEMPTS: usize = 1000;
+
+/// What a random scale must satisfy. Step sizes are in cents.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RandomScaleConstraints
+{
+    /// How many pitches, including the period.
+    pub notes: usize,
+    pub min_step: f64,
+    pub max_step: f64,
+    /// The last pitch.
+    pub period: Pitch,
+    /// If set, every pitch is a ratio with no prime above this.
+    pub prime_limit: Option<u64>
+}
+
+impl Default for RandomScaleConstraints
+{
+    fn default() -> Self
+    {
+        Self {
+            notes: 7,
+            min_step: 50.0,
+            max_step: 400.0,
+            period: Pitch::ratio(2, 1),
+            prime_limit: None
+        }
+    }
+}
+
+impl Scale
+{
+    /// A random scale meeting the constraints, or `None` if they can't be met.
+    pub fn random(constraints: &RandomScaleConstraints) -> Option<Scale>
+    {
+        Self::random_with(constraints, &mut rand::thread_rng())
+    }
+
+    /// Like [`Scale::random`], drawing from the given generator, e.g. a seeded one for reproducible scales.
+    pub fn random_with(constraints: &RandomScaleConstraints, rng: &mut impl Rng) -> Option<Scale>
+    {
+        let &RandomScaleConstraints { notes, min_step, max_step, period, prime_limit } = constraints;
+        let period_cents = period.to_cents();
+        if notes == 0 || min_step > max_step || notes as f64*min_step > period_cents || (notes as f64*max_step) < period_cents
+        {
+            return None
+        }
+
+        for _ in 0..MAX_ATTEMPTS
+        {
+            let steps = random_steps(notes, min_step, max_step, period_cents, rng);
+            let mut cents = 0.0;
+            let mut pitches: Vec<Pitch> = steps[..notes - 1].iter()
+                .map(|step| {
+                    cents += step;
+                    Pitch::Cents(cents)
+                })
+                .collect();
+
+            if let Some(prime_limit) = prime_limit
+            {
+                let options = DetemperOptions {
+                    prime_limit,
+                    max_error: min_step/2.0,
+                    max_candidates: 1,
+                    ..Default::default()
+                };
+                let Some(ratios) = pitches.iter()
+                    .map(|&pitch| pitch.just_interpretations(&options).first().map(|just| Pitch::Ratio(just.ratio)))
+                    .collect::<Option<Vec<_>>>()
+                else
+                {
+                    continue
+                };
+                pitches = ratios;
+            }
+            pitches.push(period);
+
+            let scale = Scale::new("Random scale".to_string(), pitches);
+            let fits = scale.degrees()
+                .map(|pitch| pitch.to_cents())
+                .collect::<Vec<_>>()
+                .windows(2)
+                .all(|w| (min_step - 1e-9..=max_step + 1e-9).contains(&(w[1] - w[0])));
+            if fits
+            {
+                return Some(scale)
+            }
+        }
+        None
+    }
+}
+
+impl Scale
+{
+    /// A copy with every pitch but the period moved up or down by a random amount of at most `max_cents`.
+    pub fn detuned_randomly(&self, max_cents: f64, rng: &mut impl Rng) -> Scale
+    {
+        self.detuned_by(|_| rng.gen_range(-max_cents..=max_cents))
+    }
+}
+
+/// Steps between `min` and `max` summing to `total`, spreading what's left above the minimum at random.
+fn random_steps(notes: usize, min: f64, max: f64, total: f64, rng: &mut impl Rng) -> Vec<f64>
+{
+    let mut steps = vec![min; notes];
+    let mut remaining = total - min*notes as f64;
+    while remaining > 1e-9
+    {
+        let open: Vec<usize> = (0..notes).filter(|&i| steps[i] < max - 1e-9).collect();
+        let weights: Vec<f64> = open.iter().map(|_| rng.gen::<f64>() + f64::EPSILON).collect();
+        let sum: f64 = weights.iter().sum();
+        let mut given = 0.0;
+        for (&i, weight) in open.iter().zip(weights.iter())
+        {
+            let share = (remaining*weight/sum).min(max - steps[i]);
+            steps[i] += share;
+            given += share;
+        }
+        remaining -= given;
+    }
+    steps
+}
+
+#[cfg(test)]
+mod tests
+{
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{Pitch, RandomScaleConstraints, Scale};
+
+    #[test]
+    fn random_scales()
+    {
+        let mut rng = StdRng::seed_from_u64(12);
+        let constraints = RandomScaleConstraints {
+            notes: 9,
+            min_step: 80.0,
+            max_step: 250.0,
+            period: Pitch::ratio(3, 1),
+            ..Default::default()
+        };
+        let scale = Scale::random_with(&constraints, &mut rng).unwrap();
+        assert_eq!(scale.pitches.len(), 9);
+        assert_eq!(scale.period(), Some(Pitch::ratio(3, 1)));
+        let cents: Vec<f64> = scale.degrees().map(|pitch| pitch.to_cents()).collect();
+        assert!(cents.windows(2).all(|w| w[1] - w[0] >= 80.0 - 1e-9 && w[1] - w[0] <= 250.0 + 1e-9));
+
+        let just = RandomScaleConstraints {
+            prime_limit: Some(5),
+            ..Default::default()
+        };
+        let scale = Scale::random_with(&just, &mut rng).unwrap();
+        assert!(scale.pitches.iter().all(|pitch| pitch.prime_limit().is_some_and(|limit| limit <= 5)));
+
+        assert_eq!(Scale::random(&RandomScaleConstraints { notes: 2, ..Default::default() }), None);
+    }
+
+    #[test]
+    fn random_detune()
+    {
+        let mut rng = StdRng::seed_from_u64(7);
+        let scale = Scale::edo(12);
+        let humanized = scale.detuned_randomly(5.0, &mut rng);
+
+        assert_ne!(humanized, scale);
+        assert_eq!(humanized.period(), scale.period());
+        assert!(humanized.pitches.iter().zip(scale.pitches.iter()).all(|(a, b)| (a.to_cents() - b.to_cents()).abs() <= 5.0));
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{KeyboardMapping, Pitch, Tuning};
+
+/// A concert pitch: the frequency a MIDI note sounds at, anchoring 12-EDO and any tuning built on it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReferencePitch
+{
+    pub note: u8,
+    pub frequency: f64
+}
+
+impl ReferencePitch
+{
+    /// A4 at 440 Hz.
+    pub const A440: Self = Self::new(69, 440.0);
+
+    pub const fn new(note: u8, frequency: f64) -> Self
+    {
+        Self {
+            note,
+            frequency
+        }
+    }
+
+    /// The frequency of a MIDI note in 12-EDO, fractional notes lying in between.
+    pub fn note_frequency(&self, note: f64) -> f64
+    {
+        self.frequency*((note - self.note as f64)/12.0).exp2()
+    }
+
+    /// The frequency of a pitch taken above the reference note.
+    pub fn pitch_frequency(&self, pitch: Pitch) -> f64
+    {
+        self.frequency*(pitch.to_cents()/1200.0).exp2()
+    }
+
+    /// The nearest 12-EDO MIDI note to a frequency, and how many cents the frequency deviates from it.
+    pub fn midi_note_cents(&self, frequency: f64) -> (i32, f64)
+    {
+        Pitch::cents(1200.0*(frequency/self.frequency).log2()).to_midi_note_cents(self.note)
+    }
+}
+
+impl Default for ReferencePitch
+{
+    fn default() -> Self
+    {
+        Self::A440
+    }
+}
+
+impl KeyboardMapping
+{
+    pub fn reference(&self) -> ReferencePitch
+    {
+        ReferencePitch::new(self.reference_note, self.reference_frequency)
+    }
+
+    pub fn set_reference(&mut self, reference: ReferencePitch)
+    {
+        self.reference_note = reference.note;
+        self.reference_frequency = reference.frequency;
+    }
+
+    /// Tunes another key to a frequency.
+    pub fn with_reference(mut self, reference: ReferencePitch) -> Self
+    {
+        self.set_reference(reference);
+        self
+    }
+}
+
+impl Tuning
+{
+    /// Anchors the tuning at another concert pitch, replacing the reference of the mapping.
+    pub fn with_reference(mut self, reference: ReferencePitch) -> Self
+    {
+        self.mapping.set_reference(reference);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{Pitch, ReferencePitch, Scale, Tuning, TUN_BASE_FREQUENCY};
+
+    #[test]
+    fn concert_pitch()
+    {
+        let a440 = ReferencePitch::default();
+        assert!((a440.note_frequency(0.0) - TUN_BASE_FREQUENCY).abs() < 1e-12);
+        assert_eq!(a440.pitch_frequency(Pitch::ratio(3, 2)), 660.0);
+
+        let (note, deviation) = a440.midi_note_cents(432.0);
+        assert_eq!(note, 69);
+        assert!((deviation + 31.767).abs() < 1e-3);
+
+        let baroque = Tuning::new(Scale::edo(12), Default::default()).with_reference(ReferencePitch::new(69, 415.0));
+        assert_eq!(baroque.mapping.reference(), ReferencePitch::new(69, 415.0));
+        assert!((baroque.frequency(81).unwrap() - 830.0).abs() < 1e-9);
+    }
+}
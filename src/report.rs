@@ -0,0 +1,73 @@
+use std::fmt::Write;
+
+use crate::{Pitch, Scale};
+
+impl Scale
+{
+    /// A text report laid out like Scala's `SHOW` output: every degree as written and in cents with its label,
+    /// the step from each degree to the next, and the interval matrix of `SHOW INTERVALS`.
+    ///
+    /// ```
+    /// use serde_scala::scl;
+    ///
+    /// let report = scl!{"ji" 5/4 3/2 2/1}.report();
+    /// assert!(report.contains("\n   2:              3/2       701.955\n"));
+    /// ```
+    pub fn report(&self) -> String
+    {
+        let mut report = String::new();
+        let _ = writeln!(report, "{}", self.name);
+        let _ = writeln!(report, "{} pitches", self.pitches.len());
+        report.push('\n');
+
+        let _ = writeln!(report, "{:>4}: {:>16} {:>13}", 0, "1/1", format!("{:.3}", 0.0));
+        for (i, pitch) in self.pitches.iter().enumerate()
+        {
+            let written = match pitch
+            {
+                Pitch::Cents(_) => format!("{} cents", pitch),
+                Pitch::Ratio(_) => pitch.to_string()
+            };
+            let _ = write!(report, "{:>4}: {:>16} {:>13.3}", i + 1, written, pitch.to_cents());
+            if let Some(label) = self.label(i)
+            {
+                let _ = write!(report, " {}", label);
+            }
+            report.push('\n');
+        }
+
+        report += "\nSteps:\n";
+        let cents: Vec<f64> = self.degrees().map(|pitch| pitch.to_cents()).collect();
+        for (i, step) in cents.windows(2).map(|w| w[1] - w[0]).enumerate()
+        {
+            let _ = writeln!(report, "{:>4}: {:>13.3}", i + 1, step);
+        }
+
+        report += "\nIntervals:\n";
+        report += &self.interval_matrix().to_string();
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::scl;
+
+    #[test]
+    fn report()
+    {
+        let mut scale = scl!{"mixed" 9/8 386.3 2/1};
+        scale.set_label(1, "third");
+        let report = scale.report();
+        let lines: Vec<&str> = report.lines().collect();
+
+        assert_eq!(lines[..3], ["mixed", "3 pitches", ""]);
+        assert_eq!(lines[3], "   0:              1/1         0.000");
+        assert_eq!(lines[5], "   2:  386.30000 cents       386.300 third");
+        assert_eq!(lines[8], "Steps:");
+        assert_eq!(lines[10], "   2:       182.390");
+        assert_eq!(lines[13], "Intervals:");
+        assert_eq!(lines.len(), 18);
+    }
+}
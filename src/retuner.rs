@@ -0,0 +1,349 @@
+use crate::{ReferencePitch, Tuning};
+
+/// How many bits a pitch bend has.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BendResolution
+{
+    #[default]
+    Fourteen,
+    /// Only the most significant byte, for hardware ignoring the rest.
+    Seven
+}
+
+impl BendResolution
+{
+    pub fn max(self) -> u16
+    {
+        match self
+        {
+            Self::Fourteen => 0x3fff,
+            Self::Seven => 0x7f
+        }
+    }
+}
+
+/// The pitch bend a synth is set up for, since the range varies widely between hardware.
+///
+/// Bend values are in the units of the resolution, so `center` and the range up are `0..=127` with [`BendResolution::Seven`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BendConfig
+{
+    /// How far the bend reaches either way, in semitones.
+    pub range: f64,
+    pub resolution: BendResolution,
+    /// The bend value playing a note unbent.
+    pub center: u16
+}
+
+impl Default for BendConfig
+{
+    fn default() -> Self
+    {
+        Self::new(2.0, BendResolution::Fourteen)
+    }
+}
+
+impl BendConfig
+{
+    /// A bend range with the usual center of the resolution, 8192 or 64.
+    pub fn new(range: f64, resolution: BendResolution) -> Self
+    {
+        Self {
+            range,
+            resolution,
+            center: resolution.max()/2 + 1
+        }
+    }
+
+    pub fn with_center(mut self, center: u16) -> Self
+    {
+        self.center = center;
+        self
+    }
+
+    /// The bend value for bending a note by some semitones, clamped to the range.
+    pub fn bend(&self, semitones: f64) -> u16
+    {
+        let max = self.resolution.max() as f64;
+        let step = if semitones < 0.0
+        {
+            self.center as f64
+        }
+        else
+        {
+            max - self.center as f64
+        };
+        (self.center as f64 + semitones/self.range*step).round().clamp(0.0, max) as u16
+    }
+
+    /// A bend value as the 14 bits of a pitch bend message.
+    pub fn to_14_bit(&self, bend: u16) -> u16
+    {
+        match self.resolution
+        {
+            BendResolution::Fourteen => bend,
+            BendResolution::Seven => bend << 7
+        }
+    }
+
+    /// The range as the semitones and cents of the pitch bend sensitivity RPN.
+    pub fn rpn_range(&self) -> (u8, u8)
+    {
+        let cents = (self.range.abs()*100.0).round().min(127.0*100.0 + 99.0) as u32;
+        ((cents/100) as u8, (cents % 100) as u8)
+    }
+}
+
+/// The nearest MIDI note to where a key is tuned, and the pitch bend from there.
+pub(crate) fn retuned_note(tuning: &Tuning, key: u8, bend: &BendConfig) -> Option<(u8, u16)>
+{
+    let note = ReferencePitch::A440.midi_note(tuning.frequency(key)?);
+    let nearest = note.round().clamp(0.0, 127.0);
+    Some((nearest as u8, bend.bend(note - nearest)))
+}
+
+/// Which sounding note a [`Retuner`] stops when a note is played with every channel taken.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VoiceStealing
+{
+    /// The note that started first.
+    #[default]
+    Oldest,
+    /// The note played softest, the oldest of those if there are several.
+    Quietest,
+    /// None, leaving the new note unplayed.
+    Ignore
+}
+
+/// What to send on to a synth for the notes a [`Retuner`] was given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetunerEvent
+{
+    NoteOff
+    {
+        channel: u8,
+        note: u8
+    },
+    /// Set the channel's pitch bend to `bend` first, in the units of the [`Retuner::bend`] config, then start the note.
+    NoteOn
+    {
+        channel: u8,
+        note: u8,
+        velocity: u8,
+        bend: u16
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Voice
+{
+    source_channel: u8,
+    key: u8,
+    channel: u8,
+    note: u8,
+    velocity: u8,
+    started: u64
+}
+
+/// Plays every note of a tuning on a channel of its own, bent from the nearest 12-EDO note, for
+/// retuning live MIDI on synths without tuning support.
+///
+/// ```
+/// use serde_scala::{KeyboardMapping, Retuner, RetunerEvent, Scale, Tuning};
+///
+/// let mut retuner = Retuner::mpe(Tuning::new(Scale::edo(24), KeyboardMapping::default()));
+/// assert_eq!(retuner.note_on(0, 70, 100), [RetunerEvent::NoteOn { channel: 1, note: 70, velocity: 100, bend: 6144 }]);
+/// assert_eq!(retuner.note_off(0, 70), [RetunerEvent::NoteOff { channel: 1, note: 70 }]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Retuner
+{
+    pub tuning: Tuning,
+    /// The channels notes are spread over.
+    pub channels: Vec<u8>,
+    pub stealing: VoiceStealing,
+    pub bend: BendConfig,
+    voices: Vec<Voice>,
+    /// When each channel was last given a note.
+    last_used: [u64; 16],
+    time: u64
+}
+
+impl Retuner
+{
+    pub fn new(tuning: Tuning, channels: impl IntoIterator<Item = u8>) -> Self
+    {
+        Self {
+            tuning,
+            channels: channels.into_iter().map(|channel| channel & 0x0f).collect(),
+            stealing: VoiceStealing::default(),
+            bend: BendConfig::default(),
+            voices: vec![],
+            last_used: [0; 16],
+            time: 0
+        }
+    }
+
+    /// Spreads notes over the member channels of a lower MPE zone, channels 1 to 15.
+    pub fn mpe(tuning: Tuning) -> Self
+    {
+        Self::new(tuning, 1..=15)
+    }
+
+    pub fn with_stealing(mut self, stealing: VoiceStealing) -> Self
+    {
+        self.stealing = stealing;
+        self
+    }
+
+    pub fn with_bend(mut self, bend: BendConfig) -> Self
+    {
+        self.bend = bend;
+        self
+    }
+
+    /// A key played on a channel, which is sent on as a note on a channel of its own.
+    ///
+    /// Sends nothing for untuned keys, or if every channel is taken and nothing is to be stolen. The same key played
+    /// again on the same channel stops the note it was playing.
+    pub fn note_on(&mut self, channel: u8, key: u8, velocity: u8) -> Vec<RetunerEvent>
+    {
+        if velocity == 0
+        {
+            return self.note_off(channel, key)
+        }
+        let mut events = self.note_off(channel, key);
+        let Some((note, bend)) = retuned_note(&self.tuning, key, &self.bend)
+        else
+        {
+            return events
+        };
+
+        let free = self.channels.iter()
+            .copied()
+            .filter(|&channel| self.voices.iter().all(|voice| voice.channel != channel))
+            .min_by_key(|&channel| self.last_used[channel as usize]);
+        let target = match free
+        {
+            Some(channel) => channel,
+            None =>
+            {
+                let stolen = match self.stealing
+                {
+                    VoiceStealing::Oldest => self.voices.iter().enumerate().min_by_key(|(_, voice)| voice.started),
+                    VoiceStealing::Quietest => self.voices.iter().enumerate().min_by_key(|(_, voice)| (voice.velocity, voice.started)),
+                    VoiceStealing::Ignore => None
+                };
+                let Some((i, _)) = stolen
+                else
+                {
+                    return events
+                };
+                let voice = self.voices.remove(i);
+                events.push(RetunerEvent::NoteOff {
+                    channel: voice.channel,
+                    note: voice.note
+                });
+                voice.channel
+            }
+        };
+
+        self.time += 1;
+        self.last_used[target as usize] = self.time;
+        self.voices.push(Voice {
+            source_channel: channel,
+            key,
+            channel: target,
+            note,
+            velocity,
+            started: self.time
+        });
+        events.push(RetunerEvent::NoteOn {
+            channel: target,
+            note,
+            velocity,
+            bend
+        });
+        events
+    }
+
+    /// A key released on a channel, stopping the note it's playing, if any.
+    pub fn note_off(&mut self, channel: u8, key: u8) -> Vec<RetunerEvent>
+    {
+        let Some(i) = self.voices.iter().position(|voice| voice.source_channel == channel && voice.key == key)
+        else
+        {
+            return vec![]
+        };
+        let voice = self.voices.remove(i);
+        vec![RetunerEvent::NoteOff {
+            channel: voice.channel,
+            note: voice.note
+        }]
+    }
+
+    /// Stops every sounding note.
+    pub fn all_notes_off(&mut self) -> Vec<RetunerEvent>
+    {
+        self.voices.drain(..)
+            .map(|voice| RetunerEvent::NoteOff {
+                channel: voice.channel,
+                note: voice.note
+            })
+            .collect()
+    }
+
+    /// The channel and note a key played on a channel is sounding as, such as to send its aftertouch to.
+    pub fn voice(&self, channel: u8, key: u8) -> Option<(u8, u8)>
+    {
+        self.voices.iter()
+            .find(|voice| voice.source_channel == channel && voice.key == key)
+            .map(|voice| (voice.channel, voice.note))
+    }
+
+    /// How many notes are sounding.
+    pub fn sounding(&self) -> usize
+    {
+        self.voices.len()
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, BendConfig, BendResolution, KeyboardMapping, Retuner, RetunerEvent, Tuning, VoiceStealing};
+
+    #[test]
+    fn retune_live()
+    {
+        let tuning = Tuning::new(scl!{"eighth tones" 25.0 100.0}, KeyboardMapping::new(0, 0, 127, 69, 69, 440.0, 0, vec![]));
+        let mut retuner = Retuner::new(tuning, [1, 2]);
+        let on = |channel, note, velocity, bend| RetunerEvent::NoteOn { channel, note, velocity, bend };
+        let off = |channel, note| RetunerEvent::NoteOff { channel, note };
+
+        assert_eq!(retuner.note_on(0, 70, 100), [on(1, 69, 100, 9216)]);
+        assert_eq!(retuner.note_on(0, 71, 50), [on(2, 70, 50, 8192)]);
+        assert_eq!(retuner.voice(0, 71), Some((2, 70)));
+
+        // Both channels are taken, so the oldest note makes room.
+        assert_eq!(retuner.note_on(0, 72, 80), [off(1, 69), on(1, 70, 80, 9216)]);
+        assert!(retuner.note_off(0, 70).is_empty());
+        assert_eq!(retuner.note_off(0, 71), [off(2, 70)]);
+        assert!(retuner.note_on(0, 73, 0).is_empty());
+
+        let mut quiet = retuner.clone().with_stealing(VoiceStealing::Quietest);
+        quiet.note_on(3, 69, 10);
+        assert_eq!(quiet.note_on(3, 68, 90), [off(2, 69), on(2, 68, 90, 9216)]);
+
+        let mut ignoring = retuner.with_stealing(VoiceStealing::Ignore);
+        ignoring.note_on(3, 69, 10);
+        assert!(ignoring.note_on(3, 68, 90).is_empty());
+        assert_eq!(ignoring.all_notes_off().len(), 2);
+        assert_eq!(ignoring.sounding(), 0);
+
+        let mut coarse = ignoring.with_bend(BendConfig::new(12.0, BendResolution::Seven));
+        assert_eq!(coarse.note_on(0, 70, 100), [on(1, 69, 100, 65)]);
+        assert_eq!(coarse.bend.to_14_bit(65), 8320);
+        assert_eq!(BendConfig::new(0.5, BendResolution::Fourteen).rpn_range(), (0, 50));
+    }
+}
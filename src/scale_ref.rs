@@ -0,0 +1,133 @@
+use crate::{parser::{lines, LineReader, ScaleLine}, ParseOptions, ParseScaleError, Pitch, Scale, ScaleMetadata};
+
+/// A parsed scale that borrows its name and labels from the source text.
+///
+/// Parsing this way only allocates the pitch list and any comments, which adds up when scanning the whole archive
+/// over and over.
+///
+/// ```
+/// use serde_scala::{Pitch, ScaleRef};
+///
+/// let source = String::from("! fifth.scl\nJust fifth\n2\n3/2\n2/1\n");
+/// let scale = ScaleRef::parse(&source).unwrap();
+///
+/// assert_eq!(scale.name, "Just fifth");
+/// assert_eq!(scale.pitches, [Pitch::ratio(3, 2), Pitch::ratio(2, 1)]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScaleRef<'a>
+{
+    pub name: &'a str,
+    pub pitches: Vec<Pitch>,
+    /// The text of each pitch given in cents, like [`Scale::cents_text`].
+    pub cents_text: Vec<Option<&'a str>>,
+    pub labels: Vec<Option<&'a str>>,
+    pub metadata: ScaleMetadata
+}
+
+impl<'a> ScaleRef<'a>
+{
+    pub fn parse(s: &'a str) -> Result<Self, ParseScaleError>
+    {
+        Self::parse_with(s, &ParseOptions::default())
+    }
+
+    pub fn parse_with(s: &'a str, options: &ParseOptions) -> Result<Self, ParseScaleError>
+    {
+        let mut reader = LineReader::with_options(*options);
+        let mut name = None;
+        let mut pitch_count = None;
+        let mut pitches = vec![];
+        let mut cents_text = vec![];
+        let mut labels = vec![];
+        let mut metadata = ScaleMetadata::default();
+
+        for line in lines(s)
+        {
+            match reader.read(line)?
+            {
+                ScaleLine::Blank => (),
+                ScaleLine::Comment(comment, is_first) => metadata.read_comment(comment, is_first),
+                ScaleLine::Description(description) => name = Some(description),
+                ScaleLine::NoteCount(count) => pitch_count = Some(count?),
+                ScaleLine::Pitch(pitch, text, label) =>
+                {
+                    pitches.push(pitch);
+                    cents_text.push(text);
+                    labels.push(label);
+                }
+            }
+        }
+
+        let name = name.ok_or(ParseScaleError::MissingDescription)?;
+        let pitch_count = pitch_count.ok_or(ParseScaleError::MissingNoteCount)?;
+
+        if pitches.len() != pitch_count
+        {
+            return Err(ParseScaleError::WrongPitchCount(pitches.len()))
+        }
+
+        Ok(Self {
+            name,
+            pitches,
+            cents_text,
+            labels,
+            metadata
+        })
+    }
+
+    pub fn to_scale(&self) -> Scale
+    {
+        let mut scale = Scale::new(self.name.to_string(), self.pitches.clone());
+        scale.cents_text = self.cents_text.iter().map(|text| text.map(str::to_string)).collect();
+        scale.labels = self.labels.iter().map(|label| label.map(str::to_string)).collect();
+        scale.metadata = self.metadata.clone();
+        scale
+    }
+}
+
+impl From<ScaleRef<'_>> for Scale
+{
+    fn from(value: ScaleRef<'_>) -> Self
+    {
+        value.to_scale()
+    }
+}
+
+impl From<&ScaleRef<'_>> for Scale
+{
+    fn from(value: &ScaleRef<'_>) -> Self
+    {
+        value.to_scale()
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::fs;
+
+    use crate::{ParseOptions, Scale, ScaleRef};
+
+    #[test]
+    fn matches_owned_parse()
+    {
+        for name in ["ptolemy", "pyth_12", "12edo", "05-19"]
+        {
+            let text = fs::read_to_string(format!("scl/{}.scl", name)).unwrap();
+            let scale = ScaleRef::parse(&text).unwrap();
+            let owned = text.parse::<Scale>().unwrap();
+
+            assert_eq!(Scale::from(&scale).to_string(), owned.to_string());
+            assert_eq!(Scale::from(scale), owned);
+        }
+
+        let text = "! comma.scl\n! Author: Someone\nDecimal commas\n2\n701,955 fifth\n1200,0\n";
+        let options = ParseOptions::new().decimal_comma(true);
+        let scale = ScaleRef::parse_with(text, &options).unwrap();
+        assert_eq!(scale.cents_text, [Some("701,955"), Some("1200,0")]);
+        assert_eq!(scale.labels, [Some("fifth"), None]);
+        assert_eq!(scale.metadata.author.as_deref(), Some("Someone"));
+        assert_eq!(Scale::from(scale).to_string(), Scale::parse_with(text, &options).unwrap().to_string());
+    }
+}
@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+
+use midly::{num::{u14, u28, u4, u7}, MidiMessage, PitchBend, Smf, Track, TrackEvent, TrackEventKind};
+
+use crate::{retuner::retuned_note, BendConfig, Retuner, RetunerEvent, Tuning};
+
+/// How many member channels the MPE zone of a retuned file has, following its master channel 0.
+const MPE_MEMBER_CHANNELS: u8 = 15;
+
+/// How a retuned MIDI file bends its notes into tune.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RetuneMode
+{
+    /// Every note bends the channel it's played on, retuning any other note sounding on it as well,
+    /// which suits files with one line per channel.
+    #[default]
+    PerChannel,
+    /// Every note is moved to a member channel of an MPE zone to bend on its own, the oldest note making room
+    /// when they are all taken. Other channel messages go to the master channel.
+    Mpe
+}
+
+fn midi<'a>(channel: u8, message: MidiMessage) -> TrackEventKind<'a>
+{
+    TrackEventKind::Midi {
+        channel: u4::new(channel),
+        message
+    }
+}
+
+fn pitch_bend<'a>(channel: u8, bend: u16) -> TrackEventKind<'a>
+{
+    midi(channel, MidiMessage::PitchBend { bend: PitchBend(u14::new(bend)) })
+}
+
+/// The registered parameter messages setting a channel's pitch bend range in semitones, or for parameter 6,
+/// the number of member channels of an MPE zone.
+fn rpn<'a>(channel: u8, parameter: u8, value: u8) -> [TrackEventKind<'a>; 3]
+{
+    let controller = |controller: u8, value: u8| midi(channel, MidiMessage::Controller {
+        controller: u7::new(controller),
+        value: u7::new(value)
+    });
+    [controller(101, 0), controller(100, parameter), controller(6, value)]
+}
+
+/// Where notes sound across all tracks, as they're retuned in the order they play.
+struct Retuning<'t>
+{
+    tuning: &'t Tuning,
+    mode: RetuneMode,
+    bend: BendConfig,
+    retuner: Retuner,
+    /// In per-channel mode, the note each key played on each channel sounds as.
+    notes: HashMap<(u8, u7), u7>
+}
+
+impl Retuning<'_>
+{
+    fn retune<'a>(&mut self, kind: TrackEventKind<'a>) -> Vec<TrackEventKind<'a>>
+    {
+        let TrackEventKind::Midi { channel, message } = kind
+        else
+        {
+            return vec![kind]
+        };
+        let channel = channel.as_int();
+
+        let events = match (self.mode, message)
+        {
+            (RetuneMode::PerChannel, MidiMessage::NoteOn { key, vel }) if vel > 0 =>
+            {
+                let Some((note, bend)) = retuned_note(self.tuning, key.as_int(), &self.bend)
+                else
+                {
+                    return vec![kind]
+                };
+                let note = u7::new(note);
+                self.notes.insert((channel, key), note);
+                return vec![pitch_bend(channel, self.bend.to_14_bit(bend)), midi(channel, MidiMessage::NoteOn { key: note, vel })]
+            },
+            (RetuneMode::PerChannel, MidiMessage::NoteOn { key, .. } | MidiMessage::NoteOff { key, .. }) =>
+            {
+                return match self.notes.remove(&(channel, key))
+                {
+                    Some(note) => vec![midi(channel, MidiMessage::NoteOff { key: note, vel: u7::new(0) })],
+                    None => vec![kind]
+                }
+            },
+            (RetuneMode::PerChannel, _) => return vec![kind],
+            (RetuneMode::Mpe, MidiMessage::NoteOn { key, vel }) if vel > 0 =>
+            {
+                if self.tuning.frequency(key.as_int()).is_none()
+                {
+                    return vec![kind]
+                }
+                self.retuner.note_on(channel, key.as_int(), vel.as_int())
+            },
+            (RetuneMode::Mpe, MidiMessage::NoteOn { key, .. } | MidiMessage::NoteOff { key, .. }) =>
+            {
+                let events = self.retuner.note_off(channel, key.as_int());
+                if events.is_empty()
+                {
+                    return vec![kind]
+                }
+                events
+            },
+            (RetuneMode::Mpe, MidiMessage::Aftertouch { key, vel }) =>
+            {
+                return match self.retuner.voice(channel, key.as_int())
+                {
+                    Some((channel, note)) => vec![midi(channel, MidiMessage::Aftertouch { key: u7::new(note), vel })],
+                    None => vec![kind]
+                }
+            },
+            (RetuneMode::Mpe, message) => return vec![midi(0, message)]
+        };
+
+        events.into_iter()
+            .flat_map(|event| match event
+            {
+                RetunerEvent::NoteOff { channel, note } => vec![midi(channel, MidiMessage::NoteOff { key: u7::new(note), vel: u7::new(0) })],
+                RetunerEvent::NoteOn { channel, note, velocity, bend } => vec![
+                    pitch_bend(channel, self.bend.to_14_bit(bend)),
+                    midi(channel, MidiMessage::NoteOn { key: u7::new(note), vel: u7::new(velocity) })
+                ]
+            })
+            .collect()
+    }
+}
+
+/// Rewrites a MIDI file to play in a tuning on any synth, bending every note from the nearest 12-EDO note.
+///
+/// Every channel's pitch bend range is set to ±2 semitones at the start of the first track, and with
+/// [`RetuneMode::Mpe`] the MPE zone is configured too. Notes on untuned keys are left as they are.
+pub fn retune_smf<'a>(smf: &Smf<'a>, tuning: &Tuning, mode: RetuneMode) -> Smf<'a>
+{
+    retune_smf_with(smf, tuning, mode, &BendConfig::default())
+}
+
+/// Rewrites a MIDI file like [`retune_smf`], for synths with some other pitch bend range or resolution.
+pub fn retune_smf_with<'a>(smf: &Smf<'a>, tuning: &Tuning, mode: RetuneMode, bend: &BendConfig) -> Smf<'a>
+{
+    // Tracks play alongside each other, so their events are retuned in the order they play.
+    let mut order = vec![];
+    for (track, events) in smf.tracks.iter().enumerate()
+    {
+        let mut time = 0;
+        for (i, event) in events.iter().enumerate()
+        {
+            time += event.delta.as_int() as u64;
+            order.push((time, track, i));
+        }
+    }
+    order.sort();
+
+    let mut retuned: Vec<Vec<(u64, TrackEventKind<'a>)>> = vec![vec![]; smf.tracks.len()];
+    if let Some(first) = retuned.first_mut()
+    {
+        if mode == RetuneMode::Mpe
+        {
+            first.extend(rpn(0, 6, MPE_MEMBER_CHANNELS).map(|kind| (0, kind)));
+        }
+        let (semitones, cents) = bend.rpn_range();
+        for channel in 0..16
+        {
+            first.extend(rpn(channel, 0, semitones).map(|kind| (0, kind)));
+            if cents > 0
+            {
+                first.push((0, midi(channel, MidiMessage::Controller {
+                    controller: u7::new(38),
+                    value: u7::new(cents)
+                })));
+            }
+        }
+    }
+
+    let mut retuning = Retuning {
+        tuning,
+        mode,
+        bend: *bend,
+        retuner: Retuner::new(tuning.clone(), 1..=MPE_MEMBER_CHANNELS).with_bend(*bend),
+        notes: HashMap::new()
+    };
+    for (time, track, i) in order
+    {
+        let kind = smf.tracks[track][i].kind;
+        retuned[track].extend(retuning.retune(kind).into_iter().map(|kind| (time, kind)));
+    }
+
+    let mut out = Smf::new(smf.header);
+    out.tracks = retuned.into_iter()
+        .map(|events| {
+            let mut last = 0;
+            events.into_iter()
+                .map(|(time, kind)| {
+                    let delta = u28::new((time - last) as u32);
+                    last = time;
+                    TrackEvent { delta, kind }
+                })
+                .collect::<Track>()
+        })
+        .collect();
+    out
+}
+
+#[cfg(test)]
+mod tests
+{
+    use midly::{num::{u28, u4, u7}, Format, Header, MetaMessage, MidiMessage, PitchBend, Smf, Timing, TrackEvent, TrackEventKind};
+
+    use crate::{retune_smf, scl, KeyboardMapping, RetuneMode, Tuning};
+
+    fn note(delta: u32, channel: u8, key: u8, on: bool) -> TrackEvent<'static>
+    {
+        let (key, vel) = (u7::new(key), u7::new(if on { 100 } else { 0 }));
+        TrackEvent {
+            delta: u28::new(delta),
+            kind: TrackEventKind::Midi {
+                channel: u4::new(channel),
+                message: MidiMessage::NoteOn { key, vel }
+            }
+        }
+    }
+
+    fn messages(smf: &Smf) -> Vec<(u32, u8, MidiMessage)>
+    {
+        smf.tracks[0].iter()
+            .filter_map(|event| match event.kind
+            {
+                TrackEventKind::Midi { channel, message } if !matches!(message, MidiMessage::Controller { .. }) =>
+                    Some((event.delta.as_int(), channel.as_int(), message)),
+                _ => None
+            })
+            .collect()
+    }
+
+    #[test]
+    fn retune_file()
+    {
+        // Every other key is bent an eighth tone up.
+        let tuning = Tuning::new(scl!{"eighth tones" 25.0 100.0}, KeyboardMapping::new(0, 0, 127, 69, 69, 440.0, 0, vec![]));
+        let mut smf = Smf::new(Header::new(Format::SingleTrack, Timing::Metrical(96.into())));
+        smf.tracks.push(vec![
+            note(0, 0, 71, true),
+            note(0, 0, 70, true),
+            note(96, 0, 71, false),
+            note(0, 0, 70, false),
+            TrackEvent { delta: u28::new(0), kind: TrackEventKind::Meta(MetaMessage::EndOfTrack) }
+        ]);
+        let bend = |bend: i16| MidiMessage::PitchBend { bend: PitchBend::from_int(bend) };
+        let on = |key: u8| MidiMessage::NoteOn { key: u7::new(key), vel: u7::new(100) };
+        let off = |key: u8| MidiMessage::NoteOff { key: u7::new(key), vel: u7::new(0) };
+
+        // Key 71 plays a semitone above A4, and key 70 an eighth tone above it.
+        let per_channel = retune_smf(&smf, &tuning, RetuneMode::PerChannel);
+        assert_eq!(per_channel.tracks[0].iter().filter(|event| matches!(event.kind, TrackEventKind::Midi { message: MidiMessage::Controller { .. }, .. })).count(), 48);
+        assert_eq!(messages(&per_channel), [
+            (0, 0, bend(0)),
+            (0, 0, on(70)),
+            (0, 0, bend(1024)),
+            (0, 0, on(69)),
+            (96, 0, off(70)),
+            (0, 0, off(69))
+        ]);
+
+        let mpe = retune_smf(&smf, &tuning, RetuneMode::Mpe);
+        assert_eq!(messages(&mpe), [
+            (0, 1, bend(0)),
+            (0, 1, on(70)),
+            (0, 2, bend(1024)),
+            (0, 2, on(69)),
+            (96, 1, off(70)),
+            (0, 2, off(69))
+        ]);
+        assert!(matches!(mpe.tracks[0].last().unwrap().kind, TrackEventKind::Meta(MetaMessage::EndOfTrack)));
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{Pitch, Scale};
+
+/// A fixed-size scale that can be built in `const` contexts, for tunings exposed as `static`s.
+///
+/// ```
+/// use serde_scala::{Pitch, StaticScale};
+///
+/// static PENTATONIC: StaticScale<5> = StaticScale::new("Pythagorean pentatonic", [
+///     Pitch::ratio(9, 8),
+///     Pitch::ratio(81, 64),
+///     Pitch::ratio(3, 2),
+///     Pitch::ratio(27, 16),
+///     Pitch::ratio(2, 1)
+/// ]);
+///
+/// assert_eq!(PENTATONIC.to_scale().pitches.len(), 5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StaticScale<const N: usize>
+{
+    pub name: &'static str,
+    pub pitches: [Pitch; N]
+}
+
+impl<const N: usize> StaticScale<N>
+{
+    pub const fn new(name: &'static str, pitches: [Pitch; N]) -> Self
+    {
+        Self {
+            name,
+            pitches
+        }
+    }
+
+    pub fn to_scale(&self) -> Scale
+    {
+        Scale::new(self.name.to_string(), self.pitches.to_vec())
+    }
+}
+
+impl<const N: usize> From<StaticScale<N>> for Scale
+{
+    fn from(value: StaticScale<N>) -> Self
+    {
+        value.to_scale()
+    }
+}
+
+impl<const N: usize> From<&StaticScale<N>> for Scale
+{
+    fn from(value: &StaticScale<N>) -> Self
+    {
+        value.to_scale()
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, Pitch, Scale, StaticScale};
+
+    const FIFTH: Pitch = Pitch::ratio(6, 4);
+    static SCALE: StaticScale<3> = StaticScale::new("static", [Pitch::ratio(5, 4), FIFTH, Pitch::cents(1200.0)]);
+
+    #[test]
+    fn static_scale()
+    {
+        assert_eq!(FIFTH, Pitch::ratio(3, 2));
+        assert_eq!(Scale::from(&SCALE), scl!{"static" 5/4 3/2 1200.0});
+    }
+}
@@ -0,0 +1,215 @@
+use std::{f64::consts::TAU, fmt::Write};
+
+use crate::{Pitch, Scale};
+
+/// How [`Scale::to_svg`] draws a scale.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SvgLayout
+{
+    /// Degrees around a circle spanning one period, the unison at the top.
+    #[default]
+    Wheel,
+    /// Degrees along a line from the unison to the period.
+    Linear
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SvgOptions
+{
+    pub layout: SvgLayout,
+    /// Label each degree with its label, or else its pitch.
+    pub labels: bool,
+    /// Mark every 100 cents, to compare against 12-EDO.
+    pub edo_marks: bool
+}
+
+impl Default for SvgOptions
+{
+    fn default() -> Self
+    {
+        Self {
+            layout: SvgLayout::Wheel,
+            labels: true,
+            edo_marks: false
+        }
+    }
+}
+
+impl SvgOptions
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    pub fn layout(mut self, layout: SvgLayout) -> Self
+    {
+        self.layout = layout;
+        self
+    }
+
+    pub fn labels(mut self, labels: bool) -> Self
+    {
+        self.labels = labels;
+        self
+    }
+
+    pub fn edo_marks(mut self, edo_marks: bool) -> Self
+    {
+        self.edo_marks = edo_marks;
+        self
+    }
+}
+
+const WHEEL_SIZE: f64 = 400.0;
+const WHEEL_RADIUS: f64 = 150.0;
+const LINE_WIDTH: f64 = 800.0;
+const LINE_HEIGHT: f64 = 120.0;
+const LINE_MARGIN: f64 = 40.0;
+
+fn xml_escape(s: &str) -> String
+{
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn degree_label(scale: &Scale, degree: usize) -> String
+{
+    if degree == 0
+    {
+        return "1/1".to_string()
+    }
+    if let Some(label) = scale.label(degree - 1)
+    {
+        return label.to_string()
+    }
+    match scale.pitches[degree - 1]
+    {
+        Pitch::Cents(cents) => format!("{:.1}", cents),
+        pitch => pitch.to_string()
+    }
+}
+
+impl Scale
+{
+    /// Draws the degrees within one period as an SVG image.
+    ///
+    /// ```
+    /// use serde_scala::{Scale, SvgLayout, SvgOptions};
+    ///
+    /// let svg = Scale::edo(5).to_svg(&SvgOptions::new().layout(SvgLayout::Linear).edo_marks(true));
+    /// assert!(svg.starts_with("<svg"));
+    /// ```
+    pub fn to_svg(&self, options: &SvgOptions) -> String
+    {
+        let period = self.period().map(Pitch::to_cents).filter(|&period| period > 0.0).unwrap_or(1200.0);
+        let degrees: Vec<(usize, f64)> = self.degrees()
+            .take(self.pitches.len())
+            .map(Pitch::to_cents)
+            .enumerate()
+            .collect();
+
+        let (width, height) = match options.layout
+        {
+            SvgLayout::Wheel => (WHEEL_SIZE, WHEEL_SIZE),
+            SvgLayout::Linear => (LINE_WIDTH, LINE_HEIGHT)
+        };
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n<title>{}</title>\n",
+            width, height, width, height, xml_escape(&self.name)
+        );
+
+        // Where a pitch lies, moved `offset` away from the drawing on the side its labels go.
+        let place = |cents: f64, offset: f64| -> (f64, f64)
+        {
+            match options.layout
+            {
+                SvgLayout::Wheel =>
+                {
+                    let angle = cents/period*TAU;
+                    let radius = WHEEL_RADIUS + offset;
+                    (WHEEL_SIZE/2.0 + radius*angle.sin(), WHEEL_SIZE/2.0 - radius*angle.cos())
+                },
+                SvgLayout::Linear => (LINE_MARGIN + cents/period*(LINE_WIDTH - 2.0*LINE_MARGIN), LINE_HEIGHT/2.0 - offset)
+            }
+        };
+
+        let _ = match options.layout
+        {
+            SvgLayout::Wheel => writeln!(
+                svg,
+                "<circle cx=\"{0}\" cy=\"{0}\" r=\"{1}\" fill=\"none\" stroke=\"black\"/>",
+                WHEEL_SIZE/2.0, WHEEL_RADIUS
+            ),
+            SvgLayout::Linear => writeln!(
+                svg,
+                "<line x1=\"{0}\" y1=\"{1}\" x2=\"{2}\" y2=\"{1}\" stroke=\"black\"/>",
+                LINE_MARGIN, LINE_HEIGHT/2.0, LINE_WIDTH - LINE_MARGIN
+            )
+        };
+
+        if options.edo_marks
+        {
+            // The wheel comes back around to the unison at the period.
+            let end = if options.layout == SvgLayout::Wheel { period - 1e-9 } else { period + 1e-9 };
+            let mut cents = 0.0;
+            while cents < end
+            {
+                let (x1, y1) = place(cents, -4.0);
+                let (x2, y2) = place(cents, -12.0);
+                let _ = writeln!(svg, "<line class=\"edo\" x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" stroke=\"gray\"/>", x1, y1, x2, y2);
+                cents += 100.0;
+            }
+        }
+
+        let mut ends = degrees.clone();
+        if options.layout == SvgLayout::Linear
+        {
+            ends.push((self.pitches.len(), period));
+        }
+        for &(degree, cents) in &ends
+        {
+            let (x, y) = place(cents, 0.0);
+            let _ = writeln!(svg, "<circle class=\"degree\" cx=\"{:.2}\" cy=\"{:.2}\" r=\"4\"/>", x, y);
+            if options.labels
+            {
+                let (x, y) = place(cents, 20.0);
+                let _ = writeln!(
+                    svg,
+                    "<text x=\"{:.2}\" y=\"{:.2}\" font-size=\"12\" text-anchor=\"middle\" dominant-baseline=\"middle\">{}</text>",
+                    x, y, xml_escape(&degree_label(self, degree))
+                );
+            }
+        }
+
+        svg += "</svg>\n";
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, SvgLayout, SvgOptions};
+
+    #[test]
+    fn svg()
+    {
+        let mut scale = scl!{"a <b>" 9/8 386.3 3/2 2/1};
+        scale.set_label(2, "fifth");
+
+        let wheel = scale.to_svg(&SvgOptions::new().edo_marks(true));
+        assert!(wheel.contains("<title>a &lt;b&gt;</title>"));
+        assert_eq!(wheel.matches("class=\"degree\"").count(), 4);
+        assert_eq!(wheel.matches("class=\"edo\"").count(), 12);
+        assert!(wheel.contains("<circle class=\"degree\" cx=\"200.00\" cy=\"50.00\" r=\"4\"/>"));
+        assert!(wheel.contains(">386.3</text>") && wheel.contains(">fifth</text>") && wheel.contains(">9/8</text>"));
+
+        let line = scale.to_svg(&SvgOptions::new().layout(SvgLayout::Linear).labels(false));
+        assert_eq!(line.matches("class=\"degree\"").count(), 5);
+        assert!(!line.contains("<text"));
+        assert!(line.contains("<circle class=\"degree\" cx=\"760.00\" cy=\"60.00\" r=\"4\"/>"));
+    }
+}
@@ -0,0 +1,194 @@
+use crate::{linalg::solve, prime::primes_up_to, Monzo, TuningMap, Val};
+
+/// Brings integer rows into Hermite normal form, dropping rows that turn out dependent.
+fn hermite(mut rows: Vec<Vec<i64>>) -> Vec<Vec<i64>>
+{
+    let columns = rows.first().map(Vec::len).unwrap_or(0);
+    let mut rank = 0;
+    for col in 0..columns
+    {
+        // Euclid's algorithm between the remaining rows, until one of them is left nonzero in this column.
+        while let Some(min) = (rank..rows.len()).filter(|&i| rows[i][col] != 0).min_by_key(|&i| rows[i][col].abs())
+        {
+            rows.swap(rank, min);
+            let pivot = rows[rank].clone();
+            let mut done = true;
+            for row in rows.iter_mut().skip(rank + 1)
+            {
+                let q = row[col]/pivot[col];
+                row.iter_mut().zip(pivot.iter()).for_each(|(x, p)| *x -= q*p);
+                done &= row[col] == 0;
+            }
+            if done
+            {
+                break
+            }
+        }
+        if rank == rows.len() || rows[rank][col] == 0
+        {
+            continue
+        }
+        if rows[rank][col] < 0
+        {
+            rows[rank].iter_mut().for_each(|x| *x = -*x);
+        }
+        let pivot = rows[rank].clone();
+        for row in rows.iter_mut().take(rank)
+        {
+            let q = row[col].div_euclid(pivot[col]);
+            row.iter_mut().zip(pivot.iter()).for_each(|(x, p)| *x -= q*p);
+        }
+        rank += 1;
+    }
+    rows.truncate(rank);
+    rows
+}
+
+/// A regular temperament, mapping every prime to a combination of generators.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Temperament
+{
+    pub primes: Vec<u64>,
+    /// One row per generator, giving how many of that generator each prime takes.
+    pub mapping: Vec<Vec<i64>>
+}
+
+impl Temperament
+{
+    /// The temperament in which every comma vanishes, over the primes up to `prime_limit`.
+    ///
+    /// Returns `None` if a comma has a prime above the limit.
+    ///
+    /// ```
+    /// use serde_scala::{Monzo, Temperament};
+    ///
+    /// let meantone = Temperament::from_commas(5, &[Monzo::new(vec![2, 3, 5], vec![-4, 4, -1])]).unwrap();
+    /// assert_eq!(meantone.mapping, vec![vec![1, 0, -4], vec![0, 1, 4]]);
+    ///
+    /// let generators = meantone.pote_generators().unwrap();
+    /// assert!((generators[1] - 1200.0 - 696.239).abs() < 1e-3);
+    /// ```
+    pub fn from_commas(prime_limit: u64, commas: &[Monzo]) -> Option<Self>
+    {
+        let primes = primes_up_to(prime_limit);
+        if commas.iter().any(|comma| comma.primes.iter().zip(comma.exponents.iter()).any(|(p, &exponent)| exponent != 0 && !primes.contains(p)))
+        {
+            return None
+        }
+
+        // Reducing each prime's comma exponents alongside an identity matrix leaves the vals tempering out every comma
+        // wherever the exponents cancel.
+        let rows = primes.iter()
+            .enumerate()
+            .map(|(i, &prime)| commas.iter()
+                .map(|comma| comma.exponent(prime) as i64)
+                .chain((0..primes.len()).map(|j| (i == j) as i64))
+                .collect())
+            .collect();
+        let mapping = hermite(rows).into_iter()
+            .filter(|row| row[..commas.len()].iter().all(|&x| x == 0))
+            .map(|row| row[commas.len()..].to_vec())
+            .collect();
+        Some(Self {
+            primes,
+            mapping
+        })
+    }
+
+    /// The temperament the vals agree on, or `None` if they don't share the same primes.
+    pub fn from_vals(vals: &[Val]) -> Option<Self>
+    {
+        let primes = vals.first()?.primes.clone();
+        if vals.iter().any(|val| val.primes != primes)
+        {
+            return None
+        }
+        Some(Self {
+            primes,
+            mapping: hermite(vals.iter().map(|val| val.steps.clone()).collect())
+        })
+    }
+
+    /// How many generators there are.
+    pub fn rank(&self) -> usize
+    {
+        self.mapping.len()
+    }
+
+    /// The generator sizes in cents minimizing the Tenney-weighted error over all primes.
+    pub fn te_generators(&self) -> Option<Vec<f64>>
+    {
+        let weighted: Vec<Vec<f64>> = self.mapping.iter()
+            .map(|row| row.iter().zip(self.primes.iter()).map(|(&steps, &prime)| steps as f64/(prime as f64).log2()).collect())
+            .collect();
+        let gram = weighted.iter()
+            .map(|a| weighted.iter().map(|b| a.iter().zip(b.iter()).map(|(x, y)| x*y).sum()).collect())
+            .collect();
+        let rhs = weighted.iter().map(|row| 1200.0*row.iter().sum::<f64>()).collect();
+        solve(gram, rhs)
+    }
+
+    /// The TE generators stretched so the octave is pure, or `None` if the temperament doesn't map 2.
+    pub fn pote_generators(&self) -> Option<Vec<f64>>
+    {
+        let generators = self.te_generators()?;
+        let octave = self.tuning_map(&generators).prime_cents(2)?;
+        if octave == 0.0
+        {
+            return None
+        }
+        Some(generators.into_iter().map(|generator| generator*1200.0/octave).collect())
+    }
+
+    /// The size every prime is tuned to with the given generator sizes.
+    pub fn tuning_map(&self, generators: &[f64]) -> TuningMap
+    {
+        let cents = (0..self.primes.len())
+            .map(|i| self.mapping.iter().zip(generators.iter()).map(|(row, generator)| row[i] as f64*generator).sum())
+            .collect();
+        TuningMap::new(self.primes.clone(), cents)
+    }
+
+    /// The TE tuning of every prime, to temper scales with through [`Scale::tempered_by`](crate::Scale::tempered_by).
+    pub fn te_tuning_map(&self) -> Option<TuningMap>
+    {
+        Some(self.tuning_map(&self.te_generators()?))
+    }
+
+    pub fn pote_tuning_map(&self) -> Option<TuningMap>
+    {
+        Some(self.tuning_map(&self.pote_generators()?))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, Monzo, Temperament, Val};
+
+    #[test]
+    fn optimal_tunings()
+    {
+        let syntonic = Monzo::new(vec![2, 3, 5], vec![-4, 4, -1]);
+        let meantone = Temperament::from_commas(5, std::slice::from_ref(&syntonic)).unwrap();
+        assert_eq!(meantone.rank(), 2);
+        assert_eq!(Temperament::from_vals(&[Val::patent(12, 5), Val::patent(19, 5)]), Some(meantone.clone()));
+
+        let te = meantone.te_generators().unwrap();
+        assert!((te[0] - 1201.397).abs() < 1e-3);
+        assert!((te[1] - 1898.446).abs() < 1e-3);
+        assert!(meantone.te_tuning_map().unwrap().map(syntonic.to_pitch()).unwrap().abs() < 1e-9);
+
+        let tempered = scl!{"ji" 9/8 5/4 3/2 2/1}.tempered_by(&meantone.pote_tuning_map().unwrap()).unwrap();
+        assert!((tempered.pitches[2].to_cents() - 696.239).abs() < 1e-3);
+        assert!((tempered.pitches[3].to_cents() - 1200.0).abs() < 1e-9);
+
+        let twelve = Temperament::from_vals(&[Val::patent(12, 5)]).unwrap();
+        assert!((twelve.pote_generators().unwrap()[0] - 100.0).abs() < 1e-9);
+
+        let marvel = Temperament::from_commas(7, &[Monzo::new(vec![2, 3, 5, 7], vec![-5, 2, 2, -1])]).unwrap();
+        assert_eq!(marvel.rank(), 3);
+        assert!(marvel.mapping.iter().all(|row| Val::new(marvel.primes.clone(), row.clone()).tempers_out(&Monzo::new(vec![2, 3, 5, 7], vec![-5, 2, 2, -1]))));
+        assert_eq!(Temperament::from_commas(5, &[Monzo::new(vec![7], vec![1])]), None);
+    }
+}
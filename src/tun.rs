@@ -0,0 +1,217 @@
+use std::{error::Error, fmt::Display, num::{ParseFloatError, ParseIntError}, str::FromStr};
+
+use crate::{parser::lines, FrequencyTable, Tuning, MIDI_KEYS};
+
+/// The frequency AnaMark .tun files measure cents from, MIDI key 0 in 12-EDO at [`ReferencePitch::A440`](crate::ReferencePitch::A440).
+pub const TUN_BASE_FREQUENCY: f64 = 8.175798915643707;
+
+/// An AnaMark .tun tuning file, as most software synths load them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tun
+{
+    pub name: String,
+    pub frequencies: FrequencyTable
+}
+
+impl From<&Tuning> for Tun
+{
+    fn from(value: &Tuning) -> Self
+    {
+        Self {
+            name: value.scale.name.clone(),
+            frequencies: value.frequencies()
+        }
+    }
+}
+
+impl From<Tuning> for Tun
+{
+    fn from(value: Tuning) -> Self
+    {
+        (&value).into()
+    }
+}
+
+/// Keys without a frequency are left out, which synths read as 12-EDO.
+impl Display for Tun
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        writeln!(f, "; Generated tuning")?;
+        writeln!(f, "[Scale Begin]")?;
+        writeln!(f, "Format = \"AnaMark-TUN\"")?;
+        writeln!(f, "FormatVersion = 200")?;
+        writeln!(f)?;
+        writeln!(f, "[Info]")?;
+        writeln!(f, "Name = \"{}\"", self.name.replace('"', "'"))?;
+        writeln!(f)?;
+        writeln!(f, "[Exact Tuning]")?;
+        writeln!(f, "BaseFreq = {:.10}", TUN_BASE_FREQUENCY)?;
+
+        for (key, frequency) in self.frequencies.frequencies.iter().enumerate()
+        {
+            if let Some(frequency) = frequency
+            {
+                writeln!(f, "note {} = {:.6}", key, 1200.0*(frequency/TUN_BASE_FREQUENCY).log2())?;
+            }
+        }
+
+        writeln!(f)?;
+        writeln!(f, "[Scale End]")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseTunError
+{
+    ParseFloat(ParseFloatError),
+    ParseInt(ParseIntError),
+    /// There is neither a `[Tuning]` nor an `[Exact Tuning]` section.
+    MissingTuning,
+    /// A note line names a key past the MIDI range.
+    KeyOutOfRange(usize)
+}
+impl From<ParseFloatError> for ParseTunError
+{
+    fn from(value: ParseFloatError) -> Self
+    {
+        Self::ParseFloat(value)
+    }
+}
+impl From<ParseIntError> for ParseTunError
+{
+    fn from(value: ParseIntError) -> Self
+    {
+        Self::ParseInt(value)
+    }
+}
+impl Display for ParseTunError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Self::ParseFloat(err) => write!(f, "invalid frequency or cents value: {}", err),
+            Self::ParseInt(err) => write!(f, "invalid note number: {}", err),
+            Self::MissingTuning => write!(f, "no [Tuning] or [Exact Tuning] section"),
+            Self::KeyOutOfRange(key) => write!(f, "note {} is outside the MIDI range", key)
+        }
+    }
+}
+impl Error for ParseTunError
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)>
+    {
+        match self
+        {
+            Self::ParseFloat(err) => Some(err),
+            Self::ParseInt(err) => Some(err),
+            Self::MissingTuning | Self::KeyOutOfRange(_) => None
+        }
+    }
+}
+
+/// Reads the `[Tuning]` and `[Exact Tuning]` sections, the latter taking precedence where both tune a key.
+impl FromStr for Tun
+{
+    type Err = ParseTunError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let mut name = String::new();
+        let mut section = String::new();
+        let mut base_frequency = TUN_BASE_FREQUENCY;
+        let mut coarse = [None; MIDI_KEYS];
+        let mut exact = [None; MIDI_KEYS];
+        let mut has_tuning = false;
+
+        for line in lines(s)
+        {
+            let line = line.split_once(";").map(|(line, _)| line).unwrap_or(line).trim();
+            if let Some(header) = line.strip_prefix("[").and_then(|line| line.strip_suffix("]"))
+            {
+                section = header.trim().to_lowercase();
+                has_tuning |= section == "tuning" || section == "exact tuning";
+                continue
+            }
+            let Some((key, value)) = line.split_once("=")
+            else
+            {
+                continue
+            };
+            let key = key.trim().to_lowercase();
+            let value = value.trim();
+
+            match (section.as_str(), key.as_str())
+            {
+                ("info", "name") => name = value.trim_matches('"').to_string(),
+                ("exact tuning", "basefreq") => base_frequency = value.parse()?,
+                ("tuning" | "exact tuning", key) if key.starts_with("note") =>
+                {
+                    let note: usize = key["note".len()..].trim().parse()?;
+                    let cents: f64 = value.parse()?;
+                    let table = match section.as_str()
+                    {
+                        "tuning" => &mut coarse,
+                        _ => &mut exact
+                    };
+                    *table.get_mut(note).ok_or(ParseTunError::KeyOutOfRange(note))? = Some(cents);
+                },
+                _ => ()
+            }
+        }
+
+        if !has_tuning
+        {
+            return Err(ParseTunError::MissingTuning)
+        }
+
+        let mut frequencies = FrequencyTable::default();
+        for (key, frequency) in frequencies.frequencies.iter_mut().enumerate()
+        {
+            *frequency = exact[key].map(|cents| base_frequency*(cents/1200.0).exp2())
+                .or(coarse[key].map(|cents| TUN_BASE_FREQUENCY*(cents/1200.0).exp2()));
+        }
+
+        Ok(Self {
+            name,
+            frequencies
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{KeyboardMapping, ParseTunError, Scale, Tun, Tuning};
+
+    #[test]
+    fn write_tun()
+    {
+        let tun = Tun::from(Tuning::new(Scale::edo(12), KeyboardMapping::default()));
+        let text = tun.to_string();
+
+        assert!(text.contains("Name = \"12-note equal division of octave\"\n"));
+        assert!(text.contains("note 0 = 0.000000\n"));
+        assert!(text.contains("note 69 = 6900.000000\n"));
+        assert!(text.ends_with("[Scale End]\n"));
+    }
+
+    #[test]
+    fn parse_tun()
+    {
+        let tun = Tun::from(Tuning::new(Scale::edo(19), KeyboardMapping::default()));
+        let parsed: Tun = tun.to_string().parse().unwrap();
+
+        assert_eq!(parsed.name, tun.name);
+        for (a, b) in parsed.frequencies.frequencies.iter().zip(tun.frequencies.frequencies.iter())
+        {
+            assert!((a.unwrap() - b.unwrap()).abs() < 1e-6);
+        }
+
+        let coarse: Tun = "[Tuning]\nnote 69=6900\n".parse().unwrap();
+        assert!((coarse.frequencies.get(69).unwrap() - 440.0).abs() < 1e-6);
+        assert_eq!(coarse.frequencies.get(70), None);
+        assert_eq!("[Info]\n".parse::<Tun>(), Err(ParseTunError::MissingTuning));
+    }
+}
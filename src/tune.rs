@@ -0,0 +1,79 @@
+use tune::{pitch::Ratio as TuneRatio, scala::{Scl, SclBuildError}};
+
+use crate::{ParseScaleError, Pitch, Scale};
+
+impl From<Pitch> for TuneRatio
+{
+    fn from(value: Pitch) -> Self
+    {
+        match value
+        {
+            Pitch::Cents(cents) => TuneRatio::from_cents(cents),
+            Pitch::Ratio(ratio) => TuneRatio::from_float(*ratio.numer() as f64/(*ratio.denom() as f64))
+        }
+    }
+}
+
+/// `tune` ratios are floating point, so they always become cents.
+impl From<TuneRatio> for Pitch
+{
+    fn from(value: TuneRatio) -> Self
+    {
+        Pitch::Cents(value.as_cents())
+    }
+}
+
+/// Ratios with terms too large for `tune` are given in cents instead. Labels are lost.
+impl TryFrom<&Scale> for Scl
+{
+    type Error = SclBuildError;
+
+    fn try_from(value: &Scale) -> Result<Self, Self::Error>
+    {
+        value.pitches.iter()
+            .fold(Scl::builder(), |builder, &pitch| match pitch
+            {
+                Pitch::Ratio(ratio) => match (u32::try_from(*ratio.numer()), u32::try_from(*ratio.denom()))
+                {
+                    (Ok(numer), Ok(1)) => builder.push_int(numer),
+                    (Ok(numer), Ok(denom)) => builder.push_fraction(numer, denom),
+                    _ => builder.push_cents(pitch.to_cents())
+                },
+                Pitch::Cents(cents) => builder.push_cents(cents)
+            })
+            .build_with_description(value.name.clone())
+    }
+}
+
+/// Goes through the .scl text `tune` exports, which rounds cents to three decimals.
+impl TryFrom<&Scl> for Scale
+{
+    type Error = ParseScaleError;
+
+    fn try_from(value: &Scl) -> Result<Self, Self::Error>
+    {
+        value.export().to_string().parse()
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use tune::{pitch::Ratio as TuneRatio, scala::Scl};
+
+    use crate::{scl, Pitch, Scale};
+
+    #[test]
+    fn tune_interop()
+    {
+        let scale = scl!{"tune" 9/8 386.3 3/2 2/1};
+        let tune_scl = Scl::try_from(&scale).unwrap();
+        assert_eq!(tune_scl.description(), "tune");
+        assert_eq!(tune_scl.num_items(), 4);
+        assert!((tune_scl.relative_pitch_of(2).as_cents() - 386.3).abs() < 1e-9);
+        assert_eq!(Scale::try_from(&tune_scl).unwrap(), scale);
+
+        assert!((TuneRatio::from(Pitch::ratio(3, 2)).as_float() - 1.5).abs() < 1e-12);
+        assert!((Pitch::from(TuneRatio::from_cents(700.0)).to_cents() - 700.0).abs() < 1e-9);
+    }
+}
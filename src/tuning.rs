@@ -0,0 +1,337 @@
+use std::{fs, path::Path};
+
+use crate::{KeyboardMapping, Pitch, Scale, SerdeScalaError};
+
+/// How many keys MIDI has.
+pub const MIDI_KEYS: usize = 128;
+
+/// A frequency in Hz for every MIDI key, or `None` for keys left untuned.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrequencyTable
+{
+    pub frequencies: [Option<f64>; MIDI_KEYS]
+}
+
+impl FrequencyTable
+{
+    pub fn get(&self, key: u8) -> Option<f64>
+    {
+        self.frequencies.get(key as usize).copied().flatten()
+    }
+}
+
+impl Default for FrequencyTable
+{
+    fn default() -> Self
+    {
+        Self {
+            frequencies: [None; MIDI_KEYS]
+        }
+    }
+}
+
+/// What a frequency table gives keys the keyboard mapping leaves unmapped within the range of keys to retune.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnmappedKeys
+{
+    /// Nothing, leaving them untuned.
+    #[default]
+    Skip,
+    /// The frequency of the nearest mapped key, the lower one if two are as near.
+    Nearest,
+    /// A pitch on the line between the mapped keys on either side, or past the two nearest on one side
+    /// at the edges, spacing the keys in between evenly in cents.
+    Extrapolate
+}
+
+/// A scale laid out on the keyboard by a keyboard mapping, the way Scala pairs .scl and .kbm files.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tuning
+{
+    pub scale: Scale,
+    pub mapping: KeyboardMapping
+}
+
+impl Tuning
+{
+    pub fn new(scale: Scale, mapping: KeyboardMapping) -> Self
+    {
+        Self {
+            scale,
+            mapping
+        }
+    }
+
+    /// Loads a .scl file along with the .kbm file next to it of the same name, like `19edo.scl` and `19edo.kbm`,
+    /// falling back to the standard linear mapping if there is none.
+    pub fn load_pair(scl_path: impl AsRef<Path>) -> Result<Self, SerdeScalaError>
+    {
+        let scl_path = scl_path.as_ref();
+        let scale = String::from_utf8_lossy(&fs::read(scl_path)?).parse()?;
+
+        let kbm_path = scl_path.with_extension("kbm");
+        let mapping = if kbm_path.is_file()
+        {
+            String::from_utf8_lossy(&fs::read(kbm_path)?).parse()?
+        }
+        else
+        {
+            KeyboardMapping::default()
+        };
+        Ok(Self::new(scale, mapping))
+    }
+
+    /// The scale degree a key plays, counted from the middle note and extended past the period,
+    /// ignoring the range of keys to retune.
+    pub fn key_degree(&self, key: u8) -> Option<i64>
+    {
+        let offset = key as i64 - self.mapping.middle_note as i64;
+        if self.mapping.size == 0
+        {
+            return Some(offset)
+        }
+
+        let size = self.mapping.size as i64;
+        let entry = (*self.mapping.mapping.get(offset.rem_euclid(size) as usize)?)?;
+        Some(entry as i64 + offset.div_euclid(size)*self.mapping.formal_octave as i64)
+    }
+
+    /// The cents of a scale degree above degree 0, extended past the period.
+    pub fn degree_cents(&self, degree: i64) -> Option<f64>
+    {
+        let period = self.scale.period()?.to_cents();
+        let count = self.scale.pitches.len() as i64;
+        let cents = self.scale.degree(degree.rem_euclid(count) as usize)?.to_cents();
+        Some(cents + degree.div_euclid(count) as f64*period)
+    }
+
+    /// The cents a key plays above degree 0, ignoring the range of keys to retune.
+    ///
+    /// Every time the mapping pattern repeats, it's raised by the interval of its formal octave degree, which
+    /// needn't be the period of the scale, such as a fifth or a 3/1 tritave. Linear mappings are raised by the period.
+    pub fn key_cents(&self, key: u8) -> Option<f64>
+    {
+        if self.mapping.size == 0
+        {
+            return self.degree_cents(self.key_degree(key)?)
+        }
+
+        let offset = key as i64 - self.mapping.middle_note as i64;
+        let size = self.mapping.size as i64;
+        let entry = (*self.mapping.mapping.get(offset.rem_euclid(size) as usize)?)?;
+        let formal_octave = self.degree_cents(self.mapping.formal_octave as i64)?;
+        Some(self.degree_cents(entry as i64)? + offset.div_euclid(size) as f64*formal_octave)
+    }
+
+    /// `None` outside the range of keys to retune, for unmapped keys, and for scales without pitches.
+    ///
+    /// If the reference note is unmapped itself, the middle note is taken to play the reference frequency.
+    pub fn frequency(&self, key: u8) -> Option<f64>
+    {
+        if key < self.mapping.first_note || key > self.mapping.last_note
+        {
+            return None
+        }
+        let cents = self.key_cents(key)?;
+        let reference = self.mapping.reference();
+        let reference_cents = self.key_cents(reference.note).unwrap_or(0.0);
+        Some(reference.pitch_frequency(Pitch::cents(cents - reference_cents)))
+    }
+
+    /// The nearest scale degree to a frequency, for classifying played pitches against the tuning.
+    ///
+    /// Gives how many periods up from degree 0 it is, the degree in that period, and how many cents the frequency
+    /// is off from it. `None` for scales without pitches and frequencies that aren't positive.
+    ///
+    /// ```
+    /// use serde_scala::{KeyboardMapping, Scale, Tuning};
+    ///
+    /// let tuning = Tuning::new(Scale::edo(12), KeyboardMapping::default());
+    /// let (period, degree, error) = tuning.degree_of(446.0).unwrap();
+    /// assert_eq!((period, degree), (0, 9));
+    /// assert!((error - 23.45).abs() < 0.01);
+    /// ```
+    pub fn degree_of(&self, frequency: f64) -> Option<(i64, usize, f64)>
+    {
+        if frequency.is_nan() || frequency <= 0.0
+        {
+            return None
+        }
+        let period = self.scale.period()?.to_cents();
+        let reference = self.mapping.reference();
+        let reference_cents = self.key_cents(reference.note).unwrap_or(0.0);
+        let cents = 1200.0*(frequency/reference.frequency).log2() + reference_cents;
+
+        let periods = (cents/period).floor();
+        let reduced = cents - periods*period;
+        let count = self.scale.pitches.len();
+        let (degree, degree_cents) = (0..=count)
+            .filter_map(|degree| Some((degree, self.degree_cents(degree as i64)?)))
+            .min_by(|(_, a), (_, b)| (reduced - a).abs().total_cmp(&(reduced - b).abs()))?;
+
+        // The period itself is degree 0 of the next one.
+        let periods = periods as i64 + (degree == count) as i64;
+        Some((periods, degree % count, reduced - degree_cents))
+    }
+
+    pub fn frequencies(&self) -> FrequencyTable
+    {
+        let mut table = FrequencyTable::default();
+        for (key, frequency) in table.frequencies.iter_mut().enumerate()
+        {
+            *frequency = self.frequency(key as u8);
+        }
+        table
+    }
+
+    /// The frequencies of every key, filling in unmapped ones as given.
+    pub fn frequencies_with(&self, unmapped: UnmappedKeys) -> FrequencyTable
+    {
+        let table = self.frequencies();
+        if unmapped == UnmappedKeys::Skip
+        {
+            return table
+        }
+
+        let mapped: Vec<(usize, f64)> = table.frequencies.iter()
+            .enumerate()
+            .filter_map(|(key, frequency)| Some((key, (*frequency)?)))
+            .collect();
+        let mut filled = table;
+        for key in self.mapping.first_note as usize..=(self.mapping.last_note as usize).min(MIDI_KEYS - 1)
+        {
+            if table.frequencies[key].is_some()
+            {
+                continue
+            }
+            let after = mapped.partition_point(|&(mapped_key, _)| mapped_key < key);
+            filled.frequencies[key] = match unmapped
+            {
+                UnmappedKeys::Skip => None,
+                UnmappedKeys::Nearest => mapped[after.saturating_sub(1)..mapped.len().min(after + 1)].iter()
+                    .min_by_key(|&&(mapped_key, _)| mapped_key.abs_diff(key))
+                    .map(|&(_, frequency)| frequency),
+                UnmappedKeys::Extrapolate =>
+                {
+                    // The two mapped keys around the key, or the two nearest if it's past the edge.
+                    let i = after.saturating_sub(1).min(mapped.len().saturating_sub(2));
+                    match (mapped.get(i), mapped.get(i + 1))
+                    {
+                        (Some(&(key_a, a)), Some(&(key_b, b))) =>
+                        {
+                            let step = (b/a).log2()/(key_b - key_a) as f64;
+                            Some(a*2f64.powf(step*(key as f64 - key_a as f64)))
+                        },
+                        (Some(&(_, a)), None) => Some(a),
+                        _ => None
+                    }
+                }
+            };
+        }
+        filled
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::fs;
+
+    use crate::{kbm, scl, KeyboardMapping, Scale, Tuning, UnmappedKeys};
+
+    #[test]
+    fn frequencies()
+    {
+        let tuning = Tuning::new(Scale::edo(12), KeyboardMapping::default());
+        let table = tuning.frequencies();
+
+        assert_eq!(table.get(69), Some(440.0));
+        assert!((table.get(60).unwrap() - 261.625565).abs() < 1e-6);
+        assert!((table.get(81).unwrap() - 880.0).abs() < 1e-9);
+
+        let pentatonic = Tuning::new(
+            scl!{"pentatonic" 9/8 5/4 3/2 5/3 2/1},
+            kbm!{12 0 127 60 60 261.0 5 0 x 1 x 2 3 x 4 x x x x}
+        );
+
+        assert_eq!(pentatonic.frequency(61), None);
+        assert!((pentatonic.frequency(62).unwrap() - 261.0*9.0/8.0).abs() < 1e-9);
+        assert!((pentatonic.frequency(72).unwrap() - 522.0).abs() < 1e-9);
+        assert!((pentatonic.frequency(55).unwrap() - 261.0/2.0*5.0/3.0).abs() < 1e-9);
+
+        assert_eq!(pentatonic.frequencies_with(UnmappedKeys::Skip), pentatonic.frequencies());
+        let nearest = pentatonic.frequencies_with(UnmappedKeys::Nearest);
+        assert_eq!(nearest.get(61), Some(261.0));
+        assert_eq!(nearest.get(63), pentatonic.frequency(62));
+        let extrapolated = pentatonic.frequencies_with(UnmappedKeys::Extrapolate);
+        assert!((extrapolated.get(61).unwrap() - 261.0*(9.0f64/8.0).sqrt()).abs() < 1e-9);
+        let last = Tuning::new(pentatonic.scale.clone(), kbm!{12 0 61 60 60 261.0 5 0 x 1 x 2 3 x 4 x x x x});
+        let last = last.frequencies_with(UnmappedKeys::Extrapolate);
+        assert!((last.get(61).unwrap() - 261.0*(6.0f64/5.0).powf(0.2)).abs() < 1e-9);
+        assert_eq!(last.get(62), None);
+    }
+
+    #[test]
+    fn non_octave_formal_octave()
+    {
+        // Bohlen-Pierce just intonation repeating at the tritave, as tuned by Scala.
+        let bp = scl!{"bp" 27/25 25/21 9/7 7/5 75/49 5/3 9/5 49/25 15/7 7/3 63/25 25/9 3/1};
+        let tuning = Tuning::new(bp.clone(), kbm!{13 0 127 60 60 261.625565 13 0 1 2 3 4 5 6 7 8 9 10 11 12});
+        let close = |key, frequency: f64| (tuning.frequency(key).unwrap() - frequency).abs() < 1e-6;
+        assert!(close(60, 261.625565));
+        assert!(close(61, 282.555610));
+        assert!(close(73, 784.876695));
+        assert!(close(47, 87.208522));
+        assert!(close(86, 2354.630085));
+
+        // A nine-key pattern on the tritave, leaving keys unmapped.
+        let nine = Tuning::new(bp, kbm!{9 0 127 60 60 261.625565 13 0 2 3 5 6 8 9 11 x});
+        assert_eq!(nine.frequency(68), None);
+        assert!((nine.frequency(69).unwrap() - 784.876695).abs() < 1e-6);
+        assert!((nine.frequency(51).unwrap() - 87.208522).abs() < 1e-6);
+
+        // The pattern repeats at the fifth of the formal octave degree, not three degrees up the scale.
+        let fifths = Tuning::new(scl!{"pentatonic" 9/8 5/4 3/2 5/3 2/1}, kbm!{3 0 127 60 60 240.0 3 0 1 2});
+        assert!((fifths.frequency(63).unwrap() - 360.0).abs() < 1e-9);
+        assert!((fifths.frequency(64).unwrap() - 405.0).abs() < 1e-9);
+        assert!((fifths.frequency(59).unwrap() - 200.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn degree_of()
+    {
+        let pentatonic = Tuning::new(
+            scl!{"pentatonic" 9/8 5/4 3/2 5/3 2/1},
+            kbm!{12 0 127 60 60 261.0 5 0 x 1 x 2 3 x 4 x x x x}
+        );
+        assert_eq!(pentatonic.degree_of(261.0), Some((0, 0, 0.0)));
+        let (period, degree, error) = pentatonic.degree_of(261.0*3.0).unwrap();
+        assert_eq!((period, degree), (1, 3));
+        assert!(error.abs() < 1e-9);
+        let (period, degree, error) = pentatonic.degree_of(261.0*1.98).unwrap();
+        assert_eq!((period, degree), (1, 0));
+        assert!((error + 17.4).abs() < 0.1);
+        assert_eq!(pentatonic.degree_of(130.5).unwrap().0, -1);
+        assert_eq!(pentatonic.degree_of(0.0), None);
+    }
+
+    #[test]
+    fn load_pair()
+    {
+        let directory = std::env::temp_dir().join(format!("serde_scala_pair_{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("paired.scl"), "paired\n2\n3/2\n2/1\n").unwrap();
+        fs::write(directory.join("paired.kbm"), "2\n0\n127\n60\n60\n261.0\n2\n0\n1\n").unwrap();
+        fs::write(directory.join("alone.scl"), "alone\n1\n2/1\n").unwrap();
+
+        let paired = Tuning::load_pair(directory.join("paired.scl")).unwrap();
+        assert_eq!(paired.scale, scl!{"paired" 3/2 2/1});
+        assert_eq!(paired.mapping, kbm!{2 0 127 60 60 261.0 2 0 1});
+
+        let alone = Tuning::load_pair(directory.join("alone.scl")).unwrap();
+        assert_eq!(alone.mapping, KeyboardMapping::default());
+        assert!(Tuning::load_pair(directory.join("missing.scl")).is_err());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
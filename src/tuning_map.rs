@@ -0,0 +1,160 @@
+use crate::{linalg::solve, prime::primes_up_to, Monzo, Pitch, Scale, Val};
+
+/// The size in cents every prime is tuned to, which tunes every ratio made of those primes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TuningMap
+{
+    pub primes: Vec<u64>,
+    pub cents: Vec<f64>
+}
+
+impl TuningMap
+{
+    pub fn new(primes: Vec<u64>, cents: Vec<f64>) -> Self
+    {
+        assert_eq!(primes.len(), cents.len(), "a tuning map needs exactly one size per prime");
+        Self {
+            primes,
+            cents
+        }
+    }
+
+    /// Every prime up to `prime_limit` tuned pure.
+    pub fn just(prime_limit: u64) -> Self
+    {
+        let primes = primes_up_to(prime_limit);
+        let cents = primes.iter().map(|&prime| 1200.0*(prime as f64).log2()).collect();
+        Self::new(primes, cents)
+    }
+
+    /// The equal temperament a val describes, with pure octaves, or `None` if the val maps the octave to no steps.
+    pub fn from_val(val: &Val) -> Option<Self>
+    {
+        let edo = val.edo();
+        if edo == 0
+        {
+            return None
+        }
+        let step = 1200.0/edo as f64;
+        Some(Self::new(val.primes.clone(), val.steps.iter().map(|&steps| steps as f64*step).collect()))
+    }
+
+    /// The equal temperament a val describes, with the step size minimizing the Tenney-weighted (TE) error over its primes.
+    pub fn te_from_val(val: &Val) -> Option<Self>
+    {
+        let weighted: Vec<f64> = val.primes.iter()
+            .zip(val.steps.iter())
+            .map(|(&prime, &steps)| steps as f64/(prime as f64).log2())
+            .collect();
+        let squares: f64 = weighted.iter().map(|w| w*w).sum();
+        if squares == 0.0
+        {
+            return None
+        }
+        let step = 1200.0*weighted.iter().sum::<f64>()/squares;
+        Some(Self::new(val.primes.clone(), val.steps.iter().map(|&steps| steps as f64*step).collect()))
+    }
+
+    /// The TE-optimal tuning of the primes up to `prime_limit` in which every comma vanishes.
+    ///
+    /// Returns `None` if a comma has a prime above the limit, or the commas aren't independent.
+    pub fn tempering_out(prime_limit: u64, commas: &[Monzo]) -> Option<Self>
+    {
+        let primes = primes_up_to(prime_limit);
+        let logs: Vec<f64> = primes.iter().map(|&prime| (prime as f64).log2()).collect();
+        let weighted = commas.iter()
+            .map(|comma| {
+                if comma.primes.iter().zip(comma.exponents.iter()).any(|(p, &exponent)| exponent != 0 && !primes.contains(p))
+                {
+                    return None
+                }
+                Some(primes.iter().zip(logs.iter()).map(|(&p, &log)| comma.exponent(p) as f64*log).collect::<Vec<f64>>())
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        // Projects the just tuning, 1200 for every prime in Tenney-weighted coordinates, off the span of the commas.
+        let gram = weighted.iter()
+            .map(|a| weighted.iter().map(|b| a.iter().zip(b.iter()).map(|(x, y)| x*y).sum()).collect())
+            .collect();
+        let rhs = weighted.iter().map(|a| 1200.0*a.iter().sum::<f64>()).collect();
+        let coefficients = solve(gram, rhs)?;
+        let cents = logs.iter()
+            .enumerate()
+            .map(|(i, log)| {
+                let projection: f64 = weighted.iter().zip(coefficients.iter()).map(|(comma, x)| comma[i]*x).sum();
+                (1200.0 - projection)*log
+            })
+            .collect();
+        Some(Self::new(primes, cents))
+    }
+
+    pub fn prime_cents(&self, prime: u64) -> Option<f64>
+    {
+        self.primes.iter().position(|&p| p == prime).map(|i| self.cents[i])
+    }
+
+    /// Tunes a ratio pitch, or returns `None` for cents or ratios with primes this map doesn't cover.
+    pub fn map(&self, pitch: Pitch) -> Option<f64>
+    {
+        let monzo = pitch.to_monzo(&self.primes)?;
+        Some(monzo.exponents.iter().zip(self.cents.iter()).map(|(&exponent, cents)| exponent as f64*cents).sum())
+    }
+}
+
+impl Scale
+{
+    /// Tunes every ratio through a tuning map, like one tempering out some commas, into cents. Cents are kept as they are.
+    ///
+    /// Returns `None` if a ratio has a prime the map doesn't cover.
+    ///
+    /// ```
+    /// use serde_scala::{scl, Monzo, TuningMap};
+    ///
+    /// let meantone = TuningMap::tempering_out(5, &[Monzo::new(vec![2, 3, 5], vec![-4, 4, -1])]).unwrap();
+    /// let tempered = scl!{"ji" 9/8 5/4 3/2 2/1}.tempered_by(&meantone).unwrap();
+    /// let cents: Vec<f64> = tempered.pitches.iter().map(|pitch| pitch.to_cents()).collect();
+    /// assert!((2.0*cents[0] - cents[1]).abs() < 1e-9);
+    /// ```
+    pub fn tempered_by(&self, map: &TuningMap) -> Option<Scale>
+    {
+        let cents = self.pitches.iter()
+            .map(|&pitch| match pitch
+            {
+                Pitch::Cents(cents) => Some(cents),
+                Pitch::Ratio(_) => map.map(pitch)
+            })
+            .collect::<Option<Vec<f64>>>()?;
+        let mut scale = Scale::from_cents(self.name.clone(), cents);
+        scale.labels = self.labels.clone();
+        scale.metadata = self.metadata.clone();
+        Some(scale)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, Monzo, TuningMap, Val};
+
+    #[test]
+    fn tempered_tunings()
+    {
+        let scale = scl!{"ji" 9/8 5/4 4/3 3/2 5/3 15/8 2/1 1000.0};
+
+        let twelve = scale.tempered_by(&TuningMap::from_val(&Val::patent(12, 5)).unwrap()).unwrap();
+        let cents: Vec<f64> = twelve.pitches.iter().map(|pitch| pitch.to_cents()).collect();
+        assert_eq!(cents, [200.0, 400.0, 500.0, 700.0, 900.0, 1100.0, 1200.0, 1000.0]);
+
+        let te = TuningMap::te_from_val(&Val::patent(12, 5)).unwrap();
+        assert!((te.prime_cents(2).unwrap() - 1198.4).abs() < 0.1);
+
+        let syntonic = Monzo::new(vec![2, 3, 5], vec![-4, 4, -1]);
+        let meantone = TuningMap::tempering_out(5, std::slice::from_ref(&syntonic)).unwrap();
+        assert!(meantone.map(syntonic.to_pitch()).unwrap().abs() < 1e-9);
+        assert!((meantone.prime_cents(2).unwrap() - 1201.397).abs() < 1e-3);
+        assert!((meantone.prime_cents(3).unwrap() - 1898.446).abs() < 1e-3);
+
+        assert_eq!(scl!{"septimal" 7/4 2/1}.tempered_by(&meantone), None);
+        assert_eq!(TuningMap::tempering_out(5, &[syntonic.clone(), syntonic*2]), None);
+    }
+}
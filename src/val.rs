@@ -0,0 +1,110 @@
+use std::fmt::Display;
+
+use crate::{prime::primes_up_to, Monzo, Pitch};
+
+/// A mapping from primes to steps of a tuning, e.g. `<12 19 28]` for 12edo in the 5-limit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Val
+{
+    pub primes: Vec<u64>,
+    pub steps: Vec<i64>
+}
+
+impl Val
+{
+    pub fn new(primes: Vec<u64>, steps: Vec<i64>) -> Self
+    {
+        assert_eq!(primes.len(), steps.len(), "a val needs exactly one step count per prime");
+        Self {
+            primes,
+            steps
+        }
+    }
+
+    /// The val mapping each prime up to `prime_limit` to its closest step in `edo`-note equal division of the octave.
+    pub fn patent(edo: u32, prime_limit: u64) -> Self
+    {
+        let primes = primes_up_to(prime_limit);
+        let steps = primes.iter()
+            .map(|&prime| (edo as f64*(prime as f64).log2()).round() as i64)
+            .collect();
+        Self::new(primes, steps)
+    }
+
+    /// The number of steps in one octave.
+    pub fn edo(&self) -> i64
+    {
+        self.primes.iter()
+            .position(|&prime| prime == 2)
+            .map(|i| self.steps[i])
+            .unwrap_or(0)
+    }
+
+    /// Maps a monzo to steps, or `None` if it uses a prime this val doesn't cover.
+    pub fn apply(&self, monzo: &Monzo) -> Option<i64>
+    {
+        let mut steps = 0;
+        for (&prime, &exponent) in monzo.primes.iter().zip(monzo.exponents.iter())
+        {
+            if exponent == 0
+            {
+                continue
+            }
+            let i = self.primes.iter().position(|&p| p == prime)?;
+            steps += self.steps[i]*exponent as i64;
+        }
+        Some(steps)
+    }
+
+    /// Maps a ratio pitch to steps, or `None` for cents or ratios outside the val's primes.
+    pub fn map(&self, pitch: Pitch) -> Option<i64>
+    {
+        self.apply(&pitch.to_monzo(&self.primes)?)
+    }
+
+    pub fn tempers_out(&self, comma: &Monzo) -> bool
+    {
+        self.apply(comma) == Some(0)
+    }
+}
+
+impl Display for Val
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "<")?;
+        for (i, steps) in self.steps.iter().enumerate()
+        {
+            if i > 0
+            {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", steps)?;
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use num_rational::Ratio;
+
+    use crate::{Monzo, Pitch, Val};
+
+    #[test]
+    fn patent_vals()
+    {
+        let val = Val::patent(12, 5);
+        assert_eq!(val.to_string(), "<12 19 28]");
+        assert_eq!(val.edo(), 12);
+        assert_eq!(Val::patent(31, 7).steps, vec![31, 49, 72, 87]);
+
+        let syntonic = Monzo::new(vec![2, 3, 5], vec![-4, 4, -1]);
+        assert!(val.tempers_out(&syntonic));
+        assert!(!Val::patent(53, 5).tempers_out(&syntonic));
+
+        assert_eq!(val.map(Pitch::Ratio(Ratio::new(5, 4))), Some(4));
+        assert_eq!(val.map(Pitch::Ratio(Ratio::new(7, 4))), None);
+    }
+}
@@ -0,0 +1,249 @@
+use std::{fmt::Display, fs, io, path::{Path, PathBuf}};
+
+use crate::{Diagnostic, KeyboardMapping, MappingIssue, ParseKeyboardMappingError, Scale, Severity};
+
+/// A problem with a file found by [`validate_dir`].
+#[derive(Debug)]
+pub enum ArchiveIssue
+{
+    Read(io::Error),
+    /// The file isn't valid UTF-8, and was read with the invalid bytes replaced.
+    InvalidUtf8,
+    Scale(Diagnostic),
+    KeyboardMapping(ParseKeyboardMappingError),
+    /// The keyboard mapping doesn't fit the scale of the same name next to it.
+    Mapping(MappingIssue)
+}
+impl Display for ArchiveIssue
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Self::Read(err) => write!(f, "could not read: {}", err),
+            Self::InvalidUtf8 => write!(f, "invalid UTF-8"),
+            Self::Scale(diagnostic) => write!(f, "line {}: {}", diagnostic.line, diagnostic.error),
+            Self::KeyboardMapping(err) => write!(f, "could not parse keyboard mapping: {}", err),
+            Self::Mapping(issue) => write!(f, "{}", issue)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FileReport
+{
+    pub path: PathBuf,
+    /// Problems keeping the file from being read as it's meant to.
+    pub errors: Vec<ArchiveIssue>,
+    /// Problems the file was read despite.
+    pub warnings: Vec<ArchiveIssue>
+}
+
+impl FileReport
+{
+    fn new(path: PathBuf) -> Self
+    {
+        Self {
+            path,
+            errors: vec![],
+            warnings: vec![]
+        }
+    }
+
+    pub fn is_ok(&self) -> bool
+    {
+        self.errors.is_empty()
+    }
+}
+
+/// Every .scl and .kbm file found by [`validate_dir`], with what's wrong with them, sorted by path.
+#[derive(Debug, Default)]
+pub struct ArchiveReport
+{
+    pub files: Vec<FileReport>
+}
+
+impl ArchiveReport
+{
+    /// The files with errors.
+    pub fn failed(&self) -> impl Iterator<Item = &FileReport>
+    {
+        self.files.iter().filter(|file| !file.is_ok())
+    }
+
+    pub fn error_count(&self) -> usize
+    {
+        self.files.iter().map(|file| file.errors.len()).sum()
+    }
+
+    pub fn warning_count(&self) -> usize
+    {
+        self.files.iter().map(|file| file.warnings.len()).sum()
+    }
+}
+
+impl Display for ArchiveReport
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        writeln!(
+            f,
+            "{} files, {} with errors, {} errors, {} warnings",
+            self.files.len(),
+            self.failed().count(),
+            self.error_count(),
+            self.warning_count()
+        )?;
+        for file in self.files.iter()
+        {
+            for (severity, issues) in [("error", &file.errors), ("warning", &file.warnings)]
+            {
+                for issue in issues.iter()
+                {
+                    writeln!(f, "{}: {}: {}", file.path.display(), severity, issue)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool
+{
+    path.extension().is_some_and(|found| found.eq_ignore_ascii_case(extension))
+}
+
+/// Finds the .scl and .kbm files under a directory, or reports why it couldn't be read.
+fn find_files(directory: &Path, paths: &mut Vec<PathBuf>, report: &mut ArchiveReport)
+{
+    let entries = match fs::read_dir(directory)
+    {
+        Ok(entries) => entries,
+        Err(err) =>
+        {
+            let mut file = FileReport::new(directory.to_path_buf());
+            file.errors.push(ArchiveIssue::Read(err));
+            report.files.push(file);
+            return
+        }
+    };
+    for entry in entries.flatten()
+    {
+        let path = entry.path();
+        if path.is_dir()
+        {
+            find_files(&path, paths, report);
+        }
+        else if has_extension(&path, "scl") || has_extension(&path, "kbm")
+        {
+            paths.push(path);
+        }
+    }
+}
+
+/// Reads a file leniently, replacing invalid UTF-8.
+fn read(path: &Path, file: &mut FileReport) -> Option<String>
+{
+    match fs::read(path)
+    {
+        Ok(bytes) => Some(String::from_utf8(bytes).unwrap_or_else(|err| {
+            file.warnings.push(ArchiveIssue::InvalidUtf8);
+            String::from_utf8_lossy(err.as_bytes()).into_owned()
+        })),
+        Err(err) =>
+        {
+            file.errors.push(ArchiveIssue::Read(err));
+            None
+        }
+    }
+}
+
+/// Parses every .scl and .kbm file under a directory and its subdirectories, collecting what's wrong with each
+/// instead of stopping at the first problem.
+///
+/// Scales are parsed with [`Scale::parse_with_diagnostics`], and keyboard mappings are checked against the scale
+/// of the same name next to them, if there is one.
+pub fn validate_dir(path: impl AsRef<Path>) -> ArchiveReport
+{
+    let mut report = ArchiveReport::default();
+    let mut paths = vec![];
+    find_files(path.as_ref(), &mut paths, &mut report);
+    paths.sort();
+
+    for path in paths
+    {
+        let mut file = FileReport::new(path.clone());
+        if let Some(text) = read(&path, &mut file)
+        {
+            if has_extension(&path, "scl")
+            {
+                let (_, diagnostics) = Scale::parse_with_diagnostics(&text);
+                for diagnostic in diagnostics
+                {
+                    match diagnostic.severity
+                    {
+                        Severity::Error => file.errors.push(ArchiveIssue::Scale(diagnostic)),
+                        Severity::Warning => file.warnings.push(ArchiveIssue::Scale(diagnostic))
+                    }
+                }
+            }
+            else
+            {
+                match text.parse::<KeyboardMapping>()
+                {
+                    Ok(mapping) =>
+                    {
+                        let scale = fs::read(path.with_extension("scl"))
+                            .ok()
+                            .and_then(|bytes| String::from_utf8_lossy(&bytes).parse::<Scale>().ok());
+                        if let Some(Err(issues)) = scale.map(|scale| mapping.validate(&scale))
+                        {
+                            file.warnings.extend(issues.into_iter().map(ArchiveIssue::Mapping));
+                        }
+                    },
+                    Err(err) => file.errors.push(ArchiveIssue::KeyboardMapping(err))
+                }
+            }
+        }
+        report.files.push(file);
+    }
+    report.files.sort_by(|a, b| a.path.cmp(&b.path));
+    report
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::fs;
+
+    use crate::{validate_dir, ArchiveIssue, MappingIssue};
+
+    #[test]
+    fn validate_dir_report()
+    {
+        let directory = std::env::temp_dir().join(format!("serde_scala_validate_{}", std::process::id()));
+        fs::create_dir_all(directory.join("nested")).unwrap();
+        fs::write(directory.join("good.scl"), "good\n2\n3/2\n2/1\n").unwrap();
+        fs::write(directory.join("good.kbm"), "2\n0\n127\n60\n60\n261.0\n2\n0\n5\n").unwrap();
+        fs::write(directory.join("nested/bad.scl"), b"bad\xff\n2\n3/\n2/1\n").unwrap();
+        fs::write(directory.join("notes.txt"), "not a scale").unwrap();
+
+        let report = validate_dir(&directory);
+        assert_eq!(report.files.len(), 3);
+        assert_eq!(report.failed().count(), 1);
+        assert_eq!((report.error_count(), report.warning_count()), (1, 2));
+
+        let kbm = &report.files[0];
+        assert!(kbm.is_ok());
+        assert!(matches!(kbm.warnings[..], [ArchiveIssue::Mapping(MappingIssue::DegreeOutOfRange { entry: 1, degree: 5 })]));
+
+        let bad = &report.files[2];
+        assert!(bad.path.ends_with("nested/bad.scl"));
+        assert!(matches!(bad.warnings[..], [ArchiveIssue::InvalidUtf8]));
+        assert!(matches!(&bad.errors[..], [ArchiveIssue::Scale(diagnostic)] if diagnostic.line == 3));
+        assert!(report.to_string().starts_with("3 files, 1 with errors, 1 errors, 2 warnings\n"));
+
+        assert_eq!(validate_dir(directory.join("missing")).failed().count(), 1);
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
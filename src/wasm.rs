@@ -0,0 +1,118 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{EdoWeighting, Propriety, Scale};
+
+/// A [`Scale`] as seen from JavaScript.
+#[wasm_bindgen(js_name = Scale)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct WasmScale
+{
+    scale: Scale
+}
+
+#[wasm_bindgen(js_class = Scale)]
+impl WasmScale
+{
+    /// Parses the text of a .scl file.
+    pub fn parse(text: &str) -> Result<WasmScale, JsError>
+    {
+        let scale = text.parse::<Scale>()
+            .map_err(|err| JsError::new(&err.to_string()))?;
+        Ok(scale.into())
+    }
+
+    #[wasm_bindgen(js_name = fromCents)]
+    pub fn from_cents(name: String, cents: &[f64]) -> WasmScale
+    {
+        Scale::from_cents(name, cents.iter().copied()).into()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String
+    {
+        self.scale.name.clone()
+    }
+
+    /// Every pitch in cents, as a `Float64Array`.
+    #[wasm_bindgen(getter)]
+    pub fn cents(&self) -> Vec<f64>
+    {
+        self.scale.pitches.iter().map(|pitch| pitch.to_cents()).collect()
+    }
+
+    /// Every pitch as written in a .scl file.
+    #[wasm_bindgen(getter)]
+    pub fn pitches(&self) -> Vec<String>
+    {
+        self.scale.pitches.iter().map(|pitch| pitch.to_string()).collect()
+    }
+
+    /// The text of a .scl file.
+    #[wasm_bindgen(js_name = toScl)]
+    pub fn to_scl(&self) -> String
+    {
+        self.scale.to_string()
+    }
+
+    /// The interval matrix in cents, flattened into a `Float64Array` of rows with one more entry than there are pitches.
+    #[wasm_bindgen(js_name = intervalMatrix)]
+    pub fn interval_matrix(&self) -> Vec<f64>
+    {
+        self.scale.interval_matrix().to_cents().concat()
+    }
+
+    /// One of `"strictly proper"`, `"proper"` or `"improper"`.
+    pub fn propriety(&self) -> String
+    {
+        match self.scale.propriety()
+        {
+            Propriety::StrictlyProper => "strictly proper",
+            Propriety::Proper => "proper",
+            Propriety::Improper => "improper"
+        }.to_string()
+    }
+
+    /// The EDO up to `max_edo` with the smallest mean error.
+    #[wasm_bindgen(js_name = bestEdo)]
+    pub fn best_edo(&self, max_edo: u32) -> Option<u32>
+    {
+        self.scale.best_edos(1..=max_edo, EdoWeighting::Unweighted)
+            .first()
+            .map(|approximation| approximation.edo)
+    }
+}
+
+impl From<Scale> for WasmScale
+{
+    fn from(scale: Scale) -> Self
+    {
+        Self {
+            scale
+        }
+    }
+}
+
+impl From<WasmScale> for Scale
+{
+    fn from(value: WasmScale) -> Self
+    {
+        value.scale
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, WasmScale};
+
+    #[test]
+    fn js_views()
+    {
+        let scale = WasmScale::from(scl!{"tetrachord" 9/8 81/64 4/3});
+
+        assert_eq!(scale.pitches(), ["9/8", "81/64", "4/3"]);
+        assert_eq!(scale.interval_matrix().len(), 12);
+        assert_eq!(scale.propriety(), "strictly proper");
+        assert_eq!(WasmScale::from_cents("12".to_string(), &[100.0, 1200.0]).cents(), [100.0, 1200.0]);
+    }
+}
@@ -0,0 +1,163 @@
+use std::{error::Error, fmt::Display, fs, path::{Path, PathBuf}, sync::mpsc::{self, Receiver}};
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::{KeyboardMapping, Scale, SerdeScalaError};
+
+/// What a watched file parsed to, by its extension.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchedFile
+{
+    Scale(Scale),
+    /// From a `.kbm` file.
+    Mapping(KeyboardMapping)
+}
+
+#[derive(Debug)]
+pub enum WatchError
+{
+    Notify(notify::Error),
+    Load(SerdeScalaError)
+}
+impl From<notify::Error> for WatchError
+{
+    fn from(value: notify::Error) -> Self
+    {
+        Self::Notify(value)
+    }
+}
+impl From<SerdeScalaError> for WatchError
+{
+    fn from(value: SerdeScalaError) -> Self
+    {
+        Self::Load(value)
+    }
+}
+impl Display for WatchError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Self::Notify(err) => write!(f, "could not watch file: {}", err),
+            Self::Load(err) => write!(f, "could not reload file: {}", err)
+        }
+    }
+}
+impl Error for WatchError
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)>
+    {
+        match self
+        {
+            Self::Notify(err) => Some(err),
+            Self::Load(err) => Some(err)
+        }
+    }
+}
+
+fn load(path: &Path) -> Result<WatchedFile, SerdeScalaError>
+{
+    let text = String::from_utf8_lossy(&fs::read(path)?).into_owned();
+    if path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("kbm"))
+    {
+        Ok(WatchedFile::Mapping(text.parse()?))
+    }
+    else
+    {
+        Ok(WatchedFile::Scale(text.parse()?))
+    }
+}
+
+/// Watches a .scl or .kbm file, parsing it again every time it's written to.
+///
+/// The file is parsed once right away, and every result arrives on [`ScaleWatcher::receiver`], including
+/// the errors of edits that don't parse, so a performance can carry on with the last good value.
+pub struct ScaleWatcher
+{
+    path: PathBuf,
+    receiver: Receiver<Result<WatchedFile, WatchError>>,
+    _watcher: RecommendedWatcher
+}
+
+impl ScaleWatcher
+{
+    pub fn new(path: impl AsRef<Path>) -> Result<Self, notify::Error>
+    {
+        let path = fs::canonicalize(path.as_ref())?;
+        let (sender, receiver) = mpsc::channel();
+        let _ = sender.send(load(&path).map_err(WatchError::from));
+
+        // Editors often save by replacing the file, so the directory is watched rather than the file itself.
+        let watched = path.clone();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            let result = match event
+            {
+                Ok(event) =>
+                {
+                    let changed = matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+                        && event.paths.contains(&watched);
+                    if !changed
+                    {
+                        return
+                    }
+                    load(&watched).map_err(WatchError::from)
+                },
+                Err(err) => Err(err.into())
+            };
+            let _ = sender.send(result);
+        })?;
+        let directory = path.parent().unwrap_or(&path);
+        watcher.watch(directory, RecursiveMode::NonRecursive)?;
+
+        Ok(Self {
+            path,
+            receiver,
+            _watcher: watcher
+        })
+    }
+
+    pub fn path(&self) -> &Path
+    {
+        &self.path
+    }
+
+    pub fn receiver(&self) -> &Receiver<Result<WatchedFile, WatchError>>
+    {
+        &self.receiver
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::{fs, time::Duration};
+
+    use crate::{scl, ScaleWatcher, WatchedFile};
+
+    #[test]
+    fn watch_and_reload()
+    {
+        let directory = std::env::temp_dir().join(format!("serde_scala_watch_{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("live.scl");
+        fs::write(&path, "first\n1\n2/1\n").unwrap();
+
+        let watcher = ScaleWatcher::new(&path).unwrap();
+        let first = watcher.receiver().recv().unwrap().unwrap();
+        assert_eq!(first, WatchedFile::Scale(scl!{"first" 2/1}));
+
+        fs::write(&path, "second\n2\n3/2\n2/1\n").unwrap();
+        let second = loop
+        {
+            match watcher.receiver().recv_timeout(Duration::from_secs(10)).unwrap()
+            {
+                Ok(WatchedFile::Scale(scale)) if scale.name == "second" => break scale,
+                _ => continue
+            }
+        };
+        assert_eq!(second, scl!{"second" 3/2 2/1});
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
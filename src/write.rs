@@ -0,0 +1,366 @@
+use std::{fmt::Write, io};
+
+use crate::{Pitch, Scale};
+
+/// The layout of the lines of a written scale.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WriteStyle
+{
+    /// Pitches as they were parsed, or cents to five decimals, at the start of their lines.
+    #[default]
+    Compact,
+    /// The layout the Scala program saves files in, for clean diffs against the Scala archive: an empty comment
+    /// line before the description, and the note count and pitches indented by a space, cents to six decimals.
+    Scala
+}
+
+/// How to write ratio pitches.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RatioFormat
+{
+    /// A numerator and denominator, even for whole numbers like `2/1`.
+    #[default]
+    Fraction,
+    /// Whole numbers without the denominator, like `2`, which Scala reads as the same ratio.
+    ShortWhole,
+    /// Their size in cents, losing their exactness.
+    Cents
+}
+
+/// How to lay out a scale when writing it. The default is what [`Display`](std::fmt::Display) writes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WriteOptions
+{
+    /// Comment lines to start the file with, without the `!`.
+    ///
+    /// `None` writes the file name from the scale's metadata, like `! name.scl`, or `! Generated scale:` without one.
+    pub header: Option<Vec<String>>,
+    pub style: WriteStyle,
+    pub ratios: RatioFormat,
+    /// How many decimals to round cents to, or `None` for the style's own.
+    ///
+    /// Cents are always written with at least one decimal, so that they're never read back as a ratio.
+    pub cents_decimals: Option<usize>
+}
+
+impl WriteOptions
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    pub fn header(mut self, lines: impl IntoIterator<Item = impl Into<String>>) -> Self
+    {
+        self.header = Some(lines.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn style(mut self, style: WriteStyle) -> Self
+    {
+        self.style = style;
+        self
+    }
+
+    pub fn ratios(mut self, ratios: RatioFormat) -> Self
+    {
+        self.ratios = ratios;
+        self
+    }
+
+    pub fn cents_decimals(mut self, decimals: usize) -> Self
+    {
+        self.cents_decimals = Some(decimals);
+        self
+    }
+}
+
+impl Scale
+{
+    /// Writes the scale as a .scl file.
+    ///
+    /// ```
+    /// use serde_scala::{Scale, WriteOptions};
+    ///
+    /// let scale = Scale::edo(2);
+    /// let options = WriteOptions::new().header(["2edo.scl", ""]);
+    ///
+    /// assert!(scale.to_string_with(&options).starts_with("! 2edo.scl\n!\n2-note equal division of octave\n"));
+    /// ```
+    pub fn write_with(&self, f: &mut impl Write, options: &WriteOptions) -> std::fmt::Result
+    {
+        self.write_head(f, options)?;
+
+        let decimals = options.cents_decimals
+            .unwrap_or(match options.style
+            {
+                WriteStyle::Compact => 5,
+                WriteStyle::Scala => 6
+            })
+            .max(1);
+        for (i, pitch) in self.pitches.iter().enumerate()
+        {
+            if options.style == WriteStyle::Scala
+            {
+                write!(f, " ")?;
+            }
+            match (self.source_text(i), pitch, options.ratios)
+            {
+                (Some(text), _, _) if options.style == WriteStyle::Compact && options.cents_decimals.is_none() => write!(f, "{}", text)?,
+                (_, &Pitch::Cents(cents), _) => write!(f, "{:.*}", decimals, cents)?,
+                (_, Pitch::Ratio(_), RatioFormat::Cents) => write!(f, "{:.*}", decimals, pitch.to_cents())?,
+                (_, Pitch::Ratio(ratio), RatioFormat::ShortWhole) if ratio.is_integer() => write!(f, "{}", ratio.numer())?,
+                (_, Pitch::Ratio(ratio), _) => write!(f, "{}/{}", ratio.numer(), ratio.denom())?
+            }
+            match self.label(i)
+            {
+                Some(label) => writeln!(f, " {}", label)?,
+                None => writeln!(f)?
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Everything before the pitches.
+    fn write_head(&self, f: &mut impl Write, options: &WriteOptions) -> std::fmt::Result
+    {
+        match (&options.header, &self.metadata.file_name)
+        {
+            (Some(header), _) => for line in header.iter()
+            {
+                if line.is_empty()
+                {
+                    writeln!(f, "!")?;
+                }
+                else
+                {
+                    writeln!(f, "! {}", line)?;
+                }
+            },
+            (None, Some(file_name)) => writeln!(f, "! {}", file_name)?,
+            (None, None) => writeln!(f, "! Generated scale:")?
+        }
+        self.metadata.write_comments(f)?;
+        match options.style
+        {
+            WriteStyle::Compact =>
+            {
+                writeln!(f, "{}", self.name)?;
+                writeln!(f, "{}", self.pitches.len())?;
+            },
+            WriteStyle::Scala =>
+            {
+                writeln!(f, "!")?;
+                writeln!(f, "{}", self.name)?;
+                writeln!(f, " {}", self.pitches.len())?;
+            }
+        }
+        writeln!(f, "!")
+    }
+
+    pub fn to_string_with(&self, options: &WriteOptions) -> String
+    {
+        let mut s = String::new();
+        self.write_with(&mut s, options).unwrap();
+        s
+    }
+
+    /// Writes the same text as [`Display`](std::fmt::Display) does, formatting the pitches by hand into one buffer
+    /// for exporting many scales at once.
+    ///
+    /// ```
+    /// use serde_scala::Scale;
+    ///
+    /// let mut bytes = vec![];
+    /// Scale::edo(31).write_fast(&mut bytes).unwrap();
+    /// assert_eq!(String::from_utf8(bytes).unwrap(), Scale::edo(31).to_string());
+    /// ```
+    pub fn write_fast(&self, mut w: impl io::Write) -> io::Result<()>
+    {
+        let mut head = String::new();
+        self.write_head(&mut head, &WriteOptions::default()).unwrap();
+        let mut buffer = head.into_bytes();
+        buffer.reserve(self.pitches.len()*12);
+
+        for (i, pitch) in self.pitches.iter().enumerate()
+        {
+            match (self.source_text(i), pitch)
+            {
+                (Some(text), _) => buffer.extend_from_slice(text.as_bytes()),
+                (None, &Pitch::Cents(cents)) => push_cents(&mut buffer, cents),
+                (None, Pitch::Ratio(ratio)) =>
+                {
+                    push_integer(&mut buffer, *ratio.numer());
+                    buffer.push(b'/');
+                    push_integer(&mut buffer, *ratio.denom());
+                }
+            }
+            if let Some(label) = self.label(i)
+            {
+                buffer.push(b' ');
+                buffer.extend_from_slice(label.as_bytes());
+            }
+            buffer.push(b'\n');
+        }
+
+        w.write_all(&buffer)
+    }
+}
+
+fn push_integer(buffer: &mut Vec<u8>, mut n: u128)
+{
+    let mut digits = [0; 39];
+    let mut i = digits.len();
+    loop
+    {
+        i -= 1;
+        digits[i] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0
+        {
+            break
+        }
+    }
+    buffer.extend_from_slice(&digits[i..]);
+}
+
+/// Cents with five decimals like `{:.5}`, falling back to the formatter for values it can't be sure to round the same.
+fn push_cents(buffer: &mut Vec<u8>, cents: f64)
+{
+    let scaled = cents.abs()*1e5;
+    if scaled.is_nan() || scaled >= 1e12 || (scaled.fract() - 0.5).abs() < 1e-3
+    {
+        let mut text = String::new();
+        let _ = write!(text, "{:.5}", cents);
+        buffer.extend_from_slice(text.as_bytes());
+        return
+    }
+
+    let scaled = scaled.round() as u64;
+    if cents.is_sign_negative()
+    {
+        buffer.push(b'-');
+    }
+    push_integer(buffer, (scaled/100_000) as u128);
+    buffer.push(b'.');
+    let fraction = scaled % 100_000;
+    let mut divisor = 10_000;
+    while divisor > 0
+    {
+        buffer.push(b'0' + (fraction/divisor % 10) as u8);
+        divisor /= 10;
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::{scl, Pitch, RatioFormat, Scale, WriteOptions, WriteStyle};
+
+    #[test]
+    fn header_comments()
+    {
+        let scale = scl!{"fifth" 3/2 2/1};
+
+        assert!(scale.to_string().starts_with("! Generated scale:\nfifth\n"));
+        assert!(scale.to_string_with(&WriteOptions::new().header(Vec::<String>::new())).starts_with("fifth\n"));
+
+        let mut named: Scale = scale.to_string_with(&WriteOptions::new().header(["fifth.scl"])).parse().unwrap();
+        assert_eq!(named.metadata.file_name.as_deref(), Some("fifth.scl"));
+        assert!(named.to_string().starts_with("! fifth.scl\nfifth\n"));
+
+        named.metadata.file_name = None;
+        assert!(named.to_string().starts_with("! Generated scale:\n"));
+    }
+
+    #[test]
+    fn scala_style()
+    {
+        let scala = WriteOptions::new().style(WriteStyle::Scala);
+        let text = std::fs::read_to_string("scl/ptolemy.scl").unwrap();
+        let ptolemy: Scale = text.parse().unwrap();
+        assert_eq!(ptolemy.to_string_with(&scala), text);
+
+        let mut labelled = scl!{"cents" 701.955 2/1};
+        labelled.set_label(1, "octave");
+        assert_eq!(labelled.to_string_with(&scala), "! Generated scale:\n!\ncents\n 2\n!\n 701.955000\n 2/1 octave\n");
+    }
+
+    #[test]
+    fn ratio_format()
+    {
+        let scale = scl!{"ratios" 9/8 3/1 4/2};
+        let pitches = |options: WriteOptions| scale.to_string_with(&options).lines().skip(4).map(str::to_string).collect::<Vec<_>>();
+
+        assert_eq!(pitches(WriteOptions::new()), ["9/8", "3/1", "2/1"]);
+        assert_eq!(pitches(WriteOptions::new().ratios(RatioFormat::ShortWhole)), ["9/8", "3", "2"]);
+        assert_eq!(pitches(WriteOptions::new().ratios(RatioFormat::Cents)), ["203.91000", "1901.95500", "1200.00000"]);
+        assert_eq!(pitches(WriteOptions::new().ratios(RatioFormat::Cents).style(WriteStyle::Scala))[1], " 203.910002");
+    }
+
+    #[test]
+    fn cents_keep_their_decimal_point()
+    {
+        // SplitMix64, so every run checks the same values.
+        let mut state = 0x5eed_u64;
+        let mut next = move || {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        };
+        let mut generated = vec![];
+        for _ in 0..500
+        {
+            let sign = if next() % 2 == 0 { 1.0 } else { -1.0 };
+            let unit = (next() >> 11) as f64/(1u64 << 53) as f64;
+            generated.extend([
+                sign*(next() % 10_000) as f64*100.0,
+                sign*(next() % 2_000_000) as f64,
+                sign*unit*1200.0,
+                sign*unit*10f64.powi((next() % 16) as i32),
+                sign*unit*10f64.powi(-((next() % 12) as i32))
+            ]);
+        }
+        let scale = Scale::from_cents(
+            "whole",
+            (-24..=24).map(|i| i as f64*100.0).chain([0.4, 99.96, 1200.0004, -0.04]).chain(generated)
+        );
+        for decimals in 0..=6
+        {
+            for style in [WriteStyle::Compact, WriteStyle::Scala]
+            {
+                let options = WriteOptions::new().style(style).cents_decimals(decimals);
+                let reparsed: Scale = scale.to_string_with(&options).parse().unwrap();
+                assert_eq!(reparsed.pitches.len(), scale.pitches.len());
+                for (pitch, original) in reparsed.pitches.iter().zip(scale.pitches.iter())
+                {
+                    let Pitch::Cents(cents) = pitch
+                    else
+                    {
+                        panic!("{:?} was read back as a ratio", original)
+                    };
+                    let cents_ulp = original.to_cents().abs()*f64::EPSILON;
+                    assert!((cents - original.to_cents()).abs() <= 0.05 + cents_ulp);
+                }
+            }
+        }
+        assert_eq!(scale.to_string_with(&WriteOptions::new().cents_decimals(0)).lines().nth(4), Some("-2400.0"));
+    }
+
+    #[test]
+    fn write_fast()
+    {
+        let mut labelled = scl!{"mixed" 9/8 701.955 -0.000001 1e-9 2.000005 1234567.5 2/1};
+        labelled.set_label(0, "tone");
+        labelled.pitches.push(Pitch::Ratio(u128::MAX.into()));
+        for scale in [labelled, Scale::edo(12), Scale::edo(313), "parsed\n2\n100.0 cents\n2/1\n".parse().unwrap()]
+        {
+            let mut bytes = vec![];
+            scale.write_fast(&mut bytes).unwrap();
+            assert_eq!(String::from_utf8(bytes).unwrap(), scale.to_string());
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use num_rational::Ratio;
+use rkyv::{rancor, util::AlignedVec, vec::ArchivedVec, Archive, Deserialize, Serialize};
+
+use crate::{ArchivedScale, Scale};
+
+/// How ratio pitches are archived, as their numerator and denominator.
+#[doc(hidden)]
+#[derive(Archive, Serialize, Deserialize)]
+#[rkyv(remote = Ratio<u128>, archived = ArchivedRatio)]
+pub struct RatioDef
+{
+    #[rkyv(getter = Ratio::numer)]
+    pub numer: u128,
+    #[rkyv(getter = Ratio::denom)]
+    pub denom: u128
+}
+
+impl From<RatioDef> for Ratio<u128>
+{
+    fn from(value: RatioDef) -> Self
+    {
+        Ratio::new_raw(value.numer, value.denom)
+    }
+}
+
+/// Archives scales in one buffer, to be written out and later accessed in place with [`access_scales`].
+pub fn scales_to_bytes(scales: &[Scale]) -> Result<AlignedVec, rancor::Error>
+{
+    rkyv::to_bytes::<rancor::Error>(&scales.to_vec())
+}
+
+/// Checks and accesses scales archived by [`scales_to_bytes`], such as a memory-mapped file, without copying them.
+pub fn access_scales(bytes: &[u8]) -> Result<&ArchivedVec<ArchivedScale>, rancor::Error>
+{
+    rkyv::access::<ArchivedVec<ArchivedScale>, rancor::Error>(bytes)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use rkyv::rancor;
+
+    use crate::{access_scales, scales_to_bytes, scl, ArchivedPitch, KeyboardMapping, Scale};
+
+    #[test]
+    fn zero_copy()
+    {
+        let mut ji = scl!{"ji" 9/8 5/4 2/1};
+        ji.set_label(1, "third");
+        let scales = [ji, Scale::edo(12)];
+        let bytes = scales_to_bytes(&scales).unwrap();
+
+        let archived = access_scales(&bytes).unwrap();
+        assert_eq!(archived.len(), 2);
+        assert_eq!(archived[0].name, "ji");
+        let ArchivedPitch::Ratio(third) = &archived[0].pitches[1]
+        else
+        {
+            panic!("expected a ratio")
+        };
+        assert_eq!((third.numer.to_native(), third.denom.to_native()), (5, 4));
+        assert_eq!(archived[1].pitches.len(), 12);
+
+        let deserialized: Vec<Scale> = rkyv::deserialize::<_, rancor::Error>(archived).unwrap();
+        assert_eq!(deserialized, scales);
+
+        let mapping = KeyboardMapping::default();
+        let bytes = rkyv::to_bytes::<rancor::Error>(&mapping).unwrap();
+        assert_eq!(rkyv::from_bytes::<KeyboardMapping, rancor::Error>(&bytes).unwrap(), mapping);
+    }
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "heapless")]
+
+use std::{alloc::{GlobalAlloc, Layout, System}, cell::Cell};
+
+use serde_scala::{HeaplessScale, Pitch};
+
+/// Counts the allocations made on each thread, so tests running alongside don't count.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator
+{
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8
+    {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout)
+    {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn parses_without_allocating()
+{
+    let text = "! spaced.scl\n!\nSpaced out\n 1 2\n!\n3 / 2\n5 /4 third\n-100.0\n700.0 cents\n1200.0\n1.0e3\n9/8\n4/3\n5/3\n15/8\n16/15\n2/1\n";
+
+    let before = ALLOCATIONS.with(Cell::get);
+    let scale: HeaplessScale<16> = text.parse().unwrap();
+    assert_eq!(ALLOCATIONS.with(Cell::get), before);
+
+    assert_eq!(scale.pitches[..3], [Pitch::ratio(3, 2), Pitch::ratio(5, 4), Pitch::Cents(-100.0)]);
+    assert_eq!(scale.pitches[4], Pitch::Cents(1200.0));
+}